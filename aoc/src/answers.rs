@@ -0,0 +1,127 @@
+//! Recorded answers to the real inputs, used to catch regressions when refactoring.
+//!
+//! The answers of each day are stored in the `answers` file next to its `input`, one part per line:
+//!
+//! ```text
+//! part1: 54630
+//! part2: 54770
+//! ```
+
+use crate::days::Part;
+use common::parse::{one_of, parse_lines, tag, IResult};
+use common::ParseError;
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::space0,
+    sequence::{preceded, separated_pair, tuple},
+};
+
+/// Expected answers of a day, for the parts which have been recorded.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl ExpectedAnswers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+fn parse_answer(input: &str) -> IResult<&str, (char, &str)> {
+    separated_pair(
+        preceded(tag("part"), one_of("12")),
+        tuple((space0, tag(":"), space0)),
+        take_till1(char::is_whitespace),
+    )(input)
+}
+
+pub fn parse_answers(input: &str) -> Result<ExpectedAnswers, ParseError> {
+    let mut answers = ExpectedAnswers::default();
+
+    for (part, answer) in parse_lines(input, parse_answer)? {
+        match part {
+            '1' => answers.part1 = Some(answer.to_string()),
+            _ => answers.part2 = Some(answer.to_string()),
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Result of comparing the answer to a part with its recorded answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The answer was not recorded yet.
+    Missing {
+        actual: String,
+    },
+    /// The day has no such part, and no answer was recorded for it.
+    Skipped,
+}
+
+pub fn check(expected: Option<&str>, actual: Option<&str>) -> Outcome {
+    match (expected, actual) {
+        (Some(expected), Some(actual)) if expected == actual => Outcome::Pass,
+        (Some(expected), actual) => Outcome::Fail {
+            expected: expected.to_string(),
+            actual: actual.map(String::from),
+        },
+        (None, Some(actual)) => Outcome::Missing {
+            actual: actual.to_string(),
+        },
+        (None, None) => Outcome::Skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_answers() {
+        let input = indoc! {"
+            part1: 54630
+            part2:  -1131
+        "};
+
+        let answers = parse_answers(input).unwrap();
+        assert_eq!(answers.get(Part::One), Some("54630"));
+        assert_eq!(answers.get(Part::Two), Some("-1131"));
+
+        let answers = parse_answers("part1: 606062\n").unwrap();
+        assert_eq!(answers.get(Part::Two), None);
+
+        let error = parse_answers("part3: 1\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected.as_str()),
+            (1, 5, "one of \"12\"")
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(Some("42"), Some("42")), Outcome::Pass);
+        assert_matches!(
+            check(Some("42"), Some("43")),
+            Outcome::Fail {
+                actual: Some(_),
+                ..
+            }
+        );
+        assert_matches!(check(Some("42"), None), Outcome::Fail { actual: None, .. });
+        assert_matches!(check(None, Some("43")), Outcome::Missing { .. });
+        assert_eq!(check(None, None), Outcome::Skipped);
+    }
+}
@@ -0,0 +1,223 @@
+//! Dispatch table mapping a day number to the solution of its crate.
+
+use common::Solution;
+use rayon::prelude::*;
+use std::error::Error;
+
+/// Which part of a day's puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// Answers to the requested parts, in the same order. `None` means the day has no such part.
+pub type Answers = Vec<Option<String>>;
+
+/// Operation on the solution of a day, generic over the day, so that it can be run on the day
+/// selected at runtime through [`with_solution`].
+pub trait WithSolution {
+    type Output;
+
+    fn run<S: Solution>(self) -> Self::Output;
+}
+
+/// Run the operation on the solution of the given day. Return `None` if there is no such day.
+pub fn with_solution<W: WithSolution>(day: u8, op: W) -> Option<W::Output> {
+    let output = match day {
+        1 => op.run::<day01_trebuchet::Day01>(),
+        2 => op.run::<day02_cube_conondrum::Day02>(),
+        3 => op.run::<day03_gear_ratios::Day03>(),
+        4 => op.run::<day04_scratchcards::Day04>(),
+        5 => op.run::<day05_garden::Day05>(),
+        6 => op.run::<day06_boat_race::Day06>(),
+        7 => op.run::<day07_camel_cards::Day07>(),
+        8 => op.run::<day08_wasteland::Day08>(),
+        9 => op.run::<day09_mirage::Day09>(),
+        10 => op.run::<day10_pipe_maze::Day10>(),
+        11 => op.run::<day11_cosmic_exp::Day11>(),
+        12 => op.run::<day12_hot_springs::Day12>(),
+        13 => op.run::<day13_mirrors::Day13>(),
+        14 => op.run::<day14_tilted_rocks::Day14>(),
+        15 => op.run::<day15_lens_lib::Day15>(),
+        16 => op.run::<day16_beams::Day16>(),
+        17 => op.run::<day17_crucible::Day17>(),
+        18 => op.run::<day18_lava_dig::Day18>(),
+        19 => op.run::<day19_workflows::Day19>(),
+        20 => op.run::<day20_pulse_logic::Day20>(),
+        21 => op.run::<day21_step_counter::Day21>(),
+        22 => op.run::<day22_bricks::Day22>(),
+        23 => op.run::<day23_long_walk::Day23>(),
+        24 => op.run::<day24_hail::Day24>(),
+        25 => op.run::<day25_snowverload::Day25>(),
+        _ => return None,
+    };
+    Some(output)
+}
+
+/// Parse the input once and solve each of the given parts on it, at the same time if there are
+/// free threads in the pool.
+struct Solve<'a> {
+    input: &'a str,
+    parts: &'a [Part],
+}
+
+impl WithSolution for Solve<'_> {
+    type Output = Result<Answers, Box<dyn Error>>;
+
+    fn run<S: Solution>(self) -> Self::Output {
+        let input = S::parse(self.input)?;
+
+        let answers = self
+            .parts
+            .par_iter()
+            .map(|part| match part {
+                Part::One => Some(S::part1(&input).to_string()),
+                Part::Two => S::part2(&input).map(|answer| answer.to_string()),
+            })
+            .collect();
+
+        Ok(answers)
+    }
+}
+
+/// Solve the given parts of the given day.
+pub fn solve(day: u8, input: &str, parts: &[Part]) -> Result<Answers, Box<dyn Error>> {
+    with_solution(day, Solve { input, parts })
+        .unwrap_or_else(|| Err(format!("There is no solution for day {day}").into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::ReadError;
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    /// Only parse the input, since the solutions may rightly take forever on broken inputs.
+    struct Parse<'a> {
+        input: &'a str,
+    }
+
+    impl WithSolution for Parse<'_> {
+        type Output = ();
+
+        fn run<S: Solution>(self) -> Self::Output {
+            let _ = S::parse(self.input);
+        }
+    }
+
+    /// Break the input in a few random places: replace, insert or remove characters, cut it short,
+    /// or put numbers which are zero, negative or too large in it.
+    fn mutate(rng: &mut impl Rng, input: &str) -> String {
+        const CHARS: &[char] = &[
+            '0', '1', '9', '-', '+', ' ', '\n', '\r', '\t', '.', '#', '?', 'S', 'O', '|', '7', 'J',
+            '/', '\\', '<', '>', '^', 'v', ',', ':', '=', '{', '}', '(', ')', '%', '&', 'a', 'z',
+            'A', 'Z', 'R', 'é', '🦀',
+        ];
+        const NUMBERS: &[&str] = &["0", "-1", "4294967296", "9223372036854775807"];
+        let mut chars: Vec<char> = input.chars().collect();
+
+        for _ in 0..rng.gen_range(1..=3) {
+            let index = rng.gen_range(0..=chars.len());
+            let ch = *CHARS.choose(rng).unwrap();
+            match rng.gen_range(0..5) {
+                0 if index < chars.len() => chars[index] = ch,
+                1 => chars.insert(index, ch),
+                2 if index < chars.len() => drop(chars.remove(index)),
+                3 => chars.truncate(index),
+                _ => {
+                    // Replace the digits from the index on with a number that is out of range
+                    let digits = chars[index..]
+                        .iter()
+                        .take_while(|ch| ch.is_ascii_digit())
+                        .count();
+                    let number = NUMBERS.choose(rng).unwrap();
+                    chars
+                        .splice(index..index + digits, number.chars())
+                        .for_each(drop);
+                },
+            }
+        }
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn test_parse_broken_inputs() {
+        let mut rng = generator::rng(0);
+
+        for day in 1..=25 {
+            for input in ["", "\n", "\n\n", " ", "🦀"] {
+                with_solution(day, Parse { input });
+            }
+
+            for seed in 0..20 {
+                let input = generator::generate(day, seed, 2).unwrap();
+                for _ in 0..20 {
+                    let input = mutate(&mut rng, &input);
+                    with_solution(day, Parse { input: &input });
+                }
+            }
+        }
+    }
+
+    /// Solve both parts of the day with its solution which reads the input one line at a time.
+    fn solve_reader(day: u8, input: &str) -> Result<Answers, ReadError> {
+        let answers = |part1: &dyn ToString, part2: &dyn ToString| {
+            vec![Some(part1.to_string()), Some(part2.to_string())]
+        };
+        let reader = input.as_bytes();
+
+        Ok(match day {
+            4 => {
+                let (part1, part2) = day04_scratchcards::solve_reader(reader)?;
+                answers(&part1, &part2)
+            },
+            9 => {
+                let (part1, part2) = day09_mirage::solve_reader(reader)?;
+                answers(&part1, &part2)
+            },
+            12 => {
+                let (part1, part2) = day12_hot_springs::solve_reader(reader)?;
+                answers(&part1, &part2)
+            },
+            _ => panic!("Day {day} has no reader"),
+        })
+    }
+
+    #[test]
+    fn test_reader_on_generated_inputs() {
+        for day in [4, 9, 12] {
+            for seed in 0..5 {
+                let input = generator::generate(day, seed, 10).unwrap();
+                let expected = solve(day, &input, &[Part::One, Part::Two]).unwrap();
+                let result = solve_reader(day, &input).unwrap();
+                assert_eq!(result, expected, "Day {day}, seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_generated_inputs() {
+        for day in 1..=25 {
+            for seed in 0..3 {
+                let input = generator::generate(day, seed, 5).unwrap();
+                let result = solve(day, &input, &[Part::One, Part::Two]);
+                assert!(
+                    result.is_ok(),
+                    "Day {day}, seed {seed}: {}\n{input}",
+                    result.unwrap_err()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Pool of threads for solving several days at the same time, with the output kept in order.
+
+use rayon::ThreadPoolBuilder;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::sync::mpsc;
+
+/// Run `work` on each task on a pool of `jobs` threads, or one thread per CPU if `jobs` is 0.
+///
+/// The outputs are passed to `consume` on the calling thread in the order of the tasks, each as
+/// soon as it and all the tasks before it have finished, so the output can be printed while the
+/// later tasks are still running. Work started on the pool, e.g. with `rayon::join`, also runs on
+/// its threads.
+pub fn run_ordered<T, O>(
+    jobs: usize,
+    tasks: Vec<T>,
+    work: impl Fn(T) -> O + Sync,
+    mut consume: impl FnMut(O),
+) -> Result<(), Box<dyn Error>>
+where
+    T: Send,
+    O: Send,
+{
+    let pool = ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    let (sender, receiver) = mpsc::channel();
+    let work = &work;
+
+    pool.in_place_scope(|scope| {
+        for (index, task) in tasks.into_iter().enumerate() {
+            let sender = sender.clone();
+            scope.spawn(move |_| {
+                // The receiver only goes away if `consume` panicked, which is reported anyway
+                let _ = sender.send((index, work(task)));
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut next = 0;
+        for (index, output) in receiver {
+            finished.insert(index, output);
+            while let Some(output) = finished.remove(&next) {
+                consume(output);
+                next += 1;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_ordered() {
+        let tasks: Vec<u64> = (0..20).collect();
+        let mut outputs = Vec::new();
+
+        run_ordered(
+            4,
+            tasks,
+            |task| {
+                // Later tasks finish first
+                thread::sleep(Duration::from_millis(20 - task));
+                task * 2
+            },
+            |output| outputs.push(output),
+        )
+        .unwrap();
+
+        assert_eq!(outputs, (0..20).map(|task| task * 2).collect::<Vec<_>>());
+    }
+}
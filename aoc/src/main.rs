@@ -0,0 +1,424 @@
+//! Single entry point for running the solution of any day, or a range of days.
+//!
+//! By default the input of each day is read from the `input` file in the directory of its crate,
+//! so the runner can be invoked from anywhere in the workspace.
+
+mod alloc;
+mod answers;
+mod days;
+mod jobs;
+mod records;
+mod report;
+
+use answers::{ExpectedAnswers, Outcome};
+use clap::{Parser, Subcommand, ValueEnum};
+use common::{day_dir, InputSource};
+use days::{Answers, Part};
+use records::{ErrorKind, Record};
+use std::error::Error;
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::process::ExitCode;
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one or more days and print the answers.
+    Run {
+        /// Days to run, e.g. `17`, `1-5` or `1,3,20-25`. Runs all days if omitted.
+        #[arg(short, long, value_parser = parse_days)]
+        day: Option<Days>,
+
+        /// Part to run. Runs both parts if omitted.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Path to the input file, or `-` to read from stdin. Only valid for a single day.
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Output format of the answers. In the JSON and CSV formats, errors and panics are
+        /// reported as records and don't stop the run.
+        #[arg(short, long, value_enum, default_value_t = RunFormat::Text)]
+        format: RunFormat,
+
+        /// Number of threads solving days and parts at the same time. Uses one thread per CPU if
+        /// 0. The answers are printed in order either way.
+        #[arg(short, long, default_value_t = 0)]
+        jobs: usize,
+    },
+    /// Solve one or more days and compare the answers with the ones recorded in their `answers`
+    /// files.
+    Check {
+        /// Days to check, e.g. `17`, `1-5` or `1,3,20-25`. Checks all days if omitted.
+        #[arg(short, long, value_parser = parse_days)]
+        day: Option<Days>,
+
+        /// Number of threads solving days and parts at the same time. Uses one thread per CPU if
+        /// 0.
+        #[arg(short, long, default_value_t = 0)]
+        jobs: usize,
+    },
+    /// Solve one or more days, measuring the time and the peak memory of parsing and of each part.
+    Report {
+        /// Days to measure, e.g. `17`, `1-5` or `1,3,20-25`. Measures all days if omitted.
+        #[arg(short, long, value_parser = parse_days)]
+        day: Option<Days>,
+
+        /// Output format of the report.
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+    /// Generate a random input for a day and print it, e.g. to benchmark or test the solution on
+    /// larger inputs.
+    Gen {
+        /// Day to generate the input for.
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Seed of the random generator. The same seed always gives the same input.
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// Size of the input relative to the real input, in percent.
+        #[arg(long, default_value_t = 100)]
+        scale: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RunFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Json,
+}
+
+/// Sorted list of distinct days to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Days(Vec<u8>);
+
+/// Parse a comma separated list of days or inclusive ranges of days.
+fn parse_days(spec: &str) -> Result<Days, String> {
+    let mut days = Vec::new();
+
+    for item in spec.split(',') {
+        let item = item.trim();
+        let parse_day = |s: &str| -> Result<u8, String> {
+            match s.trim().parse::<u8>() {
+                Ok(day) if (1..=25).contains(&day) => Ok(day),
+                _ => Err(format!("`{s}` is not a day between 1 and 25")),
+            }
+        };
+
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("`{item}` is an empty range"));
+                }
+                days.extend(start..=end);
+            },
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(Days(days))
+}
+
+fn read_file(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()).into())
+}
+
+/// Read the input for the given day from the given path, from stdin if the path is `-`, or from
+/// the default location in the crate directory if no path is given.
+fn read_input(day: u8, input: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let source = match input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::File(day_dir(day)?.join("input")),
+    };
+    Ok(source.read_to_string()?)
+}
+
+/// Read the recorded answers of the given day. A missing file means that no answers were recorded.
+fn read_answers(day: u8) -> Result<ExpectedAnswers, Box<dyn Error>> {
+    let path = day_dir(day)?.join("answers");
+    if !path.exists() {
+        return Ok(ExpectedAnswers::default());
+    }
+
+    let text = read_file(&path)?;
+    answers::parse_answers(&text).map_err(|e| format!("Invalid {}: {e}", path.display()).into())
+}
+
+/// Number of parts with each outcome of the check.
+#[derive(Debug, Default)]
+struct CheckSummary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Read the recorded answers of a single day and solve both of its parts. The error is turned into
+/// a message, so that it can be sent from the thread which solved the day.
+fn solve_check(day: u8) -> Result<(ExpectedAnswers, Answers), String> {
+    let parts = [Part::One, Part::Two];
+
+    let result = read_answers(day).and_then(|expected| {
+        let input = read_input(day, None)?;
+        Ok((expected, days::solve(day, &input, &parts)?))
+    });
+    result.map_err(|err| err.to_string())
+}
+
+/// Compare the answers of a single day with the recorded answers, printing the outcome of each
+/// part. Errors count as failures.
+fn check_day(
+    day: u8,
+    result: Result<(ExpectedAnswers, Answers), String>,
+    summary: &mut CheckSummary,
+) {
+    let parts = [Part::One, Part::Two];
+
+    let (expected, actual) = match result {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("Day {day}: {err}");
+            summary.failed += 1;
+            return;
+        },
+    };
+
+    for (part, actual) in parts.into_iter().zip(actual) {
+        let prefix = format!("Day {day}, part {}", part.number());
+
+        match answers::check(expected.get(part), actual.as_deref()) {
+            Outcome::Pass => {
+                println!("{prefix}: pass");
+                summary.passed += 1;
+            },
+            Outcome::Fail { expected, actual } => {
+                println!("{prefix}: FAIL");
+                println!("  - {expected}");
+                println!("  + {}", actual.as_deref().unwrap_or("(no answer)"));
+                summary.failed += 1;
+            },
+            Outcome::Missing { actual } => {
+                println!("{prefix}: missing (answer is {actual})");
+                summary.missing += 1;
+            },
+            Outcome::Skipped => (),
+        }
+    }
+}
+
+/// Solve the requested parts of a single day. The error is turned into a message, so that it can
+/// be sent from the thread which solved the day.
+fn solve_day(day: u8, parts: &[Part], input: Option<&str>) -> Result<Answers, String> {
+    let result = read_input(day, input).and_then(|input| days::solve(day, &input, parts));
+    result.map_err(|err| err.to_string())
+}
+
+/// Print the answers to the requested parts of a single day. Return whether all of them succeeded.
+fn print_day(day: u8, parts: &[Part], result: Result<Answers, String>) -> bool {
+    let answers = match result {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Day {day}: {err}");
+            return false;
+        },
+    };
+
+    let mut ok = true;
+    for (part, answer) in parts.iter().zip(answers) {
+        match answer {
+            Some(answer) => println!("Day {day}, part {}: {answer}", part.number()),
+            None if parts.len() > 1 => (),
+            None => {
+                eprintln!("Day {day}, part {}: no such part", part.number());
+                ok = false;
+            },
+        }
+    }
+
+    ok
+}
+
+/// Solve the requested parts of a single day, recording the answers and errors instead of printing
+/// them.
+fn record_day(day: u8, parts: &[Part], input: Option<&str>) -> Vec<Record> {
+    match read_input(day, input) {
+        Ok(input) => records::solve(day, &input, parts),
+        Err(err) => vec![Record::error(day, None, ErrorKind::Input, err.to_string())],
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            jobs,
+        } => {
+            let Days(days) = day.unwrap_or_else(|| Days((1..=25).collect()));
+            if input.is_some() && days.len() != 1 {
+                eprintln!("--input can only be used when running a single day");
+                return ExitCode::FAILURE;
+            }
+
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(_) => vec![Part::Two],
+                None => vec![Part::One, Part::Two],
+            };
+
+            let input = input.as_deref();
+            let mut ok = true;
+            if format == RunFormat::Text {
+                let result = jobs::run_ordered(
+                    jobs,
+                    days,
+                    |day| (day, solve_day(day, &parts, input)),
+                    |(day, result)| ok &= print_day(day, &parts, result),
+                );
+                if let Err(err) = result {
+                    eprintln!("Cannot start the threads: {err}");
+                    return ExitCode::FAILURE;
+                }
+            } else {
+                // The panics are reported in the records, so don't also print them to stderr
+                panic::set_hook(Box::new(|_| ()));
+
+                let mut records = Vec::new();
+                let result = jobs::run_ordered(
+                    jobs,
+                    days,
+                    |day| record_day(day, &parts, input),
+                    |day_records| records.extend(day_records),
+                );
+                if let Err(err) = result {
+                    eprintln!("Cannot start the threads: {err}");
+                    return ExitCode::FAILURE;
+                }
+                ok = !records.iter().any(Record::is_error);
+
+                match format {
+                    RunFormat::Json => println!("{}", records::format_json(&records)),
+                    _ => match records::format_csv(&records) {
+                        Ok(csv) => print!("{csv}"),
+                        Err(err) => {
+                            eprintln!("Cannot format the answers as CSV: {err}");
+                            ok = false;
+                        },
+                    },
+                }
+            }
+
+            if ok {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        },
+        Command::Check { day, jobs } => {
+            let Days(days) = day.unwrap_or_else(|| Days((1..=25).collect()));
+
+            let mut summary = CheckSummary::default();
+            let result = jobs::run_ordered(
+                jobs,
+                days,
+                |day| (day, solve_check(day)),
+                |(day, result)| check_day(day, result, &mut summary),
+            );
+            if let Err(err) = result {
+                eprintln!("Cannot start the threads: {err}");
+                return ExitCode::FAILURE;
+            }
+
+            let CheckSummary {
+                passed,
+                failed,
+                missing,
+            } = summary;
+            println!("{passed} passed, {failed} failed, {missing} missing");
+
+            if failed == 0 {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        },
+        Command::Report { day, format } => {
+            let Days(days) = day.unwrap_or_else(|| Days((1..=25).collect()));
+
+            let mut ok = true;
+            let mut reports = Vec::new();
+            for day in days {
+                match read_input(day, None).and_then(|input| report::report(day, &input)) {
+                    Ok(report) => reports.push(report),
+                    Err(err) => {
+                        eprintln!("Day {day}: {err}");
+                        ok = false;
+                    },
+                }
+            }
+
+            match format {
+                ReportFormat::Table => print!("{}", report::format_table(&reports)),
+                ReportFormat::Json => println!("{}", report::format_json(&reports)),
+            }
+
+            if ok {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        },
+        Command::Gen { day, seed, scale } => {
+            let input = generator::generate(day, seed, scale).expect("The day is between 1 and 25");
+            print!("{input}");
+            ExitCode::SUCCESS
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("17").unwrap(), Days(vec![17]));
+        assert_eq!(parse_days("1-3").unwrap(), Days(vec![1, 2, 3]));
+        assert_eq!(
+            parse_days("20-22,1,3,21").unwrap(),
+            Days(vec![1, 3, 20, 21, 22])
+        );
+        assert_matches!(parse_days("0"), Err(_));
+        assert_matches!(parse_days("26"), Err(_));
+        assert_matches!(parse_days("5-3"), Err(_));
+        assert_matches!(parse_days("x"), Err(_));
+    }
+}
@@ -0,0 +1,312 @@
+//! Answers of each part as records for scripts and dashboards, printed as JSON or CSV.
+//!
+//! Unlike the text output of the `run` command, a failing day does not stop the run: errors, and
+//! panics of the solutions, are recorded in place of the answers and the next day is solved.
+
+use crate::days::{self, Part, WithSolution};
+use common::Solution;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::any::{self, Any};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+/// Answer to a single part of a day, or the error which prevented it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    /// `None` for errors which affect the whole day, e.g. when its input cannot be parsed.
+    pub part: Option<u8>,
+    #[serde(flatten)]
+    pub result: PartResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PartResult {
+    Answer {
+        answer: String,
+        /// Rust type of the answer, e.g. `u64`.
+        answer_type: &'static str,
+        /// Time it took to solve the part, without parsing the input. The parts of a day may be
+        /// solved at the same time, so this is the wall time of the part on its own thread.
+        seconds: f64,
+    },
+    Error {
+        error: ErrorRecord,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorRecord {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The input could not be read.
+    Input,
+    /// The input is not valid.
+    Parse,
+    /// The solution panicked.
+    Panic,
+    /// The day has no such part.
+    NoSuchPart,
+}
+
+impl ErrorKind {
+    /// Name of the kind, the same as in JSON.
+    fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Input => "input",
+            ErrorKind::Parse => "parse",
+            ErrorKind::Panic => "panic",
+            ErrorKind::NoSuchPart => "no_such_part",
+        }
+    }
+}
+
+impl Record {
+    pub fn error(day: u8, part: Option<u8>, kind: ErrorKind, message: impl Into<String>) -> Record {
+        let error = ErrorRecord {
+            kind,
+            message: message.into(),
+        };
+        Record {
+            day,
+            part,
+            result: PartResult::Error { error },
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.result, PartResult::Error { .. })
+    }
+}
+
+/// Message of the panic, which is either a `&str` or a `String` unless the panic was raised with
+/// `panic_any`.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "Panicked with a non-string payload".to_string(),
+        },
+    }
+}
+
+/// Run the function, turning a panic into an error record.
+fn catch_panic<T>(day: u8, part: Option<u8>, f: impl FnOnce() -> T) -> Result<T, Record> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|payload| Record::error(day, part, ErrorKind::Panic, panic_message(payload)))
+}
+
+struct Records<'a> {
+    input: &'a str,
+    parts: &'a [Part],
+}
+
+impl WithSolution for Records<'_> {
+    type Output = Vec<Record>;
+
+    fn run<S: Solution>(self) -> Self::Output {
+        let day = S::DAY;
+
+        let input = match catch_panic(day, None, || S::parse(self.input)) {
+            Ok(Ok(input)) => input,
+            Ok(Err(err)) => {
+                return vec![Record::error(day, None, ErrorKind::Parse, err.to_string())];
+            },
+            Err(record) => return vec![record],
+        };
+
+        let records: Vec<Option<Record>> = self
+            .parts
+            .par_iter()
+            .map(|&part| {
+                let number = Some(part.number());
+                let start = Instant::now();
+
+                let answer = catch_panic(day, number, || match part {
+                    Part::One => Some(S::part1(&input).to_string()),
+                    Part::Two => S::part2(&input).map(|answer| answer.to_string()),
+                });
+                let seconds = start.elapsed().as_secs_f64();
+
+                match answer {
+                    Ok(Some(answer)) => Some(Record {
+                        day,
+                        part: number,
+                        result: PartResult::Answer {
+                            answer,
+                            answer_type: any::type_name::<S::Output>(),
+                            seconds,
+                        },
+                    }),
+                    // Like the text output, only asking for the missing part explicitly is an error
+                    Ok(None) if self.parts.len() > 1 => None,
+                    Ok(None) => {
+                        let message = "No such part";
+                        Some(Record::error(day, number, ErrorKind::NoSuchPart, message))
+                    },
+                    Err(record) => Some(record),
+                }
+            })
+            .collect();
+
+        records.into_iter().flatten().collect()
+    }
+}
+
+/// Solve the given parts of the given day, recording the answer or the error of each part.
+pub fn solve(day: u8, input: &str, parts: &[Part]) -> Vec<Record> {
+    days::with_solution(day, Records { input, parts }).unwrap_or_else(|| {
+        let message = format!("There is no solution for day {day}");
+        vec![Record::error(day, None, ErrorKind::Input, message)]
+    })
+}
+
+/// Format the records as a JSON array.
+pub fn format_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("Records are always serializable")
+}
+
+/// Format the records as CSV with a header. Fields which don't apply to a record are left empty.
+pub fn format_csv(records: &[Record]) -> Result<String, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record([
+        "day",
+        "part",
+        "answer",
+        "answer_type",
+        "seconds",
+        "error_kind",
+        "error",
+    ])?;
+
+    for record in records {
+        let day = record.day.to_string();
+        let part = record.part.map_or(String::new(), |part| part.to_string());
+
+        let fields: [&str; 7] = match &record.result {
+            PartResult::Answer {
+                answer,
+                answer_type,
+                seconds,
+            } => [
+                &day,
+                &part,
+                answer,
+                answer_type,
+                &seconds.to_string(),
+                "",
+                "",
+            ],
+            PartResult::Error { error } => {
+                [&day, &part, "", "", "", error.kind.name(), &error.message]
+            },
+        };
+        writer.write_record(fields)?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use indoc::indoc;
+
+    const DAY9_INPUT: &str = indoc! {"
+        0 3 6 9 12 15
+        1 3 6 10 15 21
+        10 13 16 21 30 45
+    "};
+
+    #[test]
+    fn test_solve() {
+        let records = solve(9, DAY9_INPUT, &[Part::One, Part::Two]);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].part, Some(2));
+        assert_matches!(
+            &records[0].result,
+            PartResult::Answer { answer, answer_type: "i32", .. } if answer == "114"
+        );
+
+        let records = solve(9, "0 x\n", &[Part::One, Part::Two]);
+        assert_matches!(records.as_slice(), [Record { part: None, .. }]);
+        assert_matches!(
+            &records[0].result,
+            PartResult::Error { error } if error.kind == ErrorKind::Parse
+        );
+
+        let records = solve(25, "abc: def\n", &[Part::Two]);
+        assert_matches!(
+            &records[0].result,
+            PartResult::Error { error } if error.kind == ErrorKind::NoSuchPart
+        );
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        let record = catch_panic(24, Some(2), || panic!("Brute force failed")).unwrap_err();
+        assert_eq!(
+            record,
+            Record::error(24, Some(2), ErrorKind::Panic, "Brute force failed")
+        );
+
+        let record = catch_panic(24, None, || panic!("{} failed", "Parsing")).unwrap_err();
+        assert_matches!(
+            &record.result,
+            PartResult::Error { error } if error.message == "Parsing failed"
+        );
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: Some(1),
+                result: PartResult::Answer {
+                    answer: "54630".to_string(),
+                    answer_type: "u32",
+                    seconds: 0.5,
+                },
+            },
+            Record::error(
+                24,
+                Some(2),
+                ErrorKind::Panic,
+                "Brute force failed, try again",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_format_json() {
+        let json: serde_json::Value = serde_json::from_str(&format_json(&records())).unwrap();
+
+        assert_eq!(json[0]["day"], 1);
+        assert_eq!(json[0]["answer"], "54630");
+        assert_eq!(json[0]["answer_type"], "u32");
+        assert_eq!(json[0]["seconds"], 0.5);
+        assert_eq!(json[1]["part"], 2);
+        assert_eq!(json[1]["error"]["kind"], "panic");
+        assert_eq!(json[1]["error"]["message"], "Brute force failed, try again");
+    }
+
+    #[test]
+    fn test_format_csv() {
+        let expected = indoc! {r#"
+            day,part,answer,answer_type,seconds,error_kind,error
+            1,1,54630,u32,0.5,,
+            24,2,,,,panic,"Brute force failed, try again"
+        "#};
+        assert_eq!(format_csv(&records()).unwrap(), expected);
+    }
+}
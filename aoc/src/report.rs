@@ -0,0 +1,241 @@
+//! Time and peak memory usage of each stage of the solution of a day: parsing and both parts.
+
+use crate::alloc;
+use crate::days::{self, WithSolution};
+use common::Solution;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// Measurement of a single stage.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Stage {
+    #[serde(rename = "seconds", serialize_with = "serialize_seconds")]
+    pub time: Duration,
+    /// Peak memory allocated during the stage, on top of what was allocated before it started.
+    pub peak_bytes: usize,
+}
+
+fn serialize_seconds<S: serde::Serializer>(time: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(time.as_secs_f64())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse: Stage,
+    pub part1: Stage,
+    /// `None` if the day has no second part.
+    pub part2: Option<Stage>,
+}
+
+impl DayReport {
+    pub fn total_time(&self) -> Duration {
+        self.parse.time + self.part1.time + self.part2.map_or(Duration::ZERO, |stage| stage.time)
+    }
+
+    pub fn peak_bytes(&self) -> usize {
+        let stages = [Some(self.parse), Some(self.part1), self.part2];
+        stages
+            .into_iter()
+            .flatten()
+            .map(|stage| stage.peak_bytes)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Run the function, measuring its time and peak memory.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Stage) {
+    let baseline = alloc::reset_peak();
+    let start = Instant::now();
+
+    let output = f();
+
+    let stage = Stage {
+        time: start.elapsed(),
+        peak_bytes: alloc::peak().saturating_sub(baseline),
+    };
+    (output, stage)
+}
+
+struct Report<'a> {
+    input: &'a str,
+}
+
+impl WithSolution for Report<'_> {
+    type Output = Result<DayReport, Box<dyn Error>>;
+
+    fn run<S: Solution>(self) -> Self::Output {
+        let (input, parse) = measure(|| S::parse(self.input));
+        let input = input?;
+
+        let (_, part1) = measure(|| S::part1(&input));
+        let (answer, part2) = measure(|| S::part2(&input));
+
+        Ok(DayReport {
+            day: S::DAY,
+            parse,
+            part1,
+            part2: answer.map(|_| part2),
+        })
+    }
+}
+
+/// Solve the given day, measuring each stage.
+pub fn report(day: u8, input: &str) -> Result<DayReport, Box<dyn Error>> {
+    days::with_solution(day, Report { input })
+        .unwrap_or_else(|| Err(format!("There is no solution for day {day}").into()))
+}
+
+fn format_time(time: Duration) -> String {
+    let micros = time.as_secs_f64() * 1e6;
+    if micros < 1e3 {
+        format!("{micros:.0} µs")
+    } else if micros < 1e6 {
+        format!("{:.2} ms", micros / 1e3)
+    } else {
+        format!("{:.2} s", micros / 1e6)
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Format the reports as a table with the time and the peak memory of each stage.
+pub fn format_table(reports: &[DayReport]) -> String {
+    let header = [
+        "Day",
+        "Parse",
+        "Part 1",
+        "Part 2",
+        "Total",
+        "Parse mem",
+        "Part 1 mem",
+        "Part 2 mem",
+    ];
+    let mut rows: Vec<[String; 8]> = vec![header.map(String::from)];
+
+    for report in reports {
+        let part2 = |f: fn(&Stage) -> String| report.part2.as_ref().map_or("-".to_string(), f);
+        rows.push([
+            report.day.to_string(),
+            format_time(report.parse.time),
+            format_time(report.part1.time),
+            part2(|stage| format_time(stage.time)),
+            format_time(report.total_time()),
+            format_bytes(report.parse.peak_bytes),
+            format_bytes(report.part1.peak_bytes),
+            part2(|stage| format_bytes(stage.peak_bytes)),
+        ]);
+    }
+
+    let total: Duration = reports.iter().map(DayReport::total_time).sum();
+    let peak = reports.iter().map(DayReport::peak_bytes).max().unwrap_or(0);
+    let mut footer = [(); 8].map(|_| String::new());
+    footer[0] = "All".to_string();
+    footer[4] = format_time(total);
+    footer[5] = format_bytes(peak);
+    rows.push(footer);
+
+    let mut widths = [0; 8];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        writeln!(table, "{}", line.join("  ").trim_end()).unwrap();
+    }
+    table
+}
+
+/// Format the reports as a JSON array, with times in seconds and memory in bytes.
+pub fn format_json(reports: &[DayReport]) -> String {
+    serde_json::to_string_pretty(reports).expect("Reports are always serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(millis: u64, peak_bytes: usize) -> Stage {
+        Stage {
+            time: Duration::from_millis(millis),
+            peak_bytes,
+        }
+    }
+
+    fn reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                day: 1,
+                parse: stage(1, 100),
+                part1: stage(2, 2048),
+                part2: Some(stage(1500, 0)),
+            },
+            DayReport {
+                day: 25,
+                parse: stage(0, 10),
+                part1: stage(20, 3 << 20),
+                part2: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(&reports());
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Day  "));
+        assert!(lines[1].contains("1.50 s") && lines[1].contains("2.0 KiB"));
+        assert!(lines[2].starts_with("25 ") && lines[2].contains("3.0 MiB"));
+        assert!(lines[3].starts_with("All") && lines[3].contains("1.52 s"));
+    }
+
+    #[test]
+    fn test_format_json() {
+        let json: serde_json::Value = serde_json::from_str(&format_json(&reports())).unwrap();
+
+        assert_eq!(json[0]["day"], 1);
+        assert_eq!(json[0]["part1"]["seconds"], 0.002);
+        assert_eq!(json[0]["part1"]["peak_bytes"], 2048);
+        assert_eq!(json[1]["part2"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_measure() {
+        let (buffer, stage) = measure(|| vec![1u8; 1 << 20]);
+        assert_eq!(buffer.len(), 1 << 20);
+        // Tests run concurrently and share the allocator, so only check the order of magnitude
+        assert!(stage.peak_bytes >= 1 << 19);
+    }
+}
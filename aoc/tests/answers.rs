@@ -0,0 +1,22 @@
+//! Check the answers of all days against the recorded ones. Solving all days takes a few minutes
+//! in debug mode, so the test is ignored by default. Run it with:
+//!
+//! ```text
+//! cargo test --release -p aoc -- --ignored
+//! ```
+
+use std::process::Command;
+
+#[test]
+#[ignore]
+fn recorded_answers() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("check")
+        .output()
+        .expect("Failed to run the aoc binary");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert!(stdout.ends_with(", 0 failed, 0 missing\n"), "{stdout}");
+}
@@ -0,0 +1,82 @@
+//! Reading the puzzle input from a file or from stdin.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Where the puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Source given on the command line: `-` for stdin, or the path to a file.
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Source given as the first argument of the binary of a day, or the `input` file in the
+    /// current directory if there are no arguments.
+    pub fn from_args() -> InputSource {
+        match env::args().nth(1) {
+            Some(arg) => InputSource::from_arg(&arg),
+            None => InputSource::File(PathBuf::from("./input")),
+        }
+    }
+
+    /// Open the source for reading line by line.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => {
+                let file = File::open(path).map_err(|e| cannot_read(path, e))?;
+                Ok(Box::new(BufReader::new(file)))
+            },
+        }
+    }
+
+    /// Read the whole input.
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut input = String::new();
+        match self {
+            InputSource::Stdin => io::stdin().read_to_string(&mut input)?,
+            InputSource::File(path) => {
+                let mut file = File::open(path).map_err(|e| cannot_read(path, e))?;
+                file.read_to_string(&mut input)
+                    .map_err(|e| cannot_read(path, e))?
+            },
+        };
+        Ok(input)
+    }
+}
+
+/// Directory of the crate of the given day in the workspace, e.g. `day17-crucible`, where its
+/// `input` and `answers` files are.
+pub fn day_dir(day: u8) -> io::Result<PathBuf> {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let prefix = format!("day{day:02}-");
+
+    for entry in fs::read_dir(&workspace)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            return Ok(entry.path());
+        }
+    }
+
+    let message = format!("No crate found for day {day}");
+    Err(io::Error::new(io::ErrorKind::NotFound, message))
+}
+
+/// Add the path to the error, since the io errors don't mention it.
+fn cannot_read(path: &Path, error: io::Error) -> io::Error {
+    io::Error::new(
+        error.kind(),
+        format!("Cannot read {}: {error}", path.display()),
+    )
+}
@@ -0,0 +1,34 @@
+//! Code shared between the solutions of all days.
+
+use std::fmt::Display;
+
+pub mod input;
+pub mod observe;
+pub mod parse;
+pub mod step;
+
+pub use input::{day_dir, InputSource};
+pub use observe::{Ignore, Observer};
+pub use parse::{ParseError, ReadError};
+pub use step::{Simulation, Snapshot};
+
+/// Common interface implemented by the solution of every day, so that the runner, tests and
+/// benchmarks can treat all days the same way.
+pub trait Solution {
+    /// Day of the puzzle, from 1 to 25.
+    const DAY: u8;
+
+    /// Puzzle input parsed into the form shared by both parts. It is shared between threads when
+    /// the parts are solved at the same time, so the solutions must not keep it in e.g. an `Rc`.
+    type Input: Sync;
+
+    /// Answer to either part of the puzzle.
+    type Output: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Output;
+
+    /// Return `None` if the puzzle has no second part, which is the case for day 25.
+    fn part2(input: &Self::Input) -> Option<Self::Output>;
+}
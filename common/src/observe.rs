@@ -0,0 +1,54 @@
+//! Hooks for watching a solver at work, e.g. to print the path it found, to render its
+//! intermediate states or to count its iterations, without the solver itself having side effects.
+//!
+//! A solver that supports observation takes a `&mut impl Observer<Event>`, where `Event` is the
+//! structured data it reports. Closures, `Vec`s (which collect the events) and [`Ignore`] are all
+//! observers.
+
+/// Receiver of the events of a solver.
+pub trait Observer<E> {
+    fn observe(&mut self, event: E);
+}
+
+impl<E, F: FnMut(E)> Observer<E> for F {
+    fn observe(&mut self, event: E) {
+        self(event)
+    }
+}
+
+impl<E> Observer<E> for Vec<E> {
+    fn observe(&mut self, event: E) {
+        self.push(event)
+    }
+}
+
+/// Observer which drops all the events, for when nobody is watching.
+pub struct Ignore;
+
+impl<E> Observer<E> for Ignore {
+    fn observe(&mut self, _event: E) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_to(n: u32, observer: &mut impl Observer<u32>) {
+        for i in 1..=n {
+            observer.observe(i);
+        }
+    }
+
+    #[test]
+    fn observers() {
+        let mut events = Vec::new();
+        count_to(3, &mut events);
+        assert_eq!(events, [1, 2, 3]);
+
+        let mut sum = 0;
+        count_to(4, &mut |i| sum += i);
+        assert_eq!(sum, 10);
+
+        count_to(5, &mut Ignore);
+    }
+}
@@ -0,0 +1,404 @@
+//! Parse errors with line and column diagnostics, and the nom plumbing to produce them.
+//!
+//! Parsers use [`IResult`] instead of the nom one, so that nom errors carry a description of what
+//! was expected. The [`tag`] and [`one_of`] combinators should be used instead of the nom ones,
+//! because the nom ones don't remember which tag or characters were expected.
+//!
+//! The top level `parse_input` functions run their parsers through [`parse_all`] or
+//! [`parse_lines`], which turn the nom error into a [`ParseError`] pointing into the whole input.
+//! Line-based inputs can also be parsed while they are being read, with [`parse_reader`].
+
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// Error returned by every parser and loader, pointing at the offending place in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Column number in characters, starting from 1.
+    pub column: usize,
+    /// The whole offending line, without the line ending.
+    pub text: String,
+    /// Description of what was expected at the position.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Create an error at the start of `rest`, which must be a subslice of `input`. If it is not,
+    /// the error points at the start of the input.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let position = rest.as_ptr() as usize;
+        let offset = if position >= start && position <= start + input.len() {
+            position - start
+        } else {
+            0
+        };
+
+        ParseError::at_offset(input, offset, expected)
+    }
+
+    /// Create an error at the given byte offset into the input.
+    pub fn at_offset(input: &str, offset: usize, expected: impl Into<String>) -> ParseError {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Convert a nom error, whose remaining input must be a subslice of `input`.
+    pub fn from_nom(input: &str, error: nom::Err<Error<&str>>) -> ParseError {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                ParseError::at(input, e.input, e.expected.to_string())
+            },
+            nom::Err::Incomplete(_) => ParseError::at_offset(input, input.len(), "more input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What a parser expected to find at the position where it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Char(char),
+    Tag(&'static str),
+    OneOf(&'static str),
+    Kind(ErrorKind),
+    Context(&'static str),
+    Message(String),
+    /// Several alternatives failed at the same position.
+    Any(Vec<Expected>),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Char(ch) => write!(f, "{ch:?}"),
+            Expected::Tag(tag) => write!(f, "{tag:?}"),
+            Expected::OneOf(chars) => write!(f, "one of {chars:?}"),
+            Expected::Kind(kind) => write!(f, "{}", describe_kind(*kind)),
+            Expected::Context(context) => write!(f, "{context}"),
+            Expected::Message(message) => write!(f, "{message}"),
+            Expected::Any(alternatives) => {
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    match i {
+                        0 => (),
+                        i if i == alternatives.len() - 1 => write!(f, " or ")?,
+                        _ => write!(f, ", ")?,
+                    }
+                    write!(f, "{alternative}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+fn describe_kind(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::HexDigit => "a hex number".to_string(),
+        ErrorKind::Alpha => "a word".to_string(),
+        ErrorKind::AlphaNumeric => "a word or a number".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_string(),
+        ErrorKind::CrLf => "a line ending".to_string(),
+        ErrorKind::Eof => "more input".to_string(),
+        ErrorKind::NonEmpty => "a non-empty line".to_string(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+/// The nom error type used by all parsers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<I> {
+    /// Remaining input at the position of the error.
+    pub input: I,
+    pub expected: Expected,
+}
+
+impl<I> Error<I> {
+    pub fn new(input: I, expected: Expected) -> Self {
+        Error { input, expected }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Error<&'a str> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Error::new(input, Expected::Kind(kind))
+    }
+
+    /// Keep the innermost error, since it is the most specific one.
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, ch: char) -> Self {
+        Error::new(input, Expected::Char(ch))
+    }
+
+    /// Keep the alternative which got further into the input, or all of them if they failed at
+    /// the same position.
+    fn or(self, other: Self) -> Self {
+        if self.input.len() < other.input.len() {
+            return self;
+        }
+        if other.input.len() < self.input.len() {
+            return other;
+        }
+
+        let mut alternatives = match self.expected {
+            Expected::Any(alternatives) => alternatives,
+            expected => vec![expected],
+        };
+        match other.expected {
+            Expected::Any(others) => alternatives.extend(others),
+            expected => alternatives.push(expected),
+        }
+        alternatives.dedup();
+
+        Error::new(other.input, Expected::Any(alternatives))
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<&'a str> {
+    /// Replace the error with the context only if it failed right at the start of the context,
+    /// otherwise the inner error is more precise.
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        if input.len() == other.input.len() {
+            Error::new(input, Expected::Context(context))
+        } else {
+            other
+        }
+    }
+}
+
+impl<'a, E: fmt::Display> FromExternalError<&'a str, E> for Error<&'a str> {
+    fn from_external_error(input: &'a str, _kind: ErrorKind, e: E) -> Self {
+        Error::new(input, Expected::Message(e.to_string()))
+    }
+}
+
+/// Same as the nom `IResult`, but with [`Error`] as the default error type.
+pub type IResult<I, O, E = Error<I>> = nom::IResult<I, O, E>;
+
+/// Recognize the given string. Same as the nom `tag`, but remembers the expected tag on failure.
+pub fn tag<'a>(tag: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((rest, &input[..tag.len()])),
+        None => Err(nom::Err::Error(Error::new(input, Expected::Tag(tag)))),
+    }
+}
+
+/// Recognize one of the given characters. Same as the nom `one_of`, but remembers the expected
+/// characters on failure.
+pub fn one_of<'a>(chars: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(ch) if chars.contains(ch) => Ok((&input[ch.len_utf8()..], ch)),
+        _ => Err(nom::Err::Error(Error::new(input, Expected::OneOf(chars)))),
+    }
+}
+
+/// Fail unrecoverably at the given position, with a description of what was expected.
+pub fn fail<O>(input: &str, expected: impl Into<String>) -> IResult<&str, O> {
+    Err(nom::Err::Failure(Error::new(
+        input,
+        Expected::Message(expected.into()),
+    )))
+}
+
+/// Run the parser on the whole input. Only trailing whitespace may remain unparsed.
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    let (remainder, output) = parser(input).map_err(|e| ParseError::from_nom(input, e))?;
+
+    let remainder = remainder.trim_start();
+    if !remainder.is_empty() {
+        return Err(ParseError::at(input, remainder, "end of input"));
+    }
+
+    Ok(output)
+}
+
+/// Run the parser on each line of the input. Only trailing whitespace may remain unparsed on
+/// each line.
+pub fn parse_lines<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (remainder, output) = parser(line).map_err(|e| ParseError::from_nom(input, e))?;
+
+            let remainder = remainder.trim_start();
+            if !remainder.is_empty() {
+                return Err(ParseError::at(input, remainder, "end of line"));
+            }
+
+            Ok(output)
+        })
+        .collect()
+}
+
+/// Error of parsing an input while reading it.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "{error}"),
+            ReadError::Parse(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        ReadError::Parse(error)
+    }
+}
+
+/// Same as [`parse_lines`], but reads the lines one at a time, so that the whole input never has
+/// to be in memory. The lines are parsed lazily, as the iterator is consumed.
+pub fn parse_reader<O>(
+    reader: impl BufRead,
+    mut parser: impl FnMut(&str) -> IResult<&str, O>,
+) -> impl Iterator<Item = Result<O, ReadError>> {
+    reader.lines().enumerate().map(move |(index, line)| {
+        let line = line?;
+        let at_line = |mut error: ParseError| {
+            error.line = index + 1;
+            error
+        };
+
+        let (remainder, output) =
+            parser(&line).map_err(|e| at_line(ParseError::from_nom(&line, e)))?;
+        let remainder = remainder.trim_start();
+        if !remainder.is_empty() {
+            return Err(at_line(ParseError::at(&line, remainder, "end of line")).into());
+        }
+        Ok(output)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use nom::{branch::alt, character::complete::u32, sequence::separated_pair};
+
+    fn parse_pair(input: &str) -> IResult<&str, (u32, u32)> {
+        separated_pair(u32, tag(", "), u32)(input)
+    }
+
+    #[test]
+    fn position() {
+        let input = indoc! {"
+            1, 2
+            3; 4
+        "};
+
+        let error = parse_lines(input, parse_pair).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                column: 2,
+                text: "3; 4".to_string(),
+                expected: "\", \"".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected \", \"\n  3; 4\n   ^"
+        );
+    }
+
+    #[test]
+    fn alternatives() {
+        let input = "5 purple";
+        let error = parse_all(input, alt((tag("5 red"), tag("5 blue"), tag("5 green"))));
+        assert_eq!(
+            error.unwrap_err().expected,
+            "\"5 red\", \"5 blue\" or \"5 green\""
+        );
+    }
+
+    #[test]
+    fn remainder() {
+        let error = parse_all("1, 2\n3, 4\n", parse_pair).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "end of input");
+
+        let error = parse_lines("1, 2x", parse_pair).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.expected, "end of line");
+    }
+
+    #[test]
+    fn reader() {
+        let input = indoc! {"
+            1, 2
+            3, 4
+            5,6
+        "};
+
+        let mut pairs = parse_reader(input.as_bytes(), parse_pair);
+        assert_eq!(pairs.next().unwrap().unwrap(), (1, 2));
+        assert_eq!(pairs.next().unwrap().unwrap(), (3, 4));
+
+        let error = pairs.next().unwrap().unwrap_err();
+        let ReadError::Parse(error) = error else {
+            panic!("Expected a parse error, got {error:?}");
+        };
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 2, "5,6")
+        );
+        assert!(pairs.next().is_none());
+    }
+}
@@ -0,0 +1,24 @@
+//! Simulations that can be run one step at a time, so that they can be watched and debugged, e.g.
+//! in the terminal with the `stepper` crate.
+//!
+//! The days whose puzzles are step by step simulations provide a [`Simulation`] which their
+//! solvers run as well, so the stepper shows exactly what the solver does.
+
+/// Simulation which advances one step at a time, with a snapshot of its state between the steps.
+pub trait Simulation {
+    type Snapshot: Snapshot;
+
+    /// Copy of the current state.
+    fn snapshot(&self) -> Self::Snapshot;
+
+    /// Advance by one step. Return `false` if the simulation is over, in which case the state
+    /// doesn't change.
+    fn step(&mut self) -> bool;
+}
+
+/// State of a simulation between two steps.
+pub trait Snapshot {
+    /// Values that describe the state as names and values, e.g. `("load", "136")`, for inspecting
+    /// the state and for breakpoints.
+    fn fields(&self) -> Vec<(String, String)>;
+}
@@ -0,0 +1,112 @@
+//! Throughput of the calibration on a generated input of several GiB, streamed from memory so
+//! that the disk is not measured:
+//!
+//! ```sh
+//! THROUGHPUT_GIB=4 cargo bench -p day01-trebuchet
+//! ```
+//!
+//! The size defaults to 2 GiB. For comparison, the same input is also calibrated the way part two
+//! used to work: one regex on the line for the first digit, and a reversed regex on a reversed
+//! copy of the line for the last digit.
+
+use day01_trebuchet::{calibrate_reader, DIGITS, ENGLISH_WORDS};
+use regex::Regex;
+use std::env;
+use std::hint::black_box;
+use std::io::{self, BufRead, BufReader, Read};
+use std::time::Instant;
+
+/// Size of the generated chunk, in percent of the real input, which is about 22 KiB.
+const CHUNK_SCALE: usize = 5000;
+
+const GIB: u64 = 1 << 30;
+
+/// Reader which repeats the chunk until it has read `len` bytes. The chunk ends with a newline,
+/// so that the repeats are whole lines.
+struct Repeat<'a> {
+    chunk: &'a [u8],
+    position: usize,
+    remaining: u64,
+}
+
+impl<'a> Repeat<'a> {
+    fn new(chunk: &'a [u8], len: u64) -> Repeat<'a> {
+        Repeat {
+            chunk,
+            position: 0,
+            remaining: len,
+        }
+    }
+}
+
+impl Read for Repeat<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = (self.chunk.len() - self.position).min(buf.len());
+        let n = available.min(self.remaining.try_into().unwrap_or(usize::MAX));
+
+        buf[..n].copy_from_slice(&self.chunk[self.position..self.position + n]);
+        self.position = (self.position + n) % self.chunk.len();
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Calibration of part two with regexes, reversing each line to find the last digit.
+fn calibrate_reversed(reader: impl BufRead) -> io::Result<u64> {
+    let words = || DIGITS.into_iter().chain(ENGLISH_WORDS);
+    let forward: Vec<String> = words().map(|(token, _)| token.to_string()).collect();
+    let reverse: Vec<String> = words()
+        .map(|(token, _)| token.chars().rev().collect())
+        .collect();
+    let forward_re = Regex::new(&forward.join("|")).unwrap();
+    let reverse_re = Regex::new(&reverse.join("|")).unwrap();
+
+    let value = |token: &str, tokens: &[String]| {
+        let index = tokens.iter().position(|t| t == token).unwrap();
+        words().nth(index).unwrap().1
+    };
+
+    let mut sum = 0;
+    for line in reader.lines() {
+        let line = line?;
+        let rev_line: String = line.chars().rev().collect();
+
+        let first = forward_re.find(&line).map(|m| value(m.as_str(), &forward));
+        let last = reverse_re
+            .find(&rev_line)
+            .map(|m| value(m.as_str(), &reverse));
+        sum += (first.unwrap_or(0) * 10 + last.or(first).unwrap_or(0)) as u64;
+    }
+    Ok(sum)
+}
+
+fn measure<T>(name: &str, len: u64, f: impl FnOnce() -> T) {
+    let start = Instant::now();
+    black_box(f());
+    let seconds = start.elapsed().as_secs_f64();
+
+    let gib = len as f64 / GIB as f64;
+    println!(
+        "{name}: {gib:.2} GiB in {seconds:.2} s, {:.2} GiB/s",
+        gib / seconds
+    );
+}
+
+fn main() {
+    let gib: f64 = env::var("THROUGHPUT_GIB").map_or(2.0, |gib| {
+        gib.parse().expect("THROUGHPUT_GIB must be a number")
+    });
+    let len = (gib * GIB as f64) as u64;
+
+    let mut rng = generator::rng(1);
+    let params = generator::day01::Params::scaled(CHUNK_SCALE);
+    let chunk = generator::day01::generate(&mut rng, &params);
+    let chunk = chunk.as_bytes();
+
+    measure("Automaton, both parts", len, || {
+        calibrate_reader(BufReader::new(Repeat::new(chunk, len))).unwrap()
+    });
+    measure("Reversed regex, part two", len, || {
+        calibrate_reversed(BufReader::new(Repeat::new(chunk, len))).unwrap()
+    });
+}
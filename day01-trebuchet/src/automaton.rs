@@ -0,0 +1,181 @@
+//! Aho-Corasick automaton which finds the first and the last token of every line in a single
+//! forward scan, including tokens which overlap, e.g. "one" and "eight" in "oneight".
+//!
+//! The automaton is a DFA over bytes: each state has the next state for every byte, so the scan
+//! does a single table lookup per byte and never allocates. Only the longest token ending in each
+//! state is kept, since a shorter token ending at the same place can be neither the first nor the
+//! last token of the line.
+
+use std::ops::Range;
+
+const ROOT: usize = 0;
+const MISSING: u32 = u32::MAX;
+/// Flag of the transitions into states in which a token ends, so that the scan looks up the token
+/// only when there is one.
+const OUTPUT: u32 = 1 << 31;
+
+/// Token which ends when the automaton enters a state.
+#[derive(Debug, Clone, Copy)]
+struct Output {
+    len: usize,
+    value: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    /// Next state for each state and byte, at `state * 256 + byte`
+    transitions: Vec<u32>,
+    /// The longest token which ends in each state
+    outputs: Vec<Option<Output>>,
+}
+
+impl Automaton {
+    /// Build the automaton of the tokens, which must be non-empty and must not contain line
+    /// breaks.
+    pub fn new<'a>(tokens: impl IntoIterator<Item = (&'a str, u32)>) -> Automaton {
+        let mut automaton = Automaton {
+            transitions: vec![MISSING; 256],
+            outputs: vec![None],
+        };
+
+        // Trie of the tokens
+        for (token, value) in tokens {
+            let mut state = ROOT;
+            for &byte in token.as_bytes() {
+                let index = state * 256 + byte as usize;
+                if automaton.transitions[index] == MISSING {
+                    automaton.transitions[index] = automaton.outputs.len() as u32;
+                    automaton.transitions.extend([MISSING; 256]);
+                    automaton.outputs.push(None);
+                }
+                state = automaton.transitions[index] as usize;
+            }
+            automaton.outputs[state] = Some(Output {
+                len: token.len(),
+                value,
+            });
+        }
+
+        // Fill in the missing transitions from the failure links, in the order of the depth of the
+        // states, so that the transitions of each failure state are complete when they are used
+        let mut failure = vec![ROOT; automaton.outputs.len()];
+        let mut queue = std::collections::VecDeque::from([ROOT]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let index = state * 256 + byte;
+                let fallback = match state {
+                    ROOT => ROOT as u32,
+                    _ => automaton.transitions[failure[state] * 256 + byte],
+                };
+
+                match automaton.transitions[index] {
+                    MISSING => automaton.transitions[index] = fallback,
+                    child => {
+                        let child = child as usize;
+                        failure[child] = fallback as usize;
+                        // A token ending in the failure state is a suffix of the tokens of the
+                        // child, so it is shorter than the token of the child itself
+                        if automaton.outputs[child].is_none() {
+                            automaton.outputs[child] = automaton.outputs[fallback as usize];
+                        }
+                        queue.push_back(child);
+                    },
+                }
+            }
+        }
+
+        for target in &mut automaton.transitions {
+            if automaton.outputs[*target as usize].is_some() {
+                *target |= OUTPUT;
+            }
+        }
+        automaton
+    }
+
+    /// Byte ranges and values of the tokens in the line, in the order of their ends. Only the
+    /// longest token ending at each byte is found, as in the scan.
+    pub fn matches<'b>(&'b self, line: &'b [u8]) -> impl Iterator<Item = (Range<usize>, u32)> + 'b {
+        let mut state = ROOT;
+        line.iter().enumerate().filter_map(move |(i, &byte)| {
+            let next = self.transitions[state * 256 + byte as usize];
+            state = (next & !OUTPUT) as usize;
+
+            let output = self.outputs[state].filter(|_| next & OUTPUT != 0)?;
+            Some((i + 1 - output.len..i + 1, output.value))
+        })
+    }
+}
+
+/// Scan of a text through the automaton, which can be fed in pieces, computing the calibration
+/// value of each line.
+pub struct Scanner<'a> {
+    automaton: &'a Automaton,
+    state: usize,
+    /// Number of bytes scanned before the current piece
+    offset: usize,
+    /// Start in the whole text and value of the first token of the current line
+    first: Option<(usize, u32)>,
+    /// Value of the last token of the current line
+    last: Option<u32>,
+    sum: u64,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(automaton: &'a Automaton) -> Scanner<'a> {
+        Scanner {
+            automaton,
+            state: ROOT,
+            offset: 0,
+            first: None,
+            last: None,
+            sum: 0,
+        }
+    }
+
+    /// Scan the next piece of the text.
+    pub fn feed(&mut self, text: &[u8]) {
+        let transitions = &self.automaton.transitions;
+
+        for (i, &byte) in text.iter().enumerate() {
+            if byte == b'\n' {
+                self.end_line();
+                continue;
+            }
+
+            let next = transitions[self.state * 256 + byte as usize];
+            self.state = (next & !OUTPUT) as usize;
+
+            if next & OUTPUT != 0 {
+                let output = self.automaton.outputs[self.state].expect("Flagged state has a token");
+                // Tokens are found in the order of their ends, so the last one found is the last
+                // one of the line, and a token starting where the first one starts is longer
+                let start = self.offset + i + 1 - output.len;
+                if self
+                    .first
+                    .is_none_or(|(first_start, _)| start <= first_start)
+                {
+                    self.first = Some((start, output.value));
+                }
+                self.last = Some(output.value);
+            }
+        }
+        self.offset += text.len();
+    }
+
+    /// Add the value of the current line, made of its first and last token, or 0 if it has no
+    /// tokens.
+    fn end_line(&mut self) {
+        if let (Some((_, first)), Some(last)) = (self.first, self.last) {
+            self.sum += first as u64 * 10 + last as u64;
+        }
+        self.state = ROOT;
+        self.first = None;
+        self.last = None;
+    }
+
+    /// Sum of the values of all lines, including the last line if it has no line break.
+    pub fn finish(mut self) -> u64 {
+        self.end_line();
+        self.sum
+    }
+}
@@ -1,62 +1,274 @@
 //! https://adventofcode.com/2023/day/1
 
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    pub static ref DIGIT1: Regex = Regex::new(r"\d").unwrap();
-    pub static ref DIGIT2_FORWARD: Regex =
-        Regex::new(r"\d|one|two|three|four|five|six|seven|eight|nine").unwrap();
-    pub static ref DIGIT2_REVERSE: Regex =
-        Regex::new(r"\d|eno|owt|eerht|ruof|evif|xis|neves|thgie|enin").unwrap();
-}
+use automaton::{Automaton, Scanner};
+use common::{ParseError, Solution};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::ops::Range;
+use std::str;
 
-fn parse_digit(digit_as_str: &str) -> u32 {
-    let digit: u32 = match digit_as_str {
-        "one" | "eno" => 1,
-        "two" | "owt" => 2,
-        "three" | "eerht" => 3,
-        "four" | "ruof" => 4,
-        "five" | "evif" => 5,
-        "six" | "xis" => 6,
-        "seven" | "neves" => 7,
-        "eight" | "thgie" => 8,
-        "nine" | "enin" => 9,
-        _ => digit_as_str
-            .parse::<u32>()
-            .expect("Expected a single digit string"),
-    };
-    digit
-}
+mod automaton;
+#[cfg(feature = "viz")]
+mod viz;
 
-fn find_first_digit(input: &str, digit_re: &Regex) -> Option<u32> {
-    digit_re.find(input).map(|m| parse_digit(m.as_str()))
+/// The decimal digits, which are the only digits in part one.
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// The digits spelled out in English, which also count as digits in part two.
+pub const ENGLISH_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// The tokens which count as digits, e.g. "7" or "seven", each with its value.
+///
+/// The tokens are found by a single forward scan of the input with an Aho-Corasick automaton,
+/// which finds overlapping tokens, so in "oneight" "one" is the first and "eight" the last token.
+/// Where several tokens start at the same place, the longest one is the first, and where several
+/// end at the same place, the longest one is the last.
+///
+/// The automaton matches bytes, but both the tokens and the input are valid UTF-8, so a token
+/// always matches whole characters of the input, whatever the script.
+///
+/// The vocabulary is built by each caller rather than shared in a static, so that the solutions
+/// keep no global state and can run on several threads at once.
+#[derive(Debug, Clone)]
+pub struct DigitVocabulary {
+    automaton: Automaton,
+    values: HashMap<String, u32>,
 }
 
-// Notice that you cannot find the last digit while scanning forward, because regexes are greedy
-// and for an input such as "nineight", they would match "nine" rather than the expected "eight".
-fn find_last_digit(input: &str, digit_re: &Regex) -> Option<u32> {
-    let rev_input: String = input.chars().rev().collect();
-    digit_re.find(&rev_input).map(|m| parse_digit(m.as_str()))
+impl DigitVocabulary {
+    /// Build the vocabulary from the tokens and their values, e.g. `DIGITS` chained with the
+    /// words of some language. The tokens are matched literally and case-sensitively.
+    pub fn new<S: AsRef<str>>(
+        tokens: impl IntoIterator<Item = (S, u32)>,
+    ) -> Result<DigitVocabulary, &'static str> {
+        let mut values = HashMap::new();
+        for (token, value) in tokens {
+            let token = token.as_ref();
+            if token.is_empty() {
+                return Err("Digit tokens cannot be empty");
+            }
+            if token.contains(['\n', '\r']) {
+                return Err("Digit tokens cannot contain line breaks");
+            }
+            if values
+                .insert(token.to_string(), value)
+                .is_some_and(|old| old != value)
+            {
+                return Err("Digit token has more than one value");
+            }
+        }
+        if values.is_empty() {
+            return Err("Digit vocabulary cannot be empty");
+        }
+
+        let tokens = values.iter().map(|(token, &value)| (token.as_str(), value));
+        Ok(DigitVocabulary {
+            automaton: Automaton::new(tokens),
+            values,
+        })
+    }
+
+    /// Only the decimal digits, as in part one.
+    pub fn digits() -> DigitVocabulary {
+        DigitVocabulary::new(DIGITS).expect("Decimal digits are a valid vocabulary")
+    }
+
+    /// The decimal digits and the English words for them, as in part two.
+    pub fn english() -> DigitVocabulary {
+        DigitVocabulary::new(DIGITS.into_iter().chain(ENGLISH_WORDS))
+            .expect("English words are a valid vocabulary")
+    }
+
+    /// Value of the token, if it is in the vocabulary.
+    pub fn value(&self, token: &str) -> Option<u32> {
+        self.values.get(token).copied()
+    }
 }
 
 /// For each line of the input combine the first digit and the last digit (in that order) to form a
 /// single two-digit number. Then sum all the two-digit numbers and return the sum. If there are no
 /// digits in the line, use 0.
-pub fn calibrate(input: &str, digit_re_fwd: &Regex, digit_re_rev: &Regex) -> u32 {
-    let sum = input
-        .lines()
-        .map(|line| {
-            let first = find_first_digit(line, digit_re_fwd);
-            let last = find_last_digit(line, digit_re_rev).or(first);
-            let first = first.unwrap_or(0);
-            let last = last.unwrap_or(0);
-            let line_value = first * 10 + last;
-            line_value
+pub fn calibrate(input: &str, vocabulary: &DigitVocabulary) -> u64 {
+    let mut scanner = Scanner::new(&vocabulary.automaton);
+    scanner.feed(input.as_bytes());
+    scanner.finish()
+}
+
+/// Same as `calibrate` for both parts at once, reading the input in blocks of whole lines. Fails
+/// if the input is not valid UTF-8.
+pub fn calibrate_reader(mut reader: impl BufRead) -> io::Result<(u64, u64)> {
+    let (digits, english) = (DigitVocabulary::digits(), DigitVocabulary::english());
+    let mut scanner1 = Scanner::new(&digits.automaton);
+    let mut scanner2 = Scanner::new(&english.automaton);
+
+    // Lines which don't fit into the buffer of the reader, collected until their end
+    let mut pending = Vec::new();
+    let mut scan = |text: &[u8]| {
+        str::from_utf8(text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        scanner1.feed(text);
+        scanner2.feed(text);
+        Ok::<(), io::Error>(())
+    };
+
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+
+        // Only scan whole lines, so that no character is split between two blocks
+        let (len, complete) = match buffer.iter().rposition(|&byte| byte == b'\n') {
+            Some(newline) => (newline + 1, true),
+            None => (buffer.len(), false),
+        };
+        if pending.is_empty() && complete {
+            scan(&buffer[..len])?;
+        } else {
+            pending.extend_from_slice(&buffer[..len]);
+            if complete {
+                scan(&pending)?;
+                pending.clear();
+            }
+        }
+        reader.consume(len);
+    }
+    scan(&pending)?;
+
+    Ok((scanner1.finish(), scanner2.finish()))
+}
+
+/// Token of the vocabulary found in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMatch<'a> {
+    pub token: &'a str,
+    /// Byte range of the token in the whole input
+    pub range: Range<usize>,
+    pub value: u32,
+}
+
+/// How the calibration value of a single line came about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCalibration<'a> {
+    /// Line number, starting from 1
+    pub number: usize,
+    /// The line without its line break
+    pub text: &'a str,
+    /// Byte range of the line in the whole input, without its line break
+    pub range: Range<usize>,
+    /// First and last token, which are the same if the line has only one. `None` if the line has
+    /// no tokens.
+    pub tokens: Option<(TokenMatch<'a>, TokenMatch<'a>)>,
+    /// Value of the line, 0 if it has no tokens
+    pub value: u64,
+}
+
+/// The calibration value of every line of the input, to find out which line is to blame for a
+/// wrong sum. Lines without tokens are included with the value 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationReport<'a> {
+    pub lines: Vec<LineCalibration<'a>>,
+}
+
+impl CalibrationReport<'_> {
+    /// Sum of the values of all lines, the same as the result of `calibrate`.
+    pub fn sum(&self) -> u64 {
+        self.lines.iter().map(|line| line.value).sum()
+    }
+}
+
+/// Same as `calibrate`, but with the tokens and the value of each line. Lines end at '\n', and a
+/// '\r' before it is not part of the line.
+pub fn calibration_report<'a>(
+    input: &'a str,
+    vocabulary: &DigitVocabulary,
+) -> CalibrationReport<'a> {
+    let mut start = 0;
+    let lines = input
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let line_start = start;
+            start += line.len();
+            let text = line.strip_suffix('\n').unwrap_or(line);
+            let text = text.strip_suffix('\r').unwrap_or(text);
+
+            // Same choice of tokens as the scanner
+            let mut first: Option<(Range<usize>, u32)> = None;
+            let mut last = None;
+            for (range, value) in vocabulary.automaton.matches(text.as_bytes()) {
+                if first
+                    .as_ref()
+                    .is_none_or(|(first, _)| range.start <= first.start)
+                {
+                    first = Some((range.clone(), value));
+                }
+                last = Some((range, value));
+            }
+
+            let token = |(range, value): (Range<usize>, u32)| TokenMatch {
+                token: &text[range.clone()],
+                range: line_start + range.start..line_start + range.end,
+                value,
+            };
+            let tokens = first
+                .zip(last)
+                .map(|(first, last)| (token(first), token(last)));
+            let value = tokens.as_ref().map_or(0, |(first, last)| {
+                first.value as u64 * 10 + last.value as u64
+            });
+
+            LineCalibration {
+                number: index + 1,
+                text,
+                range: line_start..line_start + text.len(),
+                tokens,
+                value,
+            }
         })
-        .sum();
+        .collect();
+
+    CalibrationReport { lines }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = String;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        calibrate(input, &DigitVocabulary::digits())
+    }
 
-    sum
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(calibrate(input, &DigitVocabulary::english()))
+    }
 }
 
 #[cfg(test)]
@@ -64,6 +276,14 @@ mod tests {
     use super::*;
     use indoc::indoc;
 
+    fn calibrate1(input: &str) -> u64 {
+        calibrate(input, &DigitVocabulary::digits())
+    }
+
+    fn calibrate2(input: &str) -> u64 {
+        calibrate(input, &DigitVocabulary::english())
+    }
+
     #[test]
     fn test1() {
         let input = indoc! {"
@@ -73,10 +293,10 @@ mod tests {
             treb7uchet
         "};
 
-        let result = calibrate(&input, &DIGIT1, &DIGIT1);
+        let result = calibrate1(input);
         assert_eq!(result, 142);
 
-        let result = calibrate(&input, &DIGIT2_FORWARD, &DIGIT2_REVERSE);
+        let result = calibrate2(input);
         assert_eq!(result, 142);
     }
 
@@ -92,11 +312,17 @@ mod tests {
             7pqrstsixteen
         "};
 
-        let result = calibrate(&input, &DIGIT1, &DIGIT1);
+        let result = calibrate1(input);
         assert_eq!(result, 209);
 
-        let result = calibrate(&input, &DIGIT2_FORWARD, &DIGIT2_REVERSE);
+        let result = calibrate2(input);
         assert_eq!(result, 281);
+
+        let result = calibrate_reader(input.as_bytes()).unwrap();
+        assert_eq!(result, (209, 281));
+
+        let result = calibrate_reader(input.replace('\n', "\r\n").trim_end().as_bytes()).unwrap();
+        assert_eq!(result, (209, 281));
     }
 
     #[test]
@@ -107,10 +333,143 @@ mod tests {
             twone
         "};
 
-        let result = calibrate(&input, &DIGIT1, &DIGIT1);
+        let result = calibrate1(input);
         assert_eq!(result, 0);
 
-        let result = calibrate(&input, &DIGIT2_FORWARD, &DIGIT2_REVERSE);
+        let result = calibrate2(input);
         assert_eq!(result, 43);
     }
+
+    #[test]
+    fn overlapping_tokens() {
+        let input = indoc! {"
+            oneight
+            eightwone
+            sevenine
+            twoneighthree
+            xnineightx
+        "};
+        assert_eq!(calibrate2(input), 18 + 81 + 79 + 23 + 98);
+
+        // Tokens which are prefixes and suffixes of each other
+        let tokens = [("ab", 1), ("abc", 2), ("bc", 3), ("c", 4)];
+        let vocabulary = DigitVocabulary::new(tokens).unwrap();
+        assert_eq!(calibrate("abc\n", &vocabulary), 22);
+        assert_eq!(calibrate("xbcx\n", &vocabulary), 33);
+        assert_eq!(calibrate("cab\n", &vocabulary), 41);
+    }
+
+    #[test]
+    fn unicode() {
+        let input = "ünë1ñtwoñ\nçseven→\n";
+        assert_eq!(calibrate2(input), 12 + 77);
+
+        // Characters and lines split between the blocks of the reader
+        let reader = io::BufReader::with_capacity(3, input.as_bytes());
+        assert_eq!(calibrate_reader(reader).unwrap(), (11, 12 + 77));
+
+        let reader = io::BufReader::with_capacity(3, &b"one2\nthree\xff\n"[..]);
+        let err = calibrate_reader(reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let vocabulary = DigitVocabulary::new([("一", 1), ("二", 2), ("三", 3)]).unwrap();
+        assert_eq!(calibrate("x二y一三z\n三\n", &vocabulary), 23 + 33);
+    }
+
+    #[test]
+    fn report() {
+        let input = "two1nine\r\nnodigits\nxoneightx\nñ7\n";
+        let report = calibration_report(input, &DigitVocabulary::english());
+        assert_eq!(report.sum(), calibrate2(input));
+        assert_eq!(report.lines.len(), 4);
+
+        let line = &report.lines[0];
+        assert_eq!(
+            (line.number, line.text, line.range.clone()),
+            (1, "two1nine", 0..8)
+        );
+        assert_eq!(line.value, 29);
+        let (first, last) = line.tokens.clone().unwrap();
+        assert_eq!(
+            first,
+            TokenMatch {
+                token: "two",
+                range: 0..3,
+                value: 2
+            }
+        );
+        assert_eq!(
+            last,
+            TokenMatch {
+                token: "nine",
+                range: 4..8,
+                value: 9
+            }
+        );
+
+        // Lines without tokens are kept
+        let line = &report.lines[1];
+        assert_eq!(
+            (line.number, line.range.clone(), line.value),
+            (2, 10..18, 0)
+        );
+        assert_eq!(line.tokens, None);
+
+        let (first, last) = report.lines[2].tokens.clone().unwrap();
+        assert_eq!((first.token, first.range), ("one", 20..23));
+        assert_eq!((last.token, last.range), ("eight", 22..27));
+        assert_eq!(report.lines[2].value, 18);
+
+        // Byte positions, and a single token which is both the first and the last
+        let (first, last) = report.lines[3].tokens.clone().unwrap();
+        assert_eq!(first, last);
+        assert_eq!(first.range, 31..32);
+        assert_eq!(&input[first.range], "7");
+
+        let report = calibration_report("1\n\n", &DigitVocabulary::digits());
+        assert_eq!(report.lines.len(), 2);
+        assert_eq!(report.sum(), 11);
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        let words = HashMap::from([
+            ("null".to_string(), 0),
+            ("eins".to_string(), 1),
+            ("zwei".to_string(), 2),
+            ("drei".to_string(), 3),
+            ("sieben".to_string(), 7),
+            ("siebenundzwanzig".to_string(), 27),
+        ]);
+        let vocabulary = DigitVocabulary::new(DIGITS.into_iter().chain(ENGLISH_WORDS)).unwrap();
+        assert_eq!(vocabulary.value("seven"), Some(7));
+        assert_eq!(vocabulary.value("null"), None);
+
+        let vocabulary = DigitVocabulary::new(words).unwrap();
+        let input = indoc! {"
+            nulleins
+            zweinsieben
+            xdreiundsiebenundzwanzig
+            siebenundzwanzigx
+            two1nine
+        "};
+        // The longest token wins, and values are not limited to single digits
+        assert_eq!(
+            calibrate(input, &vocabulary),
+            1 + 27 + (3 * 10 + 27) + (27 * 10 + 27)
+        );
+
+        let vocabulary = DigitVocabulary::new([("zéro", 0), ("un", 1), ("deux", 2)]).unwrap();
+        assert_eq!(calibrate("deuxzéro\nunzéro\n", &vocabulary), 20 + 10);
+    }
+
+    #[test]
+    fn invalid_vocabulary() {
+        assert!(DigitVocabulary::new(Vec::<(&str, u32)>::new()).is_err());
+        assert!(DigitVocabulary::new([("one", 1), ("", 0)]).is_err());
+        assert!(DigitVocabulary::new([("one\ntwo", 1)]).is_err());
+        assert!(DigitVocabulary::new([("one", 1), ("one", 2)]).is_err());
+        assert!(DigitVocabulary::new([("one", 1), ("one", 1)]).is_ok());
+        assert!(DigitVocabulary::new([("1+", 1), (".", 2)]).is_ok());
+    }
 }
@@ -1,13 +1,29 @@
-use day01_trebuchet::{calibrate, DIGIT1, DIGIT2_FORWARD, DIGIT2_REVERSE};
-use std::{fs, path::Path};
+use common::InputSource;
+use day01_trebuchet::calibrate_reader;
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args();
 
-    let result = calibrate(&input, &DIGIT1, &DIGIT1);
-    println!("Part one result: {result}");
+    // Save the rendering to the image or text file given as the second argument. The rendering
+    // needs the whole input in memory, so without the argument the input is only streamed.
+    #[cfg(feature = "viz")]
+    if let Some(output_path) = std::env::args().nth(2) {
+        use day01_trebuchet::{calibrate, calibration_report, DigitVocabulary};
 
-    let result = calibrate(&input, &DIGIT2_FORWARD, &DIGIT2_REVERSE);
-    println!("Part two result: {result}");
+        let input = input.read_to_string().expect("Input missing");
+        let report = calibration_report(&input, &DigitVocabulary::english());
+        render::save(&report, output_path).expect("Failed to save the output");
+
+        println!(
+            "Part one result: {}",
+            calibrate(&input, &DigitVocabulary::digits())
+        );
+        println!("Part two result: {}", report.sum());
+        return;
+    }
+
+    let input = input.open().expect("Input missing");
+    let (result_part1, result_part2) = calibrate_reader(input).expect("Cannot read the input");
+    println!("Part one result: {result_part1}");
+    println!("Part two result: {result_part2}");
 }
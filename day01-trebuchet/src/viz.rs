@@ -0,0 +1,68 @@
+//! Picture of the input with the tokens which make up the value of each line.
+
+use crate::CalibrationReport;
+use grid::{Coord, Grid};
+use render::{Canvas, Cell, Color, Render};
+
+/// Number of columns between the lines and their values.
+const GAP: usize = 3;
+
+/// Draws each line of the input followed by its value, with the first token in green and the last
+/// token in blue, or in yellow if they are the same token. Lines without tokens have their value of
+/// 0 in red.
+impl Render for CalibrationReport<'_> {
+    fn render(&self) -> Canvas {
+        let text_width = |text: &str| text.chars().count();
+        let width = self
+            .lines
+            .iter()
+            .map(|line| text_width(line.text))
+            .max()
+            .unwrap_or(0);
+        let value_width = self
+            .lines
+            .iter()
+            .map(|line| line.value.to_string().len())
+            .max();
+        let mut canvas = Grid::filled(
+            width + GAP + value_width.unwrap_or(0),
+            self.lines.len(),
+            Cell::new(' '),
+        );
+
+        for (y, line) in self.lines.iter().enumerate() {
+            for (x, ch) in line.text.chars().enumerate() {
+                canvas[Coord::from((x, y))] = Cell::new(ch);
+            }
+            let value_color = if line.tokens.is_some() {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            for (x, ch) in line.value.to_string().chars().enumerate() {
+                canvas[Coord::from((width + GAP + x, y))] = Cell::new(ch).fg(value_color);
+            }
+
+            let Some((first, last)) = &line.tokens else {
+                continue;
+            };
+            // The ranges are in bytes of the input, the columns in characters of the line
+            let column = |offset: usize| text_width(&line.text[..offset - line.range.start]);
+            let mut highlight = |start: usize, end: usize, color: Color| {
+                for x in column(start)..column(end) {
+                    let coord = Coord::from((x, y));
+                    canvas[coord] = canvas[coord].fg(Color::Black).bg(color);
+                }
+            };
+
+            if first.range == last.range {
+                highlight(first.range.start, first.range.end, Color::Yellow);
+            } else {
+                highlight(first.range.start, first.range.end, Color::Green);
+                // Characters shared by overlapping tokens are coloured as the last token
+                highlight(last.range.start, last.range.end, Color::Blue);
+            }
+        }
+        canvas
+    }
+}
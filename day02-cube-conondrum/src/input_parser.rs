@@ -1,38 +1,32 @@
 use crate::{CubeSet, Game};
-use itertools::Itertools;
+use common::parse::{fail, parse_lines, tag, IResult};
+use common::ParseError;
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::u32,
+    character::complete::{alpha1, u32},
+    combinator::consumed,
     multi::separated_list1,
-    sequence::{separated_pair, terminated},
-    IResult,
+    sequence::separated_pair,
 };
 
-fn parse_red(input: &str) -> IResult<&str, CubeSet> {
-    let (remainder, count) = terminated(u32, tag(" red"))(input)?;
-    Ok((remainder, CubeSet::red(count)))
-}
-
-fn parse_green(input: &str) -> IResult<&str, CubeSet> {
-    let (remainder, count) = terminated(u32, tag(" green"))(input)?;
-    Ok((remainder, CubeSet::green(count)))
-}
-
-fn parse_blue(input: &str) -> IResult<&str, CubeSet> {
-    let (remainder, count) = terminated(u32, tag(" blue"))(input)?;
-    Ok((remainder, CubeSet::blue(count)))
+/// Parse the cubes of a single colour, e.g. "3 blue".
+fn parse_cubes(input: &str) -> IResult<&str, (&str, u32)> {
+    let (remainder, (count, color)) = separated_pair(u32, tag(" "), alpha1)(input)?;
+    Ok((remainder, (color, count)))
 }
 
+/// Parse the cubes of a draw. A colour may be given more than once, as long as its cubes add up
+/// to at most `u32::MAX`.
 fn parse_cube_set(input: &str) -> IResult<&str, CubeSet> {
-    let (remainder, colors) =
-        separated_list1(tag(", "), alt((parse_red, parse_green, parse_blue)))(input)?;
+    let (remainder, cubes) = separated_list1(tag(", "), consumed(parse_cubes))(input)?;
 
     let mut cubeset = CubeSet::new();
-    for color in colors {
-        cubeset.union(&color);
+    for (text, (color, count)) in cubes {
+        let Some(union) = cubeset.union(&CubeSet::single(color, count)) else {
+            let expected = format!("{color} cubes adding up to at most {}", u32::MAX);
+            return fail(text, expected);
+        };
+        cubeset = union;
     }
-
     Ok((remainder, cubeset))
 }
 
@@ -53,107 +47,72 @@ fn parse_game(input: &str) -> IResult<&str, Game> {
     Ok((remainder, game))
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Game>, Box<dyn std::error::Error + '_>> {
-    let games = input
-        .lines()
-        .map(|line| parse_game(line).map(|(_, game)| game))
-        .try_collect()?;
-
-    Ok(games)
+pub fn parse_input(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines(input, parse_game)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn cubes<const N: usize>(counts: [(&str, u32); N]) -> CubeSet {
+        counts.into_iter().collect()
+    }
+
     #[test]
     fn single_color() {
-        let (_, cubeset) = parse_red("1 red").unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 1,
-                green: 0,
-                blue: 0,
-            }
-        ));
-
-        let (_, cubeset) = parse_green("10 green").unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 0,
-                green: 10,
-                blue: 0,
-            }
-        ));
-
-        let (_, cubeset) = parse_blue("0 blue").unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 0,
-                green: 0,
-                blue: 0,
-            }
-        ));
+        let (_, cubeset) = parse_cube_set("1 red").unwrap();
+        assert_eq!(cubeset, CubeSet::single("red", 1));
+
+        let (_, cubeset) = parse_cube_set("10 green").unwrap();
+        assert_eq!(cubeset, CubeSet::single("green", 10));
+
+        let (_, cubeset) = parse_cube_set("0 blue").unwrap();
+        assert_eq!(cubeset, CubeSet::single("blue", 0));
+        assert_eq!(cubeset.count("blue"), 0);
+
+        let (_, cubeset) = parse_cube_set("7 turquoise").unwrap();
+        assert_eq!(cubeset.colors().collect::<Vec<_>>(), [("turquoise", 7)]);
+
+        assert!(parse_cube_set("7").is_err());
+        assert!(parse_cube_set("7 ").is_err());
+        assert!(parse_cube_set("red").is_err());
     }
 
     #[test]
     fn cube_set() {
         let input = "5 blue";
         let (_, cubeset) = parse_cube_set(input).unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 0,
-                green: 0,
-                blue: 5,
-            }
-        ));
+        assert_eq!(cubeset, cubes([("blue", 5)]));
 
         let input = "5 blue, 6 red";
         let (_, cubeset) = parse_cube_set(input).unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 6,
-                green: 0,
-                blue: 5,
-            }
-        ));
+        assert_eq!(cubeset, cubes([("red", 6), ("blue", 5)]));
 
         let input = "4 red, 5 blue, 6 green";
         let (_, cubeset) = parse_cube_set(input).unwrap();
-        assert!(matches!(
-            cubeset,
-            CubeSet {
-                red: 4,
-                green: 6,
-                blue: 5,
-            }
-        ));
+        assert_eq!(cubeset, cubes([("red", 4), ("green", 6), ("blue", 5)]));
+
+        let input = "4 red, 5 yellow, 6 green, 1 red";
+        let (_, cubeset) = parse_cube_set(input).unwrap();
+        assert_eq!(cubeset, cubes([("red", 5), ("green", 6), ("yellow", 5)]));
+
+        let error = parse_input("Game 1: 4294967295 red, 1 red\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 25));
+        assert_eq!(error.expected, "red cubes adding up to at most 4294967295");
     }
 
     #[test]
     fn cube_set_list() {
         let input = "4 red, 5 blue, 6 green; 10 blue";
         let (_, cubeset_list) = parse_cube_set_list(input).unwrap();
-        assert!(matches!(
-            cubeset_list[..],
+        assert_eq!(
+            cubeset_list,
             [
-                CubeSet {
-                    red: 4,
-                    green: 6,
-                    blue: 5,
-                },
-                CubeSet {
-                    red: 0,
-                    green: 0,
-                    blue: 10,
-                },
+                cubes([("red", 4), ("green", 6), ("blue", 5)]),
+                cubes([("blue", 10)])
             ]
-        ));
+        );
     }
 
     #[test]
@@ -161,5 +120,10 @@ mod tests {
         let input = "Game 10: 4 red, 5 blue, 6 green; 10 blue";
         let (_, game) = parse_game(input).unwrap();
         assert!(matches!(game, Game { id: 10, .. }));
+
+        // Found by fuzzing, used to overflow the number of cubes
+        let error = parse_input("Game 0: 4294967295 a, 9 a").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 23));
+        assert!(parse_input("Game 0: 9 a; 4294967295 a, 4294967295 a").is_err());
     }
 }
@@ -1,55 +1,91 @@
 //! https://adventofcode.com/2023/day/2
 
+use common::{ParseError, Solution};
+use std::collections::BTreeMap;
+
 pub mod input_parser;
+pub mod queries;
 
-#[derive(Clone, Debug)]
+/// The bag of part 1, with 12 red, 13 green and 14 blue cubes.
+pub const PART1_BAG: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+/// Number of cubes of each colour, e.g. in a single draw or in the bag. Colours which are not in
+/// the set have no cubes. A colour is still in the set if it was given with 0 cubes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct CubeSet {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+    counts: BTreeMap<String, u32>,
 }
 
 impl CubeSet {
     pub fn new() -> Self {
         CubeSet {
-            red: 0,
-            green: 0,
-            blue: 0,
+            counts: BTreeMap::new(),
         }
     }
 
-    pub fn red(count: u32) -> Self {
+    /// Set with the cubes of a single colour.
+    pub fn single(color: &str, count: u32) -> Self {
         CubeSet {
-            red: count,
-            green: 0,
-            blue: 0,
+            counts: BTreeMap::from([(color.to_string(), count)]),
         }
     }
 
-    pub fn green(count: u32) -> Self {
-        CubeSet {
-            red: 0,
-            green: count,
-            blue: 0,
+    /// Number of cubes of the colour, 0 if it is not in the set.
+    pub fn count(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    /// The colours in the set with the number of their cubes, in alphabetical order.
+    pub fn colors(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts
+            .iter()
+            .map(|(color, &count)| (color.as_str(), count))
+    }
+
+    pub fn is_subset(&self, other: &CubeSet) -> bool {
+        self.colors()
+            .all(|(color, count)| count <= other.count(color))
+    }
+
+    /// The cubes of both sets together, or `None` if the cubes of a colour add up to more than
+    /// `u32::MAX`.
+    pub fn union(&self, other: &CubeSet) -> Option<CubeSet> {
+        let mut union = self.clone();
+        for (color, count) in other.colors() {
+            let own = union.counts.entry(color.to_string()).or_insert(0);
+            *own = own.checked_add(count)?;
         }
+        Some(union)
     }
 
-    pub fn blue(count: u32) -> Self {
-        CubeSet {
-            red: 0,
-            green: 0,
-            blue: count,
+    /// Add cubes so that there are at least as many cubes of each colour as in the other set.
+    pub fn cover(&mut self, other: &CubeSet) {
+        for (color, count) in other.colors() {
+            let own = self.counts.entry(color.to_string()).or_insert(0);
+            *own = (*own).max(count);
         }
     }
 
-    pub fn is_subset(&self, other: &CubeSet) -> bool {
-        self.red <= other.red && self.green <= other.green && self.blue <= other.blue
+    /// Product of the numbers of cubes of all colours in the set, or `None` if it does not fit in
+    /// a `u128`, which takes at least five colours.
+    pub fn power(&self) -> Option<u128> {
+        self.colors()
+            .try_fold(1u128, |power, (_, count)| power.checked_mul(count as u128))
     }
+}
 
-    pub fn union(&mut self, other: &CubeSet) {
-        self.red += other.red;
-        self.green += other.green;
-        self.blue += other.blue;
+/// Union of the cubes, so a colour given more than once gets the cubes of all of them.
+///
+/// Panics if the cubes of a colour add up to more than `u32::MAX`. The parser uses
+/// [`CubeSet::union`] instead, which reports it.
+impl<S: AsRef<str>> FromIterator<(S, u32)> for CubeSet {
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(iter: I) -> Self {
+        let mut cubeset = CubeSet::new();
+        for (color, count) in iter {
+            let cubes = CubeSet::single(color.as_ref(), count);
+            cubeset = cubeset.union(&cubes).expect("Number of cubes fits in u32");
+        }
+        cubeset
     }
 }
 
@@ -59,7 +95,7 @@ pub struct Game {
     pub draws: Vec<CubeSet>,
 }
 
-fn possible_games(games: &Vec<Game>, bag_configuration: &CubeSet) -> Vec<Game> {
+fn possible_games(games: &[Game], bag_configuration: &CubeSet) -> Vec<Game> {
     games
         .iter()
         .filter(|game| {
@@ -72,7 +108,7 @@ fn possible_games(games: &Vec<Game>, bag_configuration: &CubeSet) -> Vec<Game> {
 }
 
 // Return the sum of possible game ids for a given bag configuration
-pub fn solve_part1(games: &Vec<Game>, bag_configuration: &CubeSet) -> u32 {
+pub fn solve_part1(games: &[Game], bag_configuration: &CubeSet) -> u32 {
     possible_games(games, bag_configuration)
         .iter()
         .map(|game| game.id)
@@ -83,30 +119,42 @@ fn min_bag_config_for_game(game: &Game) -> CubeSet {
     game.draws
         .iter()
         .fold(CubeSet::new(), |mut bag_config, cubeset| {
-            if cubeset.red > bag_config.red {
-                bag_config.red = cubeset.red;
-            }
-            if cubeset.green > bag_config.green {
-                bag_config.green = cubeset.green;
-            }
-            if cubeset.blue > bag_config.blue {
-                bag_config.blue = cubeset.blue;
-            }
+            bag_config.cover(cubeset);
             bag_config
         })
 }
 
 // Return the sum of powers of minimum cubesets for each game. The power of a cubeset is equal to
-// the product of red, green, and blue cubes.
-pub fn solve_part2(games: &Vec<Game>) -> u32 {
+// the product of the numbers of cubes of all colours seen in the game. `None` if a power or the sum
+// does not fit in a u128.
+pub fn solve_part2(games: &[Game]) -> Option<u128> {
     games
         .iter()
-        .map(|game| {
-            let bag_config = min_bag_config_for_game(game);
-            let power = bag_config.red * bag_config.green * bag_config.blue;
-            power
-        })
-        .sum()
+        .map(|game| min_bag_config_for_game(game).power())
+        .try_fold(0u128, |sum, power| sum.checked_add(power?))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = Vec<Game>;
+    type Output = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input, &PART1_BAG.into_iter().collect()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        // With the three colours of the puzzle a power is below 2^96, so the sum only overflows
+        // with more games than any input can have
+        let sum = solve_part2(input);
+        Some(sum.expect("Sum of powers fits in u128 with the colours of the puzzle"))
+    }
 }
 
 #[cfg(test)]
@@ -125,16 +173,98 @@ mod tests {
         "};
 
         let games = input_parser::parse_input(input).unwrap();
-        let bag_config = CubeSet {
-            red: 12,
-            green: 13,
-            blue: 14,
-        };
+        let bag_config = PART1_BAG.into_iter().collect();
 
         let result = solve_part1(&games, &bag_config);
         assert_eq!(result, 8);
 
         let result = solve_part2(&games);
-        assert_eq!(result, 2286);
+        assert_eq!(result, Some(2286));
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = indoc! {"
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+        "};
+
+        let games = Day02::parse(input).unwrap();
+        assert_eq!(Day02::part1(&games), 8);
+        assert_eq!(Day02::part2(&games), Some(2286));
+    }
+
+    #[test]
+    fn more_colors() {
+        let input = indoc! {"
+            Game 1: 3 blue, 4 red; 1 red, 2 yellow, 6 blue; 2 green
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+            Game 3: 5 violet; 2 violet, 3 yellow; 1 yellow, 1 violet
+            Game 4: 0 red, 2 blue
+        "};
+        let games = input_parser::parse_input(input).unwrap();
+
+        // Colours which are not in the bag have no cubes
+        assert_eq!(solve_part1(&games, &PART1_BAG.into_iter().collect()), 2 + 4);
+        let bag = [
+            ("red", 4),
+            ("green", 3),
+            ("blue", 6),
+            ("yellow", 3),
+            ("violet", 5),
+        ];
+        assert_eq!(
+            solve_part1(&games, &bag.into_iter().collect()),
+            1 + 2 + 3 + 4
+        );
+
+        // Only the colours seen in the game count, but a colour seen with 0 cubes does
+        assert_eq!(solve_part2(&games), Some(4 * 2 * 6 * 2 + 12 + 5 * 3));
+        assert_eq!(solve_part2(&games[3..]), Some(0));
+
+        let mut bag: CubeSet = [("red", 2), ("blue", 1)].into_iter().collect();
+        bag.cover(&[("blue", 3), ("green", 1)].into_iter().collect());
+        assert_eq!(
+            bag.colors().collect::<Vec<_>>(),
+            [("blue", 3), ("green", 1), ("red", 2)]
+        );
+        assert_eq!(bag.count("violet"), 0);
+    }
+
+    #[test]
+    fn large_powers() {
+        // The power of four colours of 1000 cubes doesn't fit in a u32
+        let input = indoc! {"
+            Game 1: 1000 red, 1000 green, 1000 blue, 1000 yellow
+            Game 2: 1000 red, 1000 green, 1000 blue; 1000 yellow
+        "};
+        let games = input_parser::parse_input(input).unwrap();
+        assert_eq!(solve_part2(&games), Some(2_000_000_000_000));
+
+        // Neither do the powers of games with large counts in a u64
+        let input = indoc! {"
+            Game 1: 4294967295 red, 4294967295 green, 4294967295 blue
+            Game 2: 4294967295 red, 4294967295 green, 4294967295 blue, 4294967295 yellow
+        "};
+        let games = input_parser::parse_input(input).unwrap();
+        let max = u32::MAX as u128;
+        assert_eq!(solve_part2(&games), Some(max.pow(3) + max.pow(4)));
+
+        let colors = ["red", "green", "blue", "yellow", "violet"];
+        let bag: CubeSet = colors.iter().map(|color| (color, 10_000_000)).collect();
+        assert_eq!(bag.power(), Some(10u128.pow(35)));
+        let bag = bag.union(&CubeSet::single("black", 10_000_000)).unwrap();
+        assert_eq!(bag.power(), None);
+        let games = [Game {
+            id: 1,
+            draws: vec![bag],
+        }];
+        assert_eq!(solve_part2(&games), None);
+
+        let bag = CubeSet::single("red", u32::MAX);
+        assert_eq!(bag.union(&CubeSet::single("red", 1)), None);
     }
 }
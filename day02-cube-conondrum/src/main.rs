@@ -1,20 +1,17 @@
-use day02_cube_conondrum::{input_parser::parse_input, solve_part1, solve_part2, CubeSet};
-use std::{fs, path::Path};
+use common::InputSource;
+use day02_cube_conondrum::{input_parser::parse_input, solve_part1, solve_part2, PART1_BAG};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
     let games = parse_input(&input).expect("Invalid input");
 
-    let bag_config = CubeSet {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
+    let bag_config = PART1_BAG.into_iter().collect();
     let result = solve_part1(&games, &bag_config);
     println!("Part one result: {result}");
 
-    let result = solve_part2(&games);
+    let result = solve_part2(&games).expect("Sum of powers too large");
     println!("Part two result: {result}");
 }
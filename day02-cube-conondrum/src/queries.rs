@@ -0,0 +1,267 @@
+//! Questions about the bags which make the games possible, beyond checking a single bag.
+
+use crate::{min_bag_config_for_game, possible_games, CubeSet, Game};
+use std::collections::BTreeMap;
+
+/// The smallest bag with which every game is possible.
+pub fn min_bag_for_all(games: &[Game]) -> CubeSet {
+    games.iter().fold(CubeSet::new(), |mut bag, game| {
+        bag.cover(&min_bag_config_for_game(game));
+        bag
+    })
+}
+
+/// Bag on the Pareto frontier of the number of cubes against the number of possible games.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontierBag {
+    pub bag: CubeSet,
+    /// Total number of cubes in the bag
+    pub cubes: u64,
+    /// Ids of the games which are possible with the bag
+    pub possible: Vec<u32>,
+}
+
+/// The bags with at most `budget` cubes in total which make the most games possible for their
+/// number of cubes, ordered by the number of cubes. Each bag makes more games possible than the
+/// one before it, and no bag with fewer cubes makes as many games possible.
+///
+/// Only bags where each colour has as many cubes as some game needs are tried, since any other
+/// bag makes the same games possible as such a bag with fewer cubes. That is still every
+/// combination of these counts, so the time grows exponentially with the number of colours.
+pub fn pareto_frontier(games: &[Game], budget: u32) -> Vec<FrontierBag> {
+    let needed: Vec<CubeSet> = games.iter().map(min_bag_config_for_game).collect();
+
+    // For each colour, the numbers of its cubes some game needs, and 0 for none
+    let mut counts: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for (color, count) in needed.iter().flat_map(CubeSet::colors) {
+        counts.entry(color).or_insert_with(|| vec![0]).push(count);
+    }
+    let counts: Vec<(&str, Vec<u32>)> = counts
+        .into_iter()
+        .map(|(color, mut counts)| {
+            counts.sort_unstable();
+            counts.dedup();
+            (color, counts)
+        })
+        .collect();
+
+    // The bag which makes the most games possible for each number of cubes
+    let mut best: BTreeMap<u64, (usize, CubeSet)> = BTreeMap::new();
+    let all: Vec<usize> = (0..games.len()).collect();
+    let mut bag = Vec::new();
+    search(&needed, &counts, &all, budget, &mut bag, &mut best);
+
+    let mut frontier: Vec<FrontierBag> = Vec::new();
+    for (cubes, (possible, bag)) in best {
+        if frontier
+            .last()
+            .is_none_or(|last| possible > last.possible.len())
+        {
+            let possible = possible_games(games, &bag)
+                .iter()
+                .map(|game| game.id)
+                .collect();
+            frontier.push(FrontierBag {
+                bag,
+                cubes,
+                possible,
+            });
+        }
+    }
+    frontier
+}
+
+/// Choose the number of cubes of the next colour of the bag, keeping only the games which are
+/// still possible with it, and record the bag once all colours are chosen.
+fn search<'a>(
+    needed: &[CubeSet],
+    counts: &[(&'a str, Vec<u32>)],
+    possible: &[usize],
+    budget: u32,
+    bag: &mut Vec<(&'a str, u32)>,
+    best: &mut BTreeMap<u64, (usize, CubeSet)>,
+) {
+    let Some(((color, color_counts), rest)) = counts.split_first() else {
+        let cubes = bag.iter().map(|&(_, count)| u64::from(count)).sum();
+        if best
+            .get(&cubes)
+            .is_none_or(|(best, _)| possible.len() > *best)
+        {
+            let bag = bag
+                .iter()
+                .copied()
+                .filter(|&(_, count)| count > 0)
+                .collect();
+            best.insert(cubes, (possible.len(), bag));
+        }
+        return;
+    };
+
+    // Counted in u64, as the counts of two colours can add up to more than a u32
+    let spent: u64 = bag.iter().map(|&(_, count)| u64::from(count)).sum();
+    for &count in color_counts
+        .iter()
+        .take_while(|&&count| spent + u64::from(count) <= u64::from(budget))
+    {
+        let still_possible: Vec<usize> = possible
+            .iter()
+            .copied()
+            .filter(|&game| needed[game].count(color) <= count)
+            .collect();
+        bag.push((color, count));
+        search(needed, rest, &still_possible, budget, bag, best);
+        bag.pop();
+    }
+}
+
+/// Draw of a game with more cubes of a colour than the bag has.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Index of the draw in the game, starting from 0
+    pub draw: usize,
+    pub color: String,
+    /// Number of cubes of the colour in the draw
+    pub drawn: u32,
+    /// Number of cubes of the colour in the bag
+    pub available: u32,
+}
+
+/// Game which is not possible with the bag, with every draw and colour which makes it impossible.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImpossibleGame {
+    pub id: u32,
+    pub violations: Vec<Violation>,
+}
+
+/// The games which are not possible with the bag, in the order of the input, and why.
+pub fn impossible_games(games: &[Game], bag: &CubeSet) -> Vec<ImpossibleGame> {
+    games
+        .iter()
+        .filter_map(|game| {
+            let violations: Vec<Violation> = game
+                .draws
+                .iter()
+                .enumerate()
+                .flat_map(|(draw, cubeset)| {
+                    cubeset
+                        .colors()
+                        .filter(|&(color, drawn)| drawn > bag.count(color))
+                        .map(move |(color, drawn)| Violation {
+                            draw,
+                            color: color.to_string(),
+                            drawn,
+                            available: bag.count(color),
+                        })
+                })
+                .collect();
+
+            (!violations.is_empty()).then_some(ImpossibleGame {
+                id: game.id,
+                violations,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_parser::parse_input;
+    use indoc::indoc;
+
+    const INPUT: &str = indoc! {"
+        Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+        Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+        Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+        Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+        Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+    "};
+
+    fn cubes<const N: usize>(counts: [(&str, u32); N]) -> CubeSet {
+        counts.into_iter().collect()
+    }
+
+    #[test]
+    fn test_min_bag_for_all() {
+        let games = parse_input(INPUT).unwrap();
+        let bag = min_bag_for_all(&games);
+        assert_eq!(bag, cubes([("red", 20), ("green", 13), ("blue", 15)]));
+        assert_eq!(possible_games(&games, &bag).len(), games.len());
+        assert_eq!(min_bag_for_all(&[]), CubeSet::new());
+    }
+
+    #[test]
+    fn test_pareto_frontier() {
+        let games = parse_input(INPUT).unwrap();
+        let frontier = pareto_frontier(&games, 100);
+        let points: Vec<(u64, Vec<u32>)> = frontier
+            .iter()
+            .map(|point| (point.cubes, point.possible.clone()))
+            .collect();
+        assert_eq!(
+            points,
+            [
+                (0, vec![]),
+                (8, vec![2]),
+                (13, vec![2, 5]),
+                (15, vec![1, 2, 5]),
+                (32, vec![1, 2, 4, 5]),
+                (48, vec![1, 2, 3, 4, 5]),
+            ]
+        );
+        assert_eq!(
+            frontier[1].bag,
+            cubes([("red", 1), ("green", 3), ("blue", 4)])
+        );
+        assert_eq!(frontier[5].bag, min_bag_for_all(&games));
+
+        // The frontier is cut off by the budget
+        assert_eq!(pareto_frontier(&games, 47).len(), 5);
+        assert_eq!(pareto_frontier(&games, 5).len(), 1);
+    }
+
+    #[test]
+    fn test_pareto_frontier_large_counts() {
+        // Game 1 needs more cubes than any budget, which must not overflow the count
+        let input = indoc! {"
+            Game 1: 4294967295 red, 4294967295 green
+            Game 2: 4294967295 red
+        "};
+        let games = parse_input(input).unwrap();
+        let points: Vec<(u64, Vec<u32>)> = pareto_frontier(&games, u32::MAX)
+            .iter()
+            .map(|point| (point.cubes, point.possible.clone()))
+            .collect();
+        assert_eq!(points, [(0, vec![]), (4294967295, vec![2])]);
+    }
+
+    #[test]
+    fn test_impossible_games() {
+        let games = parse_input(INPUT).unwrap();
+        let bag = cubes([("red", 12), ("green", 13), ("blue", 14)]);
+        let impossible = impossible_games(&games, &bag);
+
+        let violation = |draw, color: &str, drawn, available| Violation {
+            draw,
+            color: color.to_string(),
+            drawn,
+            available,
+        };
+        assert_eq!(
+            impossible,
+            [
+                ImpossibleGame {
+                    id: 3,
+                    violations: vec![violation(0, "red", 20, 12)],
+                },
+                ImpossibleGame {
+                    id: 4,
+                    violations: vec![violation(2, "blue", 15, 14), violation(2, "red", 14, 12)],
+                },
+            ]
+        );
+
+        let impossible = impossible_games(&games, &min_bag_for_all(&games));
+        assert_eq!(impossible, []);
+    }
+}
@@ -1,28 +1,26 @@
 //! https://adventofcode.com/2023/day/3
 
+use common::{ParseError, Solution};
+use grid::{Coord, Grid};
+use rules::Rules;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Coordinate {
-    pub x: i32,
-    pub y: i32,
+pub mod rules;
+pub mod stream;
+#[cfg(feature = "viz")]
+mod viz;
+
+/// Tiles of a number: the columns from `x1` to `x2` of row `y`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct NumberCoordinate {
+    pub x1: i64,
+    pub x2: i64,
+    pub y: i64,
 }
 
-impl From<(i32, i32)> for Coordinate {
-    fn from((x, y): (i32, i32)) -> Self {
-        Coordinate { x, y }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct NumberCoordinate {
-    pub x1: i32,
-    pub x2: i32,
-    pub y: i32,
-}
-
-impl From<Coordinate> for NumberCoordinate {
-    fn from(coord: Coordinate) -> Self {
+impl From<Coord> for NumberCoordinate {
+    fn from(coord: Coord) -> Self {
         NumberCoordinate {
             x1: coord.x,
             x2: coord.x,
@@ -31,19 +29,29 @@ impl From<Coordinate> for NumberCoordinate {
     }
 }
 
-#[derive(Debug)]
+impl NumberCoordinate {
+    /// Coordinates of the digits of the number.
+    pub fn digit_coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (self.x1..=self.x2).map(|x| Coord::new(x, self.y))
+    }
+}
+
+#[derive(Debug, Clone)]
 struct DiagramNumber {
     number: u32,
     coord: NumberCoordinate,
     is_part_number: bool,
+    /// Coordinates of the symbols which the number touches, by row and then by column
+    symbols: Vec<Coord>,
 }
 
 impl DiagramNumber {
-    pub fn new(digit: u32, coord: Coordinate) -> Self {
+    pub fn new(digit: u32, coord: Coord) -> Self {
         DiagramNumber {
             number: digit,
             coord: NumberCoordinate::from(coord),
             is_part_number: false,
+            symbols: Vec::new(),
         }
     }
 
@@ -52,193 +60,290 @@ impl DiagramNumber {
         self.coord.x2 += 1;
     }
 
-    pub fn neighbor_coords(&self) -> HashSet<Coordinate> {
-        let mut coords: HashSet<Coordinate> = HashSet::new();
-
-        let row_up = self.coord.y - 1;
-        let row_down = self.coord.y + 1;
-        let col_left = self.coord.x1 - 1;
-        let col_right = self.coord.x2 + 1;
-
-        // Insert neighbor coords above and below the number
-        for i in col_left..=col_right {
-            coords.insert(Coordinate::from((i, row_up)));
-            coords.insert(Coordinate::from((i, row_down)));
-        }
-
-        // Insert neighbor coords left and right of the number
-        for j in row_up..=row_down {
-            coords.insert(Coordinate::from((col_left, j)));
-            coords.insert(Coordinate::from((col_right, j)));
-        }
-
-        coords
+    /// Coordinates of all the tiles in the schematic that touch the number, i.e. which are at one
+    /// of the offsets from one of its digits. For simplicity, these may include the digits of the
+    /// number itself.
+    pub fn neighbor_coords(&self, schematic: &Grid<char>, offsets: &[Coord]) -> HashSet<Coord> {
+        self.coord
+            .digit_coords()
+            .flat_map(|coord| offsets.iter().map(move |&offset| coord + offset))
+            .filter(|&coord| schematic.contains(coord))
+            .collect()
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Symbol {
-    is_star_symbol: bool,
-    adjacent_part_numbers: u32,
-    product_of_adjacent_part_numbers: u32,
+    symbol: char,
+    is_gear_symbol: bool,
+    /// Indices of the part numbers which touch the symbol
+    adjacent_part_numbers: Vec<usize>,
 }
 
 impl Symbol {
-    pub fn new(is_star_symbol: bool) -> Symbol {
+    pub fn new(symbol: char, is_gear_symbol: bool) -> Symbol {
         Symbol {
-            is_star_symbol: is_star_symbol,
-            adjacent_part_numbers: 0,
-            product_of_adjacent_part_numbers: 1,
+            symbol,
+            is_gear_symbol,
+            adjacent_part_numbers: Vec::new(),
         }
     }
 
-    pub fn is_gear_symbol(&self) -> bool {
-        self.is_star_symbol && self.adjacent_part_numbers == 2
+    pub fn is_gear(&self, rules: &Rules) -> bool {
+        self.is_gear_symbol && self.adjacent_part_numbers.len() == rules.gear_part_numbers as usize
+    }
+
+    /// The product of the part numbers touching the symbol, or `None` if it is not a gear or the
+    /// product does not fit in a `u128`, which takes a gear of at least five numbers.
+    fn gear_ratio(&self, numbers: &[DiagramNumber], rules: &Rules) -> Option<u128> {
+        if !self.is_gear(rules) {
+            return None;
+        }
+        self.adjacent_part_numbers
+            .iter()
+            .try_fold(1u128, |product, &index| {
+                product.checked_mul(numbers[index].number as u128)
+            })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Solver {
+    schematic: Grid<char>,
     numbers: Vec<DiagramNumber>,
-    symbols_by_coord: HashMap<Coordinate, Symbol>,
+    symbols_by_coord: HashMap<Coord, Symbol>,
+    rules: Rules,
 }
 
 impl Solver {
-    pub fn parse_from_input(input: &str) -> Solver {
+    /// Parse the schematic with the rules of the puzzle.
+    pub fn parse_from_input(input: &str) -> Result<Solver, ParseError> {
+        Solver::parse_with_rules(input, Rules::default())
+    }
+
+    /// Parse the schematic, whose symbols and gears follow the rules.
+    pub fn parse_with_rules(input: &str, rules: Rules) -> Result<Solver, ParseError> {
+        // Longer numbers could overflow, while the ones in the real input have at most 3 digits
+        const MAX_DIGITS: usize = 9;
+        let mut digits = 0;
+        let schematic = Grid::parse(input, |coord, ch| {
+            digits = match ch.is_ascii_digit() {
+                true if coord.x > 0 => digits + 1,
+                true => 1,
+                false => 0,
+            };
+            match digits {
+                0..=MAX_DIGITS => Ok(ch),
+                _ => Err("a number of at most 9 digits"),
+            }
+        })?;
+
         let mut numbers: Vec<DiagramNumber> = vec![];
-        let mut symbols_by_coord: HashMap<Coordinate, Symbol> = HashMap::new();
+        let mut symbols_by_coord: HashMap<Coord, Symbol> = HashMap::new();
 
         // Track the current number as we assemble it digit by digit
         let mut current_number: Option<DiagramNumber> = None;
 
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                let coord = Coordinate::from((i as i32, j as i32));
-                match ch {
-                    '0'..='9' => {
-                        let digit = ch.to_digit(10).expect("Failed to convert digit {ch}");
-                        match current_number {
-                            None => {
-                                current_number = Some(DiagramNumber::new(digit, coord));
-                            },
-                            Some(ref mut current_number) => {
-                                current_number.add_digit(digit);
-                            },
-                        }
-                    },
-                    _ => {
-                        if let Some(current_number_inner) = current_number {
-                            numbers.push(current_number_inner);
-                            current_number = None;
-                        }
-                        if ch != '.' {
-                            let symbol = Symbol::new(ch == '*');
-                            symbols_by_coord.insert(coord, symbol);
-                        }
-                    },
-                }
-            }
-
+        for (coord, &ch) in schematic.iter() {
             // If a number finishes on the last character of the line, we need to push it now,
             // because next line can start with another number and we don't want them to be treated
             // as one long number that spans over the end of line.
-            if let Some(current_number_inner) = current_number {
-                numbers.push(current_number_inner);
-                current_number = None;
+            if coord.x == 0 {
+                if let Some(current_number_inner) = current_number {
+                    numbers.push(current_number_inner);
+                    current_number = None;
+                }
+            }
+
+            match ch.to_digit(10) {
+                Some(digit) => match current_number {
+                    None => {
+                        current_number = Some(DiagramNumber::new(digit, coord));
+                    },
+                    Some(ref mut current_number) => {
+                        current_number.add_digit(digit);
+                    },
+                },
+                None => {
+                    if let Some(current_number_inner) = current_number {
+                        numbers.push(current_number_inner);
+                        current_number = None;
+                    }
+                    if rules.symbols.is_symbol(ch) {
+                        let symbol = Symbol::new(ch, rules.is_gear_symbol(ch));
+                        symbols_by_coord.insert(coord, symbol);
+                    }
+                },
             }
         }
+        numbers.extend(current_number);
 
-        Solver {
+        let mut solver = Solver {
+            schematic,
             numbers,
             symbols_by_coord,
-        }
+            rules,
+        };
+        solver.mark_part_numbers_and_gears();
+        Ok(solver)
     }
 
     fn mark_part_numbers_and_gears(&mut self) {
-        let symbol_coords: HashSet<Coordinate> = self.symbols_by_coord.keys().cloned().collect();
-
-        for number in &mut self.numbers {
-            let neighbor_coords = number.neighbor_coords();
-            if neighbor_coords.is_disjoint(&symbol_coords) {
+        let offsets = self.rules.neighbourhood.offsets();
+        for (index, number) in self.numbers.iter_mut().enumerate() {
+            let neighbor_coords = number.neighbor_coords(&self.schematic, &offsets);
+            number.symbols = neighbor_coords
+                .into_iter()
+                .filter(|coord| self.symbols_by_coord.contains_key(coord))
+                .collect();
+            if number.symbols.is_empty() {
                 continue;
             }
 
             // Otherwise, we have a part number. We should update the symbol info as well.
             number.is_part_number = true;
-            for coord in &neighbor_coords {
-                if let Some(symbol) = self.symbols_by_coord.get_mut(coord) {
-                    symbol.adjacent_part_numbers += 1;
-                    symbol.product_of_adjacent_part_numbers *= number.number;
-                }
+            number.symbols.sort_by_key(|coord| (coord.y, coord.x));
+            for coord in &number.symbols {
+                let symbol = self.symbols_by_coord.get_mut(coord).unwrap();
+                symbol.adjacent_part_numbers.push(index);
             }
         }
     }
 
-    fn sum_of_part_numbers(&self) -> u32 {
+    fn sum_of_part_numbers(&self) -> u64 {
         self.numbers
             .iter()
             .filter(|number| number.is_part_number)
-            .map(|number| number.number)
+            .map(|number| number.number as u64)
             .sum()
     }
 
-    fn sum_of_gear_ratios(&self) -> u32 {
+    /// `None` if a gear ratio or the sum does not fit in a `u128`.
+    fn sum_of_gear_ratios(&self) -> Option<u128> {
         self.symbols_by_coord
             .values()
-            .filter(|symbol| symbol.is_gear_symbol())
-            .map(|symbol| symbol.product_of_adjacent_part_numbers)
-            .sum()
+            .filter(|symbol| symbol.is_gear(&self.rules))
+            .try_fold(0u128, |sum, symbol| {
+                sum.checked_add(symbol.gear_ratio(&self.numbers, &self.rules)?)
+            })
     }
 
-    pub fn solve(&mut self) -> (u32, u32) {
-        self.mark_part_numbers_and_gears();
+    /// The answers to both parts. The sum of the gear ratios is `None` if it does not fit in a
+    /// `u128`, which can only happen if the rules make gears of at least three numbers.
+    pub fn solve(&self) -> (u64, Option<u128>) {
         let part1 = self.sum_of_part_numbers();
         let part2 = self.sum_of_gear_ratios();
         (part1, part2)
     }
 
-    // Print the input with part numbers marked in green, other numbers marked in red, and gear
-    // symbols marked in blue.
-    //
-    // Call this after running the solve method.
-    pub fn print_colored_input(&self, input: &str) {
-        use colored::Colorize;
-
-        let mut red_digits: HashSet<Coordinate> = HashSet::new();
-        let mut green_digits: HashSet<Coordinate> = HashSet::new();
-        for number in &self.numbers {
-            for i in number.coord.x1..=number.coord.x2 {
-                let coord = Coordinate::from((i, number.coord.y));
-                if number.is_part_number {
-                    green_digits.insert(coord);
-                } else {
-                    red_digits.insert(coord);
+    /// Every number and symbol of the schematic, with what they touch, together with the sums of
+    /// both parts.
+    pub fn report(&self) -> SchematicReport {
+        let mut symbol_coords: Vec<&Coord> = self.symbols_by_coord.keys().collect();
+        symbol_coords.sort_by_key(|coord| (coord.y, coord.x));
+        let symbol_indices: HashMap<&Coord, usize> = symbol_coords
+            .iter()
+            .enumerate()
+            .map(|(index, &coord)| (coord, index))
+            .collect();
+
+        let numbers = self
+            .numbers
+            .iter()
+            .map(|number| NumberReport {
+                number: number.number,
+                span: number.coord.clone(),
+                is_part_number: number.is_part_number,
+                symbols: number
+                    .symbols
+                    .iter()
+                    .map(|coord| symbol_indices[coord])
+                    .collect(),
+            })
+            .collect();
+
+        let symbols = symbol_coords
+            .into_iter()
+            .map(|coord| {
+                let symbol = &self.symbols_by_coord[coord];
+                let is_gear = symbol.is_gear(&self.rules);
+                SymbolReport {
+                    symbol: symbol.symbol,
+                    x: coord.x,
+                    y: coord.y,
+                    numbers: symbol.adjacent_part_numbers.clone(),
+                    is_gear,
+                    ratio: symbol.gear_ratio(&self.numbers, &self.rules),
                 }
-            }
-        }
+            })
+            .collect();
 
-        let mut blue_symbols: HashSet<Coordinate> = HashSet::new();
-        for (coord, symbol) in &self.symbols_by_coord {
-            if symbol.is_gear_symbol() {
-                blue_symbols.insert(coord.clone());
-            }
+        let (part_number_sum, gear_ratio_sum) = self.solve();
+        SchematicReport {
+            numbers,
+            symbols,
+            part_number_sum,
+            gear_ratio_sum,
         }
+    }
+}
 
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                let coord = Coordinate::from((i as i32, j as i32));
-                if red_digits.contains(&coord) {
-                    print!("{}", ch.to_string().red());
-                } else if green_digits.contains(&coord) {
-                    print!("{}", ch.to_string().green());
-                } else if blue_symbols.contains(&coord) {
-                    print!("{}", ch.to_string().blue());
-                } else {
-                    print!("{}", ch);
-                }
-            }
-            println!()
-        }
+/// Number of the schematic in a [`SchematicReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NumberReport {
+    pub number: u32,
+    pub span: NumberCoordinate,
+    pub is_part_number: bool,
+    /// Indices in [`SchematicReport::symbols`] of the symbols the number touches
+    pub symbols: Vec<usize>,
+}
+
+/// Symbol of the schematic in a [`SchematicReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolReport {
+    pub symbol: char,
+    pub x: i64,
+    pub y: i64,
+    /// Indices in [`SchematicReport::numbers`] of the numbers which touch the symbol, which are
+    /// all part numbers
+    pub numbers: Vec<usize>,
+    pub is_gear: bool,
+    /// Product of the numbers, only for gears, and only if it fits in a `u128`
+    pub ratio: Option<u128>,
+}
+
+/// Details of the schematic behind the answers, e.g. to find out why a number is not a part
+/// number. Numbers and symbols are ordered by row and then by column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchematicReport {
+    pub numbers: Vec<NumberReport>,
+    pub symbols: Vec<SymbolReport>,
+    /// Answer to part 1
+    pub part_number_sum: u64,
+    /// Answer to part 2, if it fits in a `u128`
+    pub gear_ratio_sum: Option<u128>,
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Solver;
+    type Output = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Solver::parse_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        input.solve().0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        // With the rules of the puzzle a gear ratio is below 10^18, so the sum only overflows with
+        // more gears than any input can have
+        let sum = input.solve().1;
+        Some(sum.expect("Sum of gear ratios fits in u128 with the rules of the puzzle"))
     }
 }
 
@@ -262,11 +367,11 @@ mod tests {
             .664.598..
         "};
 
-        let mut solver = Solver::parse_from_input(input);
+        let solver = Solver::parse_from_input(input).unwrap();
 
         let (result_part1, result_part2) = solver.solve();
         assert_eq!(result_part1, 4361);
-        assert_eq!(result_part2, 467835);
+        assert_eq!(result_part2, Some(467835));
     }
 
     #[test]
@@ -278,10 +383,224 @@ mod tests {
             ..300
         "};
 
-        let mut solver = Solver::parse_from_input(input);
+        let solver = Solver::parse_from_input(input).unwrap();
 
         let (result_part1, result_part2) = solver.solve();
         assert_eq!(result_part1, 600);
-        assert_eq!(result_part2, 20000);
+        assert_eq!(result_part2, Some(20000));
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = indoc! {"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "};
+
+        let solver = Day03::parse(input).unwrap();
+        assert_eq!(Day03::part2(&solver), Some(467835));
+        assert_eq!(Day03::part1(&solver), 4361);
+    }
+
+    #[test]
+    fn custom_rules() {
+        use rules::{Neighbourhood, Symbols};
+
+        let solve = |input: &str, rules: &Rules| {
+            let solver = Solver::parse_with_rules(input, rules.clone()).unwrap();
+            let (part1, part2) = solver.solve();
+            (part1, part2.unwrap())
+        };
+        let input = indoc! {"
+            1.2
+            .*.
+            3..
+        "};
+        assert_eq!(solve(input, &Rules::default()), (6, 0));
+
+        let rules = Rules {
+            gear_part_numbers: 3,
+            ..Rules::default()
+        };
+        assert_eq!(solve(input, &rules), (6, 6));
+
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Diamond(1),
+            ..Rules::default()
+        };
+        assert_eq!(solve(input, &rules), (0, 0));
+        assert_eq!(solve("1.*.2\n", &rules), (0, 0));
+
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Square(2),
+            ..Rules::default()
+        };
+        assert_eq!(solve("1.*.2\n", &rules), (3, 2));
+        assert_eq!(solve("1..*..2\n", &rules), (0, 0));
+
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Offsets(vec![Coord::new(1, 0)]),
+            ..Rules::default()
+        };
+        assert_eq!(solve("1*2\n", &rules), (1, 0));
+
+        // Symbols and gears
+        for symbols in [
+            Symbols::Only("#".to_string()),
+            Symbols::AllExcept(".*".to_string()),
+        ] {
+            let rules = Rules {
+                symbols,
+                ..Rules::default()
+            };
+            assert_eq!(solve(input, &rules), (0, 0));
+        }
+        assert_eq!(solve("12#34\n", &Rules::default()), (46, 0));
+        let rules = Rules {
+            gear_symbols: "#*".to_string(),
+            ..Rules::default()
+        };
+        assert_eq!(solve("12#34\n", &rules), (46, 408));
+    }
+
+    #[test]
+    fn test_report() {
+        let input = indoc! {"
+            ..100
+            200*.
+            ...-.
+            ..300
+            7....
+        "};
+        let report = Solver::parse_from_input(input).unwrap().report();
+        assert_eq!(
+            (report.part_number_sum, report.gear_ratio_sum),
+            (600, Some(20000))
+        );
+
+        let numbers: Vec<(u32, bool, &[usize])> = report
+            .numbers
+            .iter()
+            .map(|number| {
+                (
+                    number.number,
+                    number.is_part_number,
+                    number.symbols.as_slice(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            numbers,
+            [
+                (100, true, &[0][..]),
+                (200, true, &[0, 1][..]),
+                (300, true, &[1][..]),
+                (7, false, &[][..])
+            ]
+        );
+        assert_eq!(
+            report.numbers[1].span,
+            NumberCoordinate { x1: 0, x2: 2, y: 1 }
+        );
+
+        let gear = &report.symbols[0];
+        assert_eq!((gear.symbol, gear.x, gear.y), ('*', 3, 1));
+        assert_eq!(
+            (gear.numbers.as_slice(), gear.is_gear, gear.ratio),
+            (&[0, 1][..], true, Some(20000))
+        );
+        let symbol = &report.symbols[1];
+        assert_eq!(
+            (symbol.symbol, symbol.numbers.as_slice()),
+            ('-', &[1, 2][..])
+        );
+        assert_eq!((symbol.is_gear, symbol.ratio), (false, None));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["numbers"][3]["span"]["y"], 4);
+        assert_eq!(json["symbols"][0]["symbol"], "*");
+        assert_eq!(json["symbols"][0]["ratio"], 20000);
+        assert_eq!(json["gear_ratio_sum"], 20000);
+
+        // Ratios which don't fit into 32 bits
+        let report = Solver::parse_from_input("999999*999999\n")
+            .unwrap()
+            .report();
+        assert_eq!(report.gear_ratio_sum, Some(999_998_000_001));
+    }
+
+    #[test]
+    fn test_large_non_gears() {
+        // Symbols which are not gears touch more part numbers than a u64 product can hold
+        let input = indoc! {"
+            999999999.999999999
+            .........-.........
+            999999999.999999999
+        "};
+        let solver = Solver::parse_from_input(input).unwrap();
+        assert_eq!(solver.solve(), (3_999_999_996, Some(0)));
+
+        let input = indoc! {"
+            999999999.999999999
+            .........*.........
+            .........999999999.
+        "};
+        let solver = Solver::parse_from_input(input).unwrap();
+        assert_eq!(solver.solve(), (2_999_999_997, Some(0)));
+        assert_eq!(solver.report().symbols[0].ratio, None);
+    }
+
+    #[test]
+    fn test_large_gear_ratios() {
+        // The sum of the ratios of 20 such gears doesn't fit in a u64
+        let input = "999999999*999999999\n...................\n".repeat(20);
+        let solver = Day03::parse(&input).unwrap();
+        assert_eq!(Day03::part1(&solver), 39_999_999_960);
+        assert_eq!(Day03::part2(&solver), Some(19_999_999_960_000_000_020));
+
+        // Ratios of more numbers than fit in a u128
+        let input = indoc! {"
+            999999999.999999999
+            999999999*999999999
+            999999999.999999999
+        "};
+        let rules = Rules {
+            gear_part_numbers: 6,
+            ..Rules::default()
+        };
+        let solver = Solver::parse_with_rules(input, rules).unwrap();
+        assert_eq!(solver.solve(), (5_999_999_994, None));
+        let report = solver.report();
+        assert_eq!(
+            (report.symbols[0].is_gear, report.symbols[0].ratio),
+            (true, None)
+        );
+
+        let input = "999999999*999999999\n..........999999999\n";
+        let rules = Rules {
+            gear_part_numbers: 3,
+            ..Rules::default()
+        };
+        let solver = Solver::parse_with_rules(input, rules).unwrap();
+        assert_eq!(solver.solve().1, Some(999_999_997_000_000_002_999_999_999));
+    }
+
+    #[test]
+    fn test_long_number() {
+        let error = Solver::parse_from_input("123456789*\n1234567890\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+        assert_eq!(error.expected, "a number of at most 9 digits");
+
+        // Found by fuzzing, used to overflow the number
+        let error = Solver::parse_from_input("L666666666666666").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
     }
 }
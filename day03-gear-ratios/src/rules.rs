@@ -0,0 +1,105 @@
+//! Rules of the schematic: which tiles touch a number, which characters are symbols and what
+//! makes a gear. The defaults are the rules of the puzzle.
+
+use grid::Coord;
+
+/// The tiles around each digit of a number which the number touches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Tiles at most this many rows and columns away, so 1 is the 8 surrounding tiles
+    Square(u32),
+    /// Tiles at most this many steps away, not moving diagonally, so 1 is the 4 tiles above,
+    /// below, left and right
+    Diamond(u32),
+    /// Tiles at these offsets from the digit
+    Offsets(Vec<Coord>),
+}
+
+impl Neighbourhood {
+    /// Offsets of the tiles from the digit, without the digit itself.
+    pub fn offsets(&self) -> Vec<Coord> {
+        let square = |radius: u32| {
+            let radius = radius as i64;
+            (-radius..=radius).flat_map(move |y| (-radius..=radius).map(move |x| Coord::new(x, y)))
+        };
+        let offsets: Vec<Coord> = match self {
+            Neighbourhood::Square(radius) => square(*radius).collect(),
+            Neighbourhood::Diamond(radius) => square(*radius)
+                .filter(|offset| offset.manhattan_distance(Coord::new(0, 0)) <= *radius as u64)
+                .collect(),
+            Neighbourhood::Offsets(offsets) => offsets.clone(),
+        };
+        offsets
+            .into_iter()
+            .filter(|&offset| offset != Coord::new(0, 0))
+            .collect()
+    }
+}
+
+/// Which characters other than digits are symbols. The others are blanks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Symbols {
+    /// All characters except digits and these blanks
+    AllExcept(String),
+    /// Only these characters
+    Only(String),
+}
+
+impl Symbols {
+    pub fn is_symbol(&self, ch: char) -> bool {
+        match self {
+            Symbols::AllExcept(blanks) => !ch.is_ascii_digit() && !blanks.contains(ch),
+            Symbols::Only(symbols) => !ch.is_ascii_digit() && symbols.contains(ch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Tiles which a number touches
+    pub neighbourhood: Neighbourhood,
+    pub symbols: Symbols,
+    /// Symbols which are gears if they touch the right number of part numbers
+    pub gear_symbols: String,
+    /// Number of part numbers a gear touches
+    pub gear_part_numbers: u32,
+}
+
+/// The rules of the puzzle: a number touches the 8 tiles around each digit, anything but a '.' is
+/// a symbol, and a '*' touching exactly 2 part numbers is a gear.
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            neighbourhood: Neighbourhood::Square(1),
+            symbols: Symbols::AllExcept(".".to_string()),
+            gear_symbols: "*".to_string(),
+            gear_part_numbers: 2,
+        }
+    }
+}
+
+impl Rules {
+    pub fn is_gear_symbol(&self, ch: char) -> bool {
+        self.symbols.is_symbol(ch) && self.gear_symbols.contains(ch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Neighbourhood::Square(1).offsets().len(), 8);
+        assert_eq!(Neighbourhood::Square(2).offsets().len(), 24);
+        assert_eq!(Neighbourhood::Diamond(1).offsets().len(), 4);
+        assert_eq!(Neighbourhood::Diamond(2).offsets().len(), 12);
+
+        let offsets = vec![Coord::new(0, 0), Coord::new(2, 0), Coord::new(0, -1)];
+        let neighbourhood = Neighbourhood::Offsets(offsets);
+        assert_eq!(
+            neighbourhood.offsets(),
+            [Coord::new(2, 0), Coord::new(0, -1)]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Engine for schematics too large to keep in memory, which reads them row by row.
+//!
+//! A number only touches the rows up to the reach of the neighbourhood above and below it, so
+//! each number is checked against a window of `2 * reach + 1` rows: the rows above within reach,
+//! its own row and the rows below within reach. With the default rules that is three rows. A
+//! symbol only collects the numbers of those rows too, so the ratio of a gear is known as soon as
+//! the rows below it have been checked. The memory therefore grows with the width of the
+//! schematic and the reach, but not with its height.
+
+use crate::rules::Rules;
+use common::parse::ReadError;
+use common::ParseError;
+use std::collections::{HashMap, VecDeque};
+use std::io::BufRead;
+
+/// Numbers longer than this are rejected, like in `Solver::parse_with_rules`.
+const MAX_DIGITS: usize = 9;
+
+/// A number of a row, from column `x1` to column `x2`.
+#[derive(Debug, Clone, Copy)]
+struct Number {
+    value: u32,
+    x1: usize,
+    x2: usize,
+}
+
+/// The part numbers touching a gear symbol so far.
+#[derive(Debug, Clone, Copy)]
+struct Gear {
+    part_numbers: u32,
+    /// Product of the part numbers, or `None` if it does not fit in a `u128`
+    product: Option<u128>,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    is_symbol: Vec<bool>,
+    numbers: Vec<Number>,
+    /// Gear symbols by column
+    gears: HashMap<usize, Gear>,
+}
+
+impl Row {
+    /// Parse the line, whose number is `index + 1`, with `width` tiles, or any number of tiles if
+    /// `width` is `None`.
+    fn parse(
+        line: &str,
+        index: usize,
+        width: Option<usize>,
+        rules: &Rules,
+    ) -> Result<Row, ParseError> {
+        let at = |rest: &str, expected: String| {
+            let mut error = ParseError::at(line, rest, expected);
+            error.line = index + 1;
+            error
+        };
+
+        let mut row = Row {
+            is_symbol: Vec::with_capacity(width.unwrap_or(0)),
+            numbers: Vec::new(),
+            gears: HashMap::new(),
+        };
+        let mut current: Option<Number> = None;
+        let mut digits = 0;
+
+        for (x, (offset, ch)) in line.char_indices().enumerate() {
+            row.is_symbol.push(rules.symbols.is_symbol(ch));
+            if rules.is_gear_symbol(ch) {
+                let gear = Gear {
+                    part_numbers: 0,
+                    product: Some(1),
+                };
+                row.gears.insert(x, gear);
+            }
+
+            match ch.to_digit(10) {
+                Some(digit) => {
+                    digits += 1;
+                    if digits > MAX_DIGITS {
+                        return Err(at(
+                            &line[offset..],
+                            "a number of at most 9 digits".to_string(),
+                        ));
+                    }
+                    let number = current.get_or_insert(Number {
+                        value: 0,
+                        x1: x,
+                        x2: x,
+                    });
+                    number.value = number.value * 10 + digit;
+                    number.x2 = x;
+                },
+                None => {
+                    digits = 0;
+                    row.numbers.extend(current.take());
+                },
+            }
+        }
+        row.numbers.extend(current);
+
+        match width {
+            Some(width) if row.is_symbol.len() != width => {
+                Err(at(line, format!("a row of {width} tiles")))
+            },
+            None if row.is_symbol.is_empty() => Err(at(line, "a row of tiles".to_string())),
+            _ => Ok(row),
+        }
+    }
+}
+
+/// The rows around the number row being checked, and the sums so far.
+struct Window<'a> {
+    rules: &'a Rules,
+    /// Offsets of the tiles which a digit touches, as columns and rows
+    offsets: Vec<(i64, i64)>,
+    /// Number of rows above and below a number which it can touch
+    reach: usize,
+    rows: VecDeque<Row>,
+    /// Index of the first row of the window in the whole schematic
+    first: usize,
+    /// Index of the next row whose numbers are checked
+    next: usize,
+    part_number_sum: u64,
+    /// `None` once a gear ratio or the sum does not fit in a `u128`
+    gear_ratio_sum: Option<u128>,
+}
+
+impl Window<'_> {
+    /// Index of the row after the last one read.
+    fn end(&self) -> usize {
+        self.first + self.rows.len()
+    }
+
+    /// Add the row, and check the numbers of the rows whose neighbourhood is now complete.
+    fn push(&mut self, row: Row) {
+        self.rows.push_back(row);
+        while self.next + self.reach < self.end() {
+            self.check_next_row();
+        }
+    }
+
+    /// Check the numbers of the remaining rows, and return the sums.
+    fn finish(mut self) -> (u64, Option<u128>) {
+        while self.next < self.end() {
+            self.check_next_row();
+        }
+        while !self.rows.is_empty() {
+            self.drop_first_row();
+        }
+        (self.part_number_sum, self.gear_ratio_sum)
+    }
+
+    /// Find the symbols each number of the next row touches, and drop the row which no number
+    /// can touch anymore.
+    fn check_next_row(&mut self) {
+        let y = self.next;
+        let width = self.rows[0].is_symbol.len() as i64;
+
+        for number in self.rows[y - self.first].numbers.clone() {
+            let mut symbols: Vec<(usize, usize)> = (number.x1..=number.x2)
+                .flat_map(|x| {
+                    self.offsets
+                        .iter()
+                        .map(move |&(dx, dy)| (x as i64 + dx, y as i64 + dy))
+                })
+                .filter(|&(x, row)| {
+                    (0..width).contains(&x) && (self.first as i64..self.end() as i64).contains(&row)
+                })
+                .map(|(x, row)| (x as usize, row as usize))
+                .filter(|&(x, row)| self.rows[row - self.first].is_symbol[x])
+                .collect();
+            if symbols.is_empty() {
+                continue;
+            }
+
+            self.part_number_sum += number.value as u64;
+            symbols.sort_unstable();
+            symbols.dedup();
+            for (x, row) in symbols {
+                if let Some(gear) = self.rows[row - self.first].gears.get_mut(&x) {
+                    gear.part_numbers += 1;
+                    if gear.part_numbers <= self.rules.gear_part_numbers {
+                        gear.product = gear
+                            .product
+                            .and_then(|product| product.checked_mul(number.value as u128));
+                    }
+                }
+            }
+        }
+
+        self.next += 1;
+        if self.first + self.reach < self.next {
+            self.drop_first_row();
+        }
+    }
+
+    /// Drop the first row, whose gears have seen all their part numbers.
+    fn drop_first_row(&mut self) {
+        let row = self.rows.pop_front().expect("Window is not empty");
+        self.first += 1;
+        for gear in row.gears.values() {
+            if gear.part_numbers == self.rules.gear_part_numbers {
+                self.gear_ratio_sum = self
+                    .gear_ratio_sum
+                    .zip(gear.product)
+                    .and_then(|(sum, ratio)| sum.checked_add(ratio));
+            }
+        }
+    }
+}
+
+/// Same as `Solver::solve` with the rules, but reads the schematic one row at a time, keeping only
+/// the rows around the current one in memory. The sum of the gear ratios is `None` if it does not
+/// fit in a `u128`.
+pub fn solve_reader(reader: impl BufRead, rules: &Rules) -> Result<(u64, Option<u128>), ReadError> {
+    let offsets: Vec<(i64, i64)> = rules
+        .neighbourhood
+        .offsets()
+        .into_iter()
+        .map(|offset| (offset.x, offset.y))
+        .collect();
+    let reach = offsets
+        .iter()
+        .map(|(_, dy)| dy.unsigned_abs() as usize)
+        .max()
+        .unwrap_or(0);
+    let mut window = Window {
+        rules,
+        offsets,
+        reach,
+        rows: VecDeque::new(),
+        first: 0,
+        next: 0,
+        part_number_sum: 0,
+        gear_ratio_sum: Some(0),
+    };
+
+    let mut width = None;
+    for (index, line) in reader.lines().enumerate() {
+        let row = Row::parse(&line?, index, width, rules)?;
+        width = Some(row.is_symbol.len());
+        window.push(row);
+    }
+    if width.is_none() {
+        return Err(ParseError::at_offset("", 0, "a row of tiles").into());
+    }
+
+    Ok(window.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Neighbourhood;
+    use crate::Solver;
+    use indoc::indoc;
+
+    #[test]
+    fn test_solve_reader() {
+        let input = indoc! {"
+            467..114..
+            ...*......
+            ..35..633.
+            ......#...
+            617*......
+            .....+.58.
+            ..592.....
+            ......755.
+            ...$.*....
+            .664.598..
+        "};
+        let rules = Rules::default();
+        assert_eq!(
+            solve_reader(input.as_bytes(), &rules).unwrap(),
+            (4361, Some(467835))
+        );
+
+        let input = "1.2\n.*.\n3..\n";
+        let rules = Rules {
+            gear_part_numbers: 3,
+            ..Rules::default()
+        };
+        assert_eq!(
+            solve_reader(input.as_bytes(), &rules).unwrap(),
+            (6, Some(6))
+        );
+    }
+
+    #[test]
+    fn test_same_as_solver() {
+        let neighbourhoods = [
+            Neighbourhood::Square(1),
+            Neighbourhood::Square(2),
+            Neighbourhood::Diamond(1),
+            Neighbourhood::Diamond(3),
+            Neighbourhood::Offsets(vec![grid::Coord::new(2, -1), grid::Coord::new(-1, 3)]),
+        ];
+        let large_numbers = indoc! {"
+            999999999.999999999
+            .........-.........
+            999999999.999999999
+            .........*.........
+            .........999999999.
+        "};
+        // The sum of the ratios of these gears doesn't fit in a u64
+        let large_gears = "999999999*999999999\n...................\n".repeat(20);
+        let inputs = (0..3)
+            .map(|seed| generator::generate(3, seed, 20).unwrap())
+            .chain([large_numbers.to_string(), large_gears]);
+        for (index, input) in inputs.enumerate() {
+            for neighbourhood in &neighbourhoods {
+                let rules = Rules {
+                    neighbourhood: neighbourhood.clone(),
+                    ..Rules::default()
+                };
+                let expected = Solver::parse_with_rules(&input, rules.clone())
+                    .unwrap()
+                    .solve();
+                let result = solve_reader(input.as_bytes(), &rules).unwrap();
+                assert_eq!(result, expected, "Input {index}, {neighbourhood:?}");
+            }
+        }
+
+        // Gear ratios which don't fit in a u128
+        let input = indoc! {"
+            999999999.999999999
+            999999999*999999999
+            999999999.999999999
+        "};
+        let rules = Rules {
+            gear_part_numbers: 6,
+            ..Rules::default()
+        };
+        let expected = Solver::parse_with_rules(input, rules.clone())
+            .unwrap()
+            .solve();
+        assert_eq!(expected.1, None);
+        assert_eq!(solve_reader(input.as_bytes(), &rules).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_errors() {
+        let rules = Rules::default();
+        let error = |input: &str| match solve_reader(input.as_bytes(), &rules).unwrap_err() {
+            ReadError::Parse(error) => error,
+            ReadError::Io(error) => panic!("Unexpected error {error}"),
+        };
+
+        let expected = Solver::parse_with_rules("123456789*\n1234567890\n", rules.clone());
+        assert_eq!(Some(error("123456789*\n1234567890\n")), expected.err());
+
+        let error = error("..1\n..*.\n");
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a row of 3 tiles");
+
+        assert!(solve_reader("".as_bytes(), &rules).is_err());
+    }
+}
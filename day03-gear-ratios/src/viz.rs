@@ -0,0 +1,31 @@
+//! Colouring of the engine schematic by the kind of each number and symbol.
+
+use crate::Solver;
+use render::{Canvas, Cell, Color, Render};
+
+/// Draws the input with part numbers marked in green, other numbers marked in red, and gear symbols
+/// marked in blue.
+impl Render for Solver {
+    fn render(&self) -> Canvas {
+        let mut canvas = self.schematic.map(|&ch| Cell::new(ch));
+
+        for number in &self.numbers {
+            let color = if number.is_part_number {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            for coord in number.coord.digit_coords() {
+                canvas[coord].fg = Some(color);
+            }
+        }
+
+        for (coord, symbol) in &self.symbols_by_coord {
+            if symbol.is_gear(&self.rules) {
+                canvas[*coord].fg = Some(Color::Blue);
+            }
+        }
+
+        canvas
+    }
+}
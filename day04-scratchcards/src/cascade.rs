@@ -0,0 +1,164 @@
+//! How the copies of the cards cascade in part 2: how many copies of each card are won and from
+//! which cards, and how the total changes if a card had a different number of matches.
+
+use crate::{cards_by_id, wins, Card};
+use std::collections::HashMap;
+
+/// The copies of a single card.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCopies {
+    pub id: u32,
+    /// Number of matches of the card, which is how many of the following cards it wins copies of
+    pub matches: usize,
+    /// Number of copies of the card, including the original
+    pub copies: u64,
+    /// Ids of the earlier cards which won copies of this card, in order, with how many copies
+    /// each of them won. These add up to all the copies except the original.
+    pub won_from: Vec<(u32, u64)>,
+}
+
+/// The copies of every card, in the order of their ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cascade {
+    pub cards: Vec<CardCopies>,
+}
+
+impl Cascade {
+    /// Total number of cards, the answer to part 2.
+    pub fn total(&self) -> u64 {
+        self.cards.iter().map(|card| card.copies).sum()
+    }
+
+    /// The copies of the card with the id.
+    pub fn card(&self, id: u32) -> Option<&CardCopies> {
+        let index = self.cards.binary_search_by_key(&id, |card| card.id).ok()?;
+        Some(&self.cards[index])
+    }
+
+    /// How much the total number of cards would change if the card with the id had the given
+    /// number of matches. `None` if there is no such card.
+    ///
+    /// The copies of the earlier cards, and the copies they win, stay the same, so only the
+    /// copies won by the card and the ones after it are counted again.
+    pub fn what_if(&self, id: u32, matches: usize) -> Option<i64> {
+        let index = self.cards.binary_search_by_key(&id, |card| card.id).ok()?;
+        let following = &self.cards[index..];
+
+        let mut copies: Vec<u64> = following
+            .iter()
+            .map(|card| {
+                let won_before = card.won_from.iter().filter(|(from, _)| *from < id);
+                1 + won_before.map(|(_, won)| won).sum::<u64>()
+            })
+            .collect();
+        for (index, card) in following.iter().enumerate() {
+            let won = copies[index];
+            let matches = if index == 0 { matches } else { card.matches };
+            let later = copies[index + 1..].iter_mut().zip(&following[index + 1..]);
+            for (count, _) in later.take_while(|(_, next)| wins(card.id, matches, next.id)) {
+                *count += won;
+            }
+        }
+
+        let before: u64 = following.iter().map(|card| card.copies).sum();
+        Some(copies.iter().sum::<u64>() as i64 - before as i64)
+    }
+}
+
+/// Follow the copies won by each card through the following cards.
+pub fn cascade(cards: &[Card]) -> Cascade {
+    cascade_with_matches(cards, &HashMap::new())
+}
+
+/// Same as `cascade`, but with the number of matches of the cards in `matches` replaced, by id.
+pub fn cascade_with_matches(cards: &[Card], matches: &HashMap<u32, usize>) -> Cascade {
+    let mut copies: Vec<CardCopies> = cards_by_id(cards)
+        .into_iter()
+        .map(|card| CardCopies {
+            id: card.id,
+            matches: matches
+                .get(&card.id)
+                .copied()
+                .unwrap_or_else(|| card.matches()),
+            copies: 1,
+            won_from: Vec::new(),
+        })
+        .collect();
+
+    // Copies are only won of later cards, so the copies of each card are known when it is reached
+    for index in 0..copies.len() {
+        let card = &mut copies[index];
+        card.copies += card.won_from.iter().map(|(_, won)| won).sum::<u64>();
+        let (id, won, matches) = (card.id, card.copies, card.matches);
+
+        let later = copies.iter_mut().skip(index + 1);
+        for next in later.take_while(|next| wins(id, matches, next.id)) {
+            next.won_from.push((id, won));
+        }
+    }
+
+    Cascade { cards: copies }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+    use crate::{parser, solve_part2};
+
+    #[test]
+    fn test_cascade() {
+        let cards = parser::parse_input(INPUT).unwrap();
+        let cascade = cascade(&cards);
+        assert_eq!(cascade.total(), 30);
+
+        let copies: Vec<u64> = cascade.cards.iter().map(|card| card.copies).collect();
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1]);
+
+        let card = cascade.card(4).unwrap();
+        assert_eq!(card.matches, 1);
+        assert_eq!(card.won_from, [(1, 1), (2, 2), (3, 4)]);
+        assert_eq!(cascade.card(5).unwrap().won_from, [(1, 1), (3, 4), (4, 8)]);
+        assert_eq!(cascade.card(1).unwrap().won_from, []);
+        assert_eq!(cascade.card(7), None);
+
+        for seed in 0..5 {
+            let input = generator::generate(4, seed, 10).unwrap();
+            let cards = parser::parse_input(&input).unwrap();
+            assert_eq!(crate::cascade::cascade(&cards).total(), solve_part2(&cards));
+        }
+    }
+
+    #[test]
+    fn test_what_if() {
+        let cards = parser::parse_input(INPUT).unwrap();
+        let cascade = cascade(&cards);
+        assert_eq!(cascade.what_if(4, 1), Some(0));
+        // Card 5 has 14 copies, so it would win 14 more copies of card 6
+        assert_eq!(cascade.what_if(5, 1), Some(14));
+        // Card 1 would win no copies, so cards 2 to 5 would have one copy fewer each, and fewer
+        // copies of the cards these win in turn
+        assert_eq!(cascade.what_if(1, 0), Some(-14));
+        // Copies are never won past the last card
+        assert_eq!(cascade.what_if(6, 3), Some(0));
+        assert_eq!(cascade.what_if(7, 1), None);
+        assert_eq!(cascade.what_if(0, 1), None);
+
+        let cascade = cascade_with_matches(&cards, &HashMap::from([(1, 0), (6, 3)]));
+        assert_eq!(cascade.card(1).unwrap().matches, 0);
+        assert_eq!(cascade.card(2).unwrap().copies, 1);
+
+        // Same as running the whole cascade again
+        let input = generator::generate(4, 0, 10).unwrap();
+        let cards = parser::parse_input(&input).unwrap();
+        let base = crate::cascade::cascade(&cards);
+        for card in &cards {
+            for matches in [0, 1, card.matches() + 2] {
+                let changed = HashMap::from([(card.id, matches)]);
+                let total = cascade_with_matches(&cards, &changed).total();
+                let expected = total as i64 - base.total() as i64;
+                assert_eq!(base.what_if(card.id, matches), Some(expected));
+            }
+        }
+    }
+}
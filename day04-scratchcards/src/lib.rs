@@ -1,76 +1,182 @@
 //! https://adventofcode.com/2023/day/4
 
-use std::collections::HashSet;
+use common::{ParseError, ReadError, Solution};
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
 
+pub mod cascade;
 pub mod parser;
 
 #[derive(Debug)]
 pub struct Card {
+    id: u32,
     winning_numbers: HashSet<u32>,
     scratched_numbers: HashSet<u32>,
 }
 
+impl Card {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn matches(&self) -> usize {
+        self.scratched_numbers
+            .intersection(&self.winning_numbers)
+            .count()
+    }
+
+    fn points(&self) -> u32 {
+        match self.matches() {
+            0 => 0,
+            n => 1 << (n - 1),
+        }
+    }
+}
+
 /// Return the sum of card points. Card has 1 point for the first match, and the points are doubled
 /// for each match after the first.
-pub fn solve_part1(cards: &Vec<Card>) -> u32 {
-    cards
-        .iter()
-        .map(|card| {
-            let matches = card
-                .scratched_numbers
-                .intersection(&card.winning_numbers)
-                .count();
-            let points = match matches {
-                0 => 0,
-                n => 1 << (n - 1),
-            };
-            points
-        })
-        .sum()
+pub fn solve_part1(cards: &[Card]) -> u32 {
+    cards.iter().map(Card::points).sum()
+}
+
+/// The cards in the order of their ids, keeping only the first card with each id.
+pub(crate) fn cards_by_id(cards: &[Card]) -> Vec<&Card> {
+    let mut by_id: Vec<&Card> = cards.iter().collect();
+    // The sort is stable, so the first card with an id stays in front of its duplicates
+    by_id.sort_by_key(|card| card.id);
+    by_id.dedup_by_key(|card| card.id);
+    by_id
+}
+
+/// Whether the card with id `later`, after the card with id `id`, is one of the cards won by the
+/// `matches` of that card.
+pub(crate) fn wins(id: u32, matches: usize, later: u32) -> bool {
+    u64::from(later) <= u64::from(id) + matches as u64
 }
 
-/// Return the total number of cards won. Having N matches on card i wins you cards i+1..i+N-1.
-pub fn solve_part2(cards: &Vec<Card>) -> u32 {
-    let mut cards_won: Vec<u32> = vec![1; cards.len()];
-
-    for (card_id, card) in cards.iter().enumerate() {
-        let copies = cards_won[card_id];
-        let matches = card
-            .scratched_numbers
-            .intersection(&card.winning_numbers)
-            .count();
-
-        for i in 1..=matches {
-            let next_id = card_id + i;
-            if let Some(count) = cards_won.get_mut(next_id) {
-                *count += copies;
-            }
+/// Return the total number of cards won. Having N matches on card i wins you cards i+1..i+N, as
+/// far as there are cards. The cards are followed by their ids, in whatever order they are given,
+/// and a card repeating the id of an earlier one is ignored.
+pub fn solve_part2(cards: &[Card]) -> u64 {
+    let cards = cards_by_id(cards);
+    let mut cards_won: Vec<u64> = vec![1; cards.len()];
+
+    for (index, card) in cards.iter().enumerate() {
+        let copies = cards_won[index];
+
+        let following = cards_won[index + 1..].iter_mut().zip(&cards[index + 1..]);
+        for (count, _) in following.take_while(|(_, next)| wins(card.id, card.matches(), next.id)) {
+            *count += copies;
         }
     }
 
     cards_won.iter().sum()
 }
 
+/// Solve both parts while reading the cards one at a time. For part 2, only the copies won of the
+/// upcoming cards are kept, which are never more than the number of matches on a card.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u32, u64), ReadError> {
+    let mut points = 0;
+    let mut cards_won = 0;
+    // Copies won of the cards following the current one, in order
+    let mut copies_won: VecDeque<u64> = VecDeque::new();
+
+    for card in parser::parse_reader(reader) {
+        let card = card?;
+        points += card.points();
+
+        let copies = 1 + copies_won.pop_front().unwrap_or(0);
+        cards_won += copies;
+
+        let matches = card.matches();
+        if copies_won.len() < matches {
+            copies_won.resize(matches, 0);
+        }
+        for count in copies_won.iter_mut().take(matches) {
+            *count += copies;
+        }
+    }
+
+    Ok((points, cards_won))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Card>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use indoc::indoc;
 
+    /// The example of the puzzle, also used by the tests of the other modules
+    pub(crate) const INPUT: &str = indoc! {"
+        Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+        Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+        Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+        Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+        Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+        Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+    "};
+
     #[test]
     fn test1() {
-        let input = indoc! {"
-            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-            Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-            Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-            Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-            Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
-        "};
-
-        let cards = parser::parse_input(&input).unwrap();
+        let cards = parser::parse_input(INPUT).unwrap();
         let result = solve_part1(&cards);
         assert_eq!(result, 13);
         let result = solve_part2(&cards);
         assert_eq!(result, 30);
+
+        let result = solve_reader(INPUT.as_bytes()).unwrap();
+        assert_eq!(result, (13, 30));
+    }
+
+    #[test]
+    fn test_part2_follows_ids() {
+        let mut cards = parser::parse_input(INPUT).unwrap();
+        // The cards with ids 4 to 6 on their own
+        assert_eq!(solve_part2(&cards[3..]), 1 + 2 + 1);
+
+        cards.reverse();
+        assert_eq!(solve_part2(&cards), 30);
+        assert_eq!(cascade::cascade(&cards).total(), 30);
+
+        // A card repeating the id of an earlier one is ignored
+        cards.extend(parser::parse_input("Card 1: 1 | 1").unwrap());
+        assert_eq!(solve_part2(&cards), 30);
+        assert_eq!(cascade::cascade(&cards).total(), 30);
+
+        // Without card 3, no copies are won of it or by it
+        cards.retain(|card| card.id != 3);
+        assert_eq!(solve_part2(&cards), 1 + 2 + 4 + 6 + 1);
+        assert_eq!(cascade::cascade(&cards).total(), 1 + 2 + 4 + 6 + 1);
+    }
+
+    #[test]
+    fn test_part2_large() {
+        let input: String = (1..=40)
+            .map(|id| format!("Card {id}: 1 2 3 4 5 | 1 2 3 4 5\n"))
+            .collect();
+        let cards = parser::parse_input(&input).unwrap();
+        let result = solve_part2(&cards);
+        assert!(result > u32::MAX as u64);
+        assert_eq!(cascade::cascade(&cards).total(), result);
+        assert_eq!(solve_reader(input.as_bytes()).unwrap(), (40 * 16, result));
     }
 }
@@ -1,15 +1,10 @@
-use day04_scratchcards::{parser::parse_input, solve_part1, solve_part2};
-use std::{fs, path::Path};
+use common::InputSource;
+use day04_scratchcards::solve_reader;
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args().open().expect("Input missing");
 
-    let cards = parse_input(&input).expect("Invalid input");
-
-    let result = solve_part1(&cards);
-    println!("Part one result: {result}");
-
-    let result = solve_part2(&cards);
-    println!("Part two result: {result}");
+    let (result_part1, result_part2) = solve_reader(input).expect("Invalid input");
+    println!("Part one result: {result_part1}");
+    println!("Part two result: {result_part2}");
 }
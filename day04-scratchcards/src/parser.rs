@@ -1,54 +1,86 @@
 use crate::Card;
-use itertools::Itertools;
+use common::parse::{self, fail, parse_lines, tag, IResult};
+use common::ReadError;
 use nom::{
-    bytes::complete::tag,
     character::complete::{space0, space1, u32},
+    combinator::consumed,
     error::ParseError,
     multi::separated_list1,
-    sequence::{delimited, tuple},
-    IResult,
+    sequence::delimited,
 };
 use std::collections::HashSet;
+use std::io::BufRead;
 
 /// A combinator that consumes both leading and trailing whitespace.
-fn trim<'a, F: 'a, O, E: ParseError<&'a str>>(
-    inner: F,
-) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+fn trim<'a, F, O, E: ParseError<&'a str>>(inner: F) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
 where
-    F: Fn(&'a str) -> IResult<&'a str, O, E>,
+    F: 'a + Fn(&'a str) -> IResult<&'a str, O, E>,
 {
     delimited(space0, inner, space0)
 }
 
-fn parse_number_list(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, u32)(input)
+/// Parse a list of numbers, failing at the first number which is repeated.
+fn parse_distinct_numbers<'a>(input: &'a str, name: &str) -> IResult<&'a str, HashSet<u32>> {
+    let (remainder, numbers) = separated_list1(space1, consumed(u32))(input)?;
+
+    let mut distinct = HashSet::new();
+    for (text, number) in numbers {
+        if !distinct.insert(number) {
+            let expected = format!("{name} other than {number}, which is repeated");
+            return fail(text, expected);
+        }
+    }
+    Ok((remainder, distinct))
 }
 
 fn parse_card(input: &str) -> IResult<&str, Card> {
-    let (remainder, (_, _card_id, _, winning_numbers, _, scratched_numbers)) = tuple((
-        tag("Card"),
-        trim(u32),
-        tag(":"),
-        trim(parse_number_list),
-        tag("|"),
-        trim(parse_number_list),
-    ))(input)?;
+    let (remainder, _) = tag("Card")(input)?;
+    let (remainder, id) = trim(u32)(remainder)?;
+    let (remainder, _) = tag(":")(remainder)?;
+    let (remainder, winning_numbers) =
+        trim(|input| parse_distinct_numbers(input, "a winning number"))(remainder)?;
+    let (remainder, _) = tag("|")(remainder)?;
+    let (remainder, scratched_numbers) =
+        trim(|input| parse_distinct_numbers(input, "a number"))(remainder)?;
 
     let card = Card {
-        winning_numbers: HashSet::from_iter(winning_numbers),
-        scratched_numbers: HashSet::from_iter(scratched_numbers),
+        id,
+        winning_numbers,
+        scratched_numbers,
     };
 
     Ok((remainder, card))
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Card>, Box<dyn std::error::Error + '_>> {
-    let cards = input
-        .lines()
-        .map(|line| parse_card(line).map(|(_, card)| card))
-        .try_collect()?;
+/// Parser of the cards of an input, which must be numbered from 1 in order. Fails at the id of
+/// the first card which is not the next one, e.g. because a card is missing or repeated.
+fn parse_cards_in_order() -> impl FnMut(&str) -> IResult<&str, Card> {
+    let mut next_id = 1;
+    move |input| {
+        let (remainder, card) = parse_card(input)?;
+
+        if card.id != next_id {
+            // The card was parsed, so the id follows the tag and the spaces
+            let id = input["Card".len()..].trim_start();
+            let expected = match card.id < next_id {
+                true => format!("card {next_id}, not a duplicate of card {}", card.id),
+                false => format!("card {next_id}, which is missing or out of order"),
+            };
+            return fail(id, expected);
+        }
+        next_id += 1;
+
+        Ok((remainder, card))
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>, common::ParseError> {
+    parse_lines(input, parse_cards_in_order())
+}
 
-    Ok(cards)
+/// Parse the cards one at a time, while reading them.
+pub fn parse_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Card, ReadError>> {
+    parse::parse_reader(reader, parse_cards_in_order())
 }
 
 #[cfg(test)]
@@ -57,14 +89,54 @@ mod tests {
 
     #[test]
     fn number_list() {
-        let (_, numbers) = parse_number_list("1 10  100").unwrap();
-        assert!(matches!(numbers[..], [1, 10, 100]));
+        let (_, numbers) = parse_distinct_numbers("1 10  100", "a number").unwrap();
+        assert_eq!(numbers, HashSet::from([1, 10, 100]));
     }
 
     #[test]
     fn card() {
         let input = "Card  10:  1 10 100 |  2 20 200";
         let (_, card) = parse_card(input).unwrap();
-        assert!(matches!(card, Card { .. }));
+        assert!(matches!(card, Card { id: 10, .. }));
+        assert_eq!(card.winning_numbers, HashSet::from([1, 10, 100]));
+    }
+
+    #[test]
+    fn duplicate_numbers() {
+        let input = "Card 1: 1 2 3 | 4 5 6\nCard 2: 1 2  1 | 3\n";
+        let error = parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 14));
+        assert_eq!(
+            error.expected,
+            "a winning number other than 1, which is repeated"
+        );
+
+        let error = parse_input("Card 1: 1 2 3 | 3 4 3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 21));
+        assert_eq!(error.expected, "a number other than 3, which is repeated");
+
+        // The same number can be both a winning number and a number we have
+        assert!(parse_input("Card 1: 1 2 3 | 3 2 1\n").is_ok());
+    }
+
+    #[test]
+    fn card_ids() {
+        let error = parse_input("Card 1: 1 | 2\nCard  3: 3 | 4\nCard 2: 5 | 6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 7));
+        assert_eq!(error.expected, "card 2, which is missing or out of order");
+
+        let error = parse_input("Card 1: 1 | 2\nCard 1: 3 | 4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.expected, "card 2, not a duplicate of card 1");
+
+        let error = parse_input("Card 2: 1 | 2\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+
+        let cards: Result<Vec<Card>, ReadError> =
+            parse_reader("Card 1: 1 | 2\nCard 3: 3 | 4\n".as_bytes()).collect();
+        match cards {
+            Err(ReadError::Parse(error)) => assert_eq!((error.line, error.column), (2, 6)),
+            _ => panic!("Expected a parse error"),
+        }
     }
 }
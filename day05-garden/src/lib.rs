@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/5
 
+use common::{ParseError, Solution};
 use rangemap::map::RangeMap;
 use std::ops::Range;
 
@@ -45,14 +46,14 @@ fn shift_range(range: Range<i64>, offset: i64) -> Range<i64> {
     }
 }
 
+/// The parts of a range before, inside and after another range.
+type RangeSplit = (Option<Range<i64>>, Option<Range<i64>>, Option<Range<i64>>);
+
 /// Compute the overlap of `range` with `overlap_range`. Return a tuple of three elements, where
 /// the first element is the part of the `range` before the `overlap_range` starts, the second
 /// element is the overlap of the two ranges, and the third element is the part of the `range`
 /// after the `overlap_range` ends.
-fn overlap_range(
-    range: &Range<i64>,
-    overlap_range: &Range<i64>,
-) -> (Option<Range<i64>>, Option<Range<i64>>, Option<Range<i64>>) {
+fn overlap_range(range: &Range<i64>, overlap_range: &Range<i64>) -> RangeSplit {
     use itertools::Itertools;
 
     let before = Range {
@@ -89,7 +90,7 @@ fn map_range(range: Range<i64>, map: &RangeMap<i64, i64>) -> Vec<Range<i64>> {
                 break;
             },
             Some(current_range_inner) => {
-                let (before, overlap, after) = overlap_range(&current_range_inner, &key_range);
+                let (before, overlap, after) = overlap_range(&current_range_inner, key_range);
                 if let Some(before_range) = before {
                     ranges.push(before_range);
                 }
@@ -124,8 +125,7 @@ fn map_seed_ranges_to_location_ranges(
     ] {
         ranges = ranges
             .into_iter()
-            .map(|range| map_range(range, map))
-            .flatten()
+            .flat_map(|range| map_range(range, map))
             .collect();
     }
     ranges
@@ -158,13 +158,33 @@ pub fn solve_part2(cfg: &Configuration) -> i64 {
         })
         .collect();
 
-    map_seed_ranges_to_location_ranges(seed_ranges, &cfg)
+    map_seed_ranges_to_location_ranges(seed_ranges, cfg)
         .into_iter()
         .map(|range| range.start)
         .min()
         .expect("Error: No locations")
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Configuration;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,7 +273,7 @@ mod tests {
             56 93 4
         "};
 
-        let cfg = parser::parse_input(&input).unwrap();
+        let cfg = parser::parse_input(input).unwrap();
         let result = solve_part1(&cfg);
         assert_eq!(result, 35);
         let result = solve_part2(&cfg);
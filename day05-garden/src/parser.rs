@@ -1,10 +1,10 @@
 use crate::Configuration;
+use common::parse::{fail, parse_all, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
     character::complete::{char, i64, line_ending, multispace1, not_line_ending, space1},
     multi::{count, separated_list1},
     sequence::{preceded, tuple},
-    IResult,
 };
 use rangemap::map::RangeMap;
 
@@ -31,6 +31,14 @@ fn parse_range_mapping(input: &str) -> IResult<&str, RangeMapping> {
     let (remainder, (dst_range_start, _, src_range_start, _, range_len)) =
         tuple((i64, char(' '), i64, char(' '), i64))(input)?;
 
+    if range_len <= 0 {
+        return fail(input, "a mapping with a positive range length");
+    }
+    let ends = [src_range_start, dst_range_start].map(|start| start.checked_add(range_len));
+    if ends.contains(&None) || dst_range_start.checked_sub(src_range_start).is_none() {
+        return fail(input, "a mapping whose ranges fit in 64 bits");
+    }
+
     let mapping = RangeMapping {
         src_range_start,
         dst_range_start,
@@ -57,9 +65,9 @@ fn parse_map(input: &str) -> IResult<&str, RangeMap<i64, i64>> {
     Ok((remainder, range_map))
 }
 
-pub fn parse_input(input: &str) -> Result<Configuration, Box<dyn std::error::Error + '_>> {
+fn parse_configuration(input: &str) -> IResult<&str, Configuration> {
     let (remainder, seeds) = parse_seeds(input)?;
-    let (_, mut maps) = count(preceded(multispace1, parse_map), 7)(remainder)?;
+    let (remainder, mut maps) = count(preceded(multispace1, parse_map), 7)(remainder)?;
 
     let cfg = Configuration {
         seeds,
@@ -72,7 +80,11 @@ pub fn parse_input(input: &str) -> Result<Configuration, Box<dyn std::error::Err
         seed_to_soil: maps.remove(0),
     };
 
-    Ok(cfg)
+    Ok((remainder, cfg))
+}
+
+pub fn parse_input(input: &str) -> Result<Configuration, ParseError> {
+    parse_all(input, parse_configuration)
 }
 
 #[cfg(test)]
@@ -99,4 +111,17 @@ mod tests {
         let as_vec = map.iter().collect::<Vec<_>>();
         assert!(matches!(as_vec[..], [(Range { start: 15, end: 54 }, -15)]));
     }
+
+    #[test]
+    fn invalid_mappings() {
+        let error = parse_map("soil-to-fertilizer map:\n0 15 37\n37 52 0\n").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("a mapping with a positive range length"));
+
+        let error = parse_map("soil-to-fertilizer map:\n0 9223372036854775807 2\n").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("a mapping whose ranges fit in 64 bits"));
+    }
 }
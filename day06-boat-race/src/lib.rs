@@ -1,5 +1,7 @@
 //! https://adventofcode.com/2023/day/6
 
+use common::{ParseError, Solution};
+
 pub mod parser;
 
 #[derive(Debug)]
@@ -43,11 +45,8 @@ fn ways_to_beat_race_record(race: &Race) -> u64 {
 
 /// For each race compute the number of ways that the record distance can be broken. Return the
 /// product of these values.
-pub fn solve_part1(races: &Vec<Race>) -> u64 {
-    races
-        .iter()
-        .map(|race| ways_to_beat_race_record(race))
-        .product()
+pub fn solve_part1(races: &[Race]) -> u64 {
+    races.iter().map(ways_to_beat_race_record).product()
 }
 
 /// Compute the number of ways that the record distance can be broken.
@@ -55,6 +54,30 @@ pub fn solve_part2(race: &Race) -> u64 {
     ways_to_beat_race_record(race)
 }
 
+pub struct Day06;
+
+/// The input is read as a list of races for part 1, and as a single race with the digits of all
+/// columns joined together for part 2.
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = (Vec<Race>, Race);
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let races = parser::parse_input_part1(input)?;
+        let race = parser::parse_input_part2(input)?;
+        Ok((races, race))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(&input.0)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(&input.1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,12 +90,24 @@ mod tests {
             Distance:  9  40  200
         "};
 
-        let races = parser::parse_input_part1(&input).unwrap();
+        let races = parser::parse_input_part1(input).unwrap();
         let result = solve_part1(&races);
         assert_eq!(result, 288);
 
-        let race = parser::parse_input_part2(&input).unwrap();
+        let race = parser::parse_input_part2(input).unwrap();
         let result = solve_part2(&race);
         assert_eq!(result, 71503);
     }
+
+    #[test]
+    fn test_solution() {
+        let input = indoc! {"
+            Time:      7  15   30
+            Distance:  9  40  200
+        "};
+
+        let races = Day06::parse(input).unwrap();
+        assert_eq!(Day06::part1(&races), 288);
+        assert_eq!(Day06::part2(&races), Some(71503));
+    }
 }
@@ -1,9 +1,10 @@
+use common::InputSource;
 use day06_boat_race::{parser::{parse_input_part1, parse_input_part2}, solve_part1, solve_part2};
-use std::{fs, path::Path};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
     let races = parse_input_part1(&input).expect("Invalid input");
     let result = solve_part1(&races);
@@ -1,25 +1,30 @@
 use crate::Race;
+use common::parse::{parse_all, IResult};
+use common::ParseError;
 use nom::{
     bytes::complete::take_till,
     character::complete::{digit1, space1, u64},
+    combinator::map_res,
     multi::separated_list1,
-    sequence::preceded,
-    IResult,
+    sequence::{pair, preceded},
 };
 
 fn parse_number_list(input: &str) -> IResult<&str, Vec<u64>> {
     separated_list1(space1, u64)(input)
 }
 
-pub fn parse_input_part1(input: &str) -> Result<Vec<Race>, Box<dyn std::error::Error + '_>> {
-    let (reminder, times) =
-        preceded(take_till(|ch: char| ch.is_digit(10)), parse_number_list)(input)?;
-    let (_, distances) =
-        preceded(take_till(|ch: char| ch.is_digit(10)), parse_number_list)(reminder)?;
+pub fn parse_input_part1(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (times, distances) = parse_all(
+        input,
+        pair(
+            preceded(take_till(|ch: char| ch.is_ascii_digit()), parse_number_list),
+            preceded(take_till(|ch: char| ch.is_ascii_digit()), parse_number_list),
+        ),
+    )?;
 
     let races = times
         .into_iter()
-        .zip(distances.into_iter())
+        .zip(distances)
         .map(|(time, distance)| Race { time, distance })
         .collect();
 
@@ -27,18 +32,28 @@ pub fn parse_input_part1(input: &str) -> Result<Vec<Race>, Box<dyn std::error::E
 }
 
 fn parse_separated_number(input: &str) -> IResult<&str, u64> {
-    let (remainder, parts) = separated_list1(space1, digit1)(input)?;
-    let number_str = parts.join("");
-    let number: u64 = number_str.parse().expect("Failed parsing an input u64 number");
-
-    Ok((remainder, number))
+    map_res(separated_list1(space1, digit1), |parts: Vec<&str>| {
+        parts
+            .join("")
+            .parse::<u64>()
+            .map_err(|_| "a number that fits in 64 bits")
+    })(input)
 }
 
-pub fn parse_input_part2(input: &str) -> Result<Race, Box<dyn std::error::Error + '_>> {
-    let (reminder, time) =
-        preceded(take_till(|ch: char| ch.is_digit(10)), parse_separated_number)(input)?;
-    let (_, distance) =
-        preceded(take_till(|ch: char| ch.is_digit(10)), parse_separated_number)(reminder)?;
+pub fn parse_input_part2(input: &str) -> Result<Race, ParseError> {
+    let (time, distance) = parse_all(
+        input,
+        pair(
+            preceded(
+                take_till(|ch: char| ch.is_ascii_digit()),
+                parse_separated_number,
+            ),
+            preceded(
+                take_till(|ch: char| ch.is_ascii_digit()),
+                parse_separated_number,
+            ),
+        ),
+    )?;
 
     let race = Race { time, distance };
 
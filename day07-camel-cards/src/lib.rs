@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/7
 
+use common::{ParseError, Solution};
 use std::cmp::{Ord, Ordering};
 use std::collections::HashMap;
 
@@ -130,7 +131,7 @@ impl PartialOrd for Hand {
 }
 
 /// Sort hands to obtain their rank, then multiply each hands bid by its rank and add it all up.
-fn sort_and_sum_hands(hands: &Vec<Hand>) -> u32 {
+fn sort_and_sum_hands(hands: &[Hand]) -> u32 {
     let mut sorted_hands: Vec<Hand> = hands.to_vec();
     sorted_hands.sort();
 
@@ -164,7 +165,7 @@ fn replace_jacks_with_jokers(hands: &Vec<Hand>) -> Vec<Hand> {
 }
 
 /// Sort hands to obtain their rank, then multiply each hands bid by its rank and add it all up.
-pub fn solve_part1(hands: &Vec<Hand>) -> u32 {
+pub fn solve_part1(hands: &[Hand]) -> u32 {
     sort_and_sum_hands(hands)
 }
 
@@ -175,6 +176,26 @@ pub fn solve_part2(hands: &Vec<Hand>) -> u32 {
     sort_and_sum_hands(&hands)
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<Hand>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,10 +211,23 @@ mod tests {
             QQQJA 483
         "};
 
-        let hands = parser::parse_input(&input).unwrap();
+        let hands = parser::parse_input(input).unwrap();
         let result = solve_part1(&hands);
         assert_eq!(result, 6440);
         let result = solve_part2(&hands);
         assert_eq!(result, 5905);
     }
+
+    #[test]
+    fn parse_error() {
+        let input = indoc! {"
+            32T3K 765
+            T55X5 684
+        "};
+
+        let error = parser::parse_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.text, "T55X5 684");
+        assert_eq!(error.expected, "one of \"23456789TJQKA\"");
+    }
 }
@@ -1,11 +1,12 @@
-use day07_camel_cards::{parser::parse_input, solve_part1, solve_part2};
-use std::{fs, path::Path};
+use common::InputSource;
+use day07_camel_cards::{parser::parse_reader, solve_part1, solve_part2};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args().open().expect("Input missing");
 
-    let hands = parse_input(&input).expect("Invalid input");
+    let hands = parse_reader(input)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Invalid input");
 
     let result = solve_part1(&hands);
     println!("Part one result: {result}");
@@ -1,20 +1,18 @@
 use crate::{CardType, Hand};
-use itertools::Itertools;
+use common::parse::{self, one_of, parse_lines, IResult};
+use common::{ParseError, ReadError};
 use nom::{
-    character::complete::{anychar, space1, u32},
+    character::complete::{space1, u32},
+    combinator::map_res,
     multi::count,
     sequence::separated_pair,
-    IResult,
 };
+use std::io::BufRead;
 
 fn parse_cards(input: &str) -> IResult<&str, [CardType; 5]> {
-    let (remainder, cards_vec) = count(anychar, 5)(input)?;
-    let cards: [CardType; 5] = cards_vec
-        .into_iter()
-        .map(|card_symbol| CardType::try_from(card_symbol).expect("Unrecognized card symbol"))
-        .collect::<Vec<CardType>>()
-        .try_into()
-        .expect("Cards length different from 5");
+    let (remainder, cards_vec) =
+        count(map_res(one_of("23456789TJQKA"), CardType::try_from), 5)(input)?;
+    let cards: [CardType; 5] = cards_vec.try_into().expect("Cards length different from 5");
 
     Ok((remainder, cards))
 }
@@ -27,13 +25,13 @@ fn parse_hand(input: &str) -> IResult<&str, Hand> {
     Ok((remainder, hand))
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Hand>, Box<dyn std::error::Error + '_>> {
-    let hands = input
-        .lines()
-        .map(|line| parse_hand(line).map(|(_, hand)| hand))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<Vec<Hand>, ParseError> {
+    parse_lines(input, parse_hand)
+}
 
-    Ok(hands)
+/// Parse the hands one at a time, while reading them.
+pub fn parse_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Hand, ReadError>> {
+    parse::parse_reader(reader, parse_hand)
 }
 
 #[cfg(test)]
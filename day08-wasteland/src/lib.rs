@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/8
 
+use common::{ParseError, Solution};
 use std::collections::HashMap;
 
 pub mod parser;
@@ -69,10 +70,30 @@ pub fn solve_part2(map: &Map) -> u64 {
     start_nodes
         .into_iter()
         .map(|node_id| count_steps(node_id, map))
-        .reduce(|left, right| num::integer::lcm(left, right))
+        .reduce(num::integer::lcm)
         .expect("Error: No start nodes")
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Map;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,7 +113,7 @@ mod tests {
             ZZZ = (ZZZ, ZZZ)
         "};
 
-        let items = parser::parse_input(&input).unwrap();
+        let items = parser::parse_input(input).unwrap();
         let result = solve_part1(&items);
         assert_eq!(result, 2);
     }
@@ -107,7 +128,7 @@ mod tests {
             ZZZ = (ZZZ, ZZZ)
         "};
 
-        let items = parser::parse_input(&input).unwrap();
+        let items = parser::parse_input(input).unwrap();
         let result = solve_part1(&items);
         assert_eq!(result, 6);
     }
@@ -127,7 +148,7 @@ mod tests {
             XXX = (XXX, XXX)
         "};
 
-        let items = parser::parse_input(&input).unwrap();
+        let items = parser::parse_input(input).unwrap();
         let result = solve_part2(&items);
         assert_eq!(result, 6);
     }
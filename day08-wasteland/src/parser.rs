@@ -1,10 +1,10 @@
 use crate::{Map, Node};
+use common::parse::{parse_all, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
     character::complete::{alpha1, alphanumeric1, line_ending, multispace1},
     multi::separated_list1,
     sequence::{separated_pair, tuple},
-    IResult,
 };
 use std::collections::HashMap;
 
@@ -32,8 +32,9 @@ fn parse_node_list(input: &str) -> IResult<&str, Vec<Node>> {
     separated_list1(line_ending, parse_node)(input)
 }
 
-pub fn parse_input(input: &str) -> Result<Map, Box<dyn std::error::Error + '_>> {
-    let (_, (instructions, nodes)) = separated_pair(alpha1, multispace1, parse_node_list)(input)?;
+pub fn parse_input(input: &str) -> Result<Map, ParseError> {
+    let (instructions, nodes) =
+        parse_all(input, separated_pair(alpha1, multispace1, parse_node_list))?;
 
     let nodes = HashMap::from_iter(nodes.into_iter().map(|node| (node.id.clone(), node)));
     let map = Map {
@@ -1,57 +1,101 @@
 //! https://adventofcode.com/2023/day/9
 
+use common::{ParseError, ReadError, Solution};
+use std::io::BufRead;
+
 pub mod parser;
 
-/// Extrapolate the next and previous values for each sequence by creating the delta sequences
-/// until you get an all-0 delta sequence.
+/// Extrapolate the next and previous values of the sequence by creating the delta sequences until
+/// you get an all-0 delta sequence.
+fn extrapolate(seq: &[i32]) -> (i32, i32) {
+    let first = *seq.first().expect("Sequence is empty");
+    let last = *seq.last().expect("Sequence is empty");
+
+    let mut seq_starts: Vec<i32> = vec![first];
+    let mut seq_ends: Vec<i32> = vec![last];
+    let mut current_seq: Vec<i32> = seq.to_vec();
+
+    loop {
+        let delta_seq: Vec<i32> = current_seq
+            .iter()
+            .zip(current_seq.iter().skip(1))
+            .map(|(x, y)| y - x)
+            .collect();
+
+        if delta_seq.iter().all(|x| *x == 0) {
+            break;
+        }
+
+        let first = *delta_seq.first().expect("Sequence is empty");
+        seq_starts.push(first);
+        let last = *delta_seq.last().expect("Sequence is empty");
+        seq_ends.push(last);
+
+        current_seq = delta_seq;
+    }
+
+    // Forward extrapolate is equal to the sum of ends of each sequence
+    let forward_extrapolate = seq_ends.into_iter().sum::<i32>();
+
+    // Backwards extrapolate is equal to the sum of starts of each sequence, except that the
+    // sign is changing for every other element.
+    let backwards_extrapolate = seq_starts
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| if i % 2 == 0 { x } else { -x })
+        .sum::<i32>();
+
+    (forward_extrapolate, backwards_extrapolate)
+}
+
+/// Extrapolate the next and previous values for each sequence, and return their sums.
 pub fn solve(sequences: &Vec<Vec<i32>>) -> (i32, i32) {
     let mut sum_backwards_extrapolate: i32 = 0;
     let mut sum_forwards_extrapolate: i32 = 0;
 
     for seq in sequences {
-        let first = *seq.first().expect("Sequence is empty");
-        let last = *seq.last().expect("Sequence is empty");
-
-        let mut seq_starts: Vec<i32> = vec![first];
-        let mut seq_ends: Vec<i32> = vec![last];
-        let mut current_seq: Vec<i32> = seq.to_vec();
-
-        loop {
-            let delta_seq: Vec<i32> = current_seq
-                .iter()
-                .zip(current_seq.iter().skip(1))
-                .map(|(x, y)| y - x)
-                .collect();
-
-            if delta_seq.iter().all(|x| *x == 0) {
-                break;
-            }
-
-            let first = *delta_seq.first().expect("Sequence is empty");
-            seq_starts.push(first);
-            let last = *delta_seq.last().expect("Sequence is empty");
-            seq_ends.push(last);
-
-            current_seq = delta_seq;
-        }
-
-        // Forward extrapolate is equal to the sum of ends of each sequence
-        let forward_extrapolate = seq_ends.into_iter().sum::<i32>();
-        sum_forwards_extrapolate += forward_extrapolate;
-
-        // Backwards extrapolate is equal to the sum of starts of each sequence, except that the
-        // sign is changing for every other element.
-        let backwards_extrapolate = seq_starts
-            .into_iter()
-            .enumerate()
-            .map(|(i, x)| if i % 2 == 0 { x } else { -x })
-            .sum::<i32>();
-        sum_backwards_extrapolate += backwards_extrapolate;
+        let (forward, backwards) = extrapolate(seq);
+        sum_forwards_extrapolate += forward;
+        sum_backwards_extrapolate += backwards;
     }
 
     (sum_forwards_extrapolate, sum_backwards_extrapolate)
 }
 
+/// Same as `solve`, while reading the sequences one at a time.
+pub fn solve_reader(reader: impl BufRead) -> Result<(i32, i32), ReadError> {
+    let mut sum_backwards_extrapolate: i32 = 0;
+    let mut sum_forwards_extrapolate: i32 = 0;
+
+    for seq in parser::parse_reader(reader) {
+        let (forward, backwards) = extrapolate(&seq?);
+        sum_forwards_extrapolate += forward;
+        sum_backwards_extrapolate += backwards;
+    }
+
+    Ok((sum_forwards_extrapolate, sum_backwards_extrapolate))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<Vec<i32>>;
+    type Output = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve(input).0
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve(input).1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,9 +109,12 @@ mod tests {
             10 13 16 21 30 45
         "};
 
-        let sequences = parser::parse_input(&input).unwrap();
+        let sequences = parser::parse_input(input).unwrap();
         let (result_part1, result_part2) = solve(&sequences);
         assert_eq!(result_part1, 114);
         assert_eq!(result_part2, 2);
+
+        let result = solve_reader(input.as_bytes()).unwrap();
+        assert_eq!(result, (114, 2));
     }
 }
@@ -1,13 +1,10 @@
-use day09_mirage::{parser::parse_input, solve};
-use std::{fs, path::Path};
+use common::InputSource;
+use day09_mirage::solve_reader;
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args().open().expect("Input missing");
 
-    let sequences = parse_input(&input).expect("Invalid input");
-
-    let (result_part1, result_part2) = solve(&sequences);
+    let (result_part1, result_part2) = solve_reader(input).expect("Invalid input");
     println!("Part one result: {result_part1}");
     println!("Part two result: {result_part2}");
 }
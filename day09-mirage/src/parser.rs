@@ -1,19 +1,20 @@
-use itertools::Itertools;
+use common::parse::{self, parse_lines, IResult};
+use common::{ParseError, ReadError};
 use nom::{
     character::complete::{i32, space1},
     multi::separated_list1,
-    IResult,
 };
+use std::io::BufRead;
 
 fn parse_number_list(input: &str) -> IResult<&str, Vec<i32>> {
     separated_list1(space1, i32)(input)
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, Box<dyn std::error::Error + '_>> {
-    let sequences = input
-        .lines()
-        .map(|line| parse_number_list(line).map(|(_, seq)| seq))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    parse_lines(input, parse_number_list)
+}
 
-    Ok(sequences)
+/// Parse the sequences one at a time, while reading them.
+pub fn parse_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Vec<i32>, ReadError>> {
+    parse::parse_reader(reader, parse_number_list)
 }
@@ -1,14 +1,11 @@
 //! https://adventofcode.com/2023/day/10
 
+use common::{ParseError, Solution};
+use grid::{Coord, Direction, Grid};
 use std::collections::VecDeque;
 
-#[derive(Debug)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
+#[cfg(feature = "viz")]
+mod viz;
 
 #[derive(Debug, Clone, PartialEq)]
 enum TileType {
@@ -30,83 +27,55 @@ enum MiniTileType {
     Outside,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
-impl From<(usize, usize)> for Coord {
-    fn from((x, y): (usize, usize)) -> Coord {
-        Coord { x, y }
-    }
-}
-
+#[derive(Clone)]
 pub struct Maze {
     start_coord: Coord,
-    tiles: Vec<Vec<TileType>>,
-    mini_tiles: Vec<Vec<MiniTileType>>,
+    tiles: Grid<TileType>,
+    mini_tiles: Grid<MiniTileType>,
 }
 
 impl Maze {
-    pub fn load_from_input(input: &str) -> Maze {
-        let mut start_coord: Coord = Coord::from((0, 0));
-        let mut maze: Vec<Vec<TileType>> = Vec::new();
-
-        for (j, line) in input.lines().enumerate() {
-            let mut maze_line: Vec<TileType> = Vec::new();
-            for (i, ch) in line.chars().enumerate() {
-                let tile = match ch {
-                    '|' => TileType::NS,
-                    'L' => TileType::NE,
-                    'J' => TileType::NW,
-                    'F' => TileType::SE,
-                    '7' => TileType::SW,
-                    '-' => TileType::EW,
-                    '.' => TileType::Ground,
-                    'S' => {
-                        start_coord = Coord::from((i, j));
-                        TileType::Start
-                    },
-                    _ => panic!("Unknown tile type"),
-                };
-                maze_line.push(tile);
-            }
-            maze.push(maze_line);
-        }
+    pub fn load_from_input(input: &str) -> Result<Maze, ParseError> {
+        let mut start_coord: Option<Coord> = None;
+
+        let tiles = Grid::parse(input, |coord, ch| match ch {
+            '|' => Ok(TileType::NS),
+            'L' => Ok(TileType::NE),
+            'J' => Ok(TileType::NW),
+            'F' => Ok(TileType::SE),
+            '7' => Ok(TileType::SW),
+            '-' => Ok(TileType::EW),
+            '.' => Ok(TileType::Ground),
+            'S' if start_coord.is_none() => {
+                start_coord = Some(coord);
+                Ok(TileType::Start)
+            },
+            'S' => Err("a single start tile"),
+            _ => Err("one of \"|-LJ7F.S\""),
+        })?;
+
+        let Some(start_coord) = start_coord else {
+            return Err(ParseError::at_offset(input, input.len(), "a start tile"));
+        };
 
         // For each normal tile we make a grid of 9 mini-tiles
-        let (m, n) = (maze.len(), maze[0].len());
-        let mini_tiles: Vec<Vec<MiniTileType>> = vec![vec![MiniTileType::Unvisited; 3 * n]; 3 * m];
+        let (m, n) = (tiles.height(), tiles.width());
+        let mini_tiles = Grid::filled(3 * n, 3 * m, MiniTileType::Unvisited);
 
-        Maze {
+        Ok(Maze {
             start_coord,
-            tiles: maze,
+            tiles,
             mini_tiles,
-        }
-    }
-
-    pub fn print(&self) {
-        use colored::Colorize;
-
-        for line in self.mini_tiles.iter() {
-            for minitile in line.iter() {
-                match minitile {
-                    MiniTileType::Loop => print!("{}", ".".blue()),
-                    MiniTileType::Outside => print!("{}", ".".green()),
-                    // The remainder is Inside
-                    _ => print!("{}", ".".red()),
-                }
-            }
-            println!();
-        }
+        })
     }
 
     /// Discover the tile type of the start tile based on the neighbouring tiles.
     fn discover_start_tile_type(&self, start_coord: &Coord) -> TileType {
-        let n_tile = self.tiles[start_coord.y - 1][start_coord.x].clone();
-        let s_tile = self.tiles[start_coord.y + 1][start_coord.x].clone();
-        let e_tile = self.tiles[start_coord.y][start_coord.x + 1].clone();
+        // Tiles outside of the maze don't connect to anything, same as the ground
+        let tile = |coord: Coord| self.tiles.get(coord).cloned().unwrap_or(TileType::Ground);
+        let n_tile = tile(start_coord.north());
+        let s_tile = tile(start_coord.south());
+        let e_tile = tile(start_coord.east());
 
         match n_tile {
             // Start tile connects to N
@@ -169,41 +138,29 @@ impl Maze {
     }
 
     fn move_up(&self, prev_coord: &Coord) -> (Coord, TileType, Direction) {
-        let coord = Coord {
-            x: prev_coord.x,
-            y: prev_coord.y - 1,
-        };
-        let tile = self.tiles[coord.y][coord.x].clone();
+        let coord = prev_coord.north();
+        let tile = self.tiles[coord].clone();
         let direction = Direction::S;
         (coord, tile, direction)
     }
 
     fn move_down(&self, prev_coord: &Coord) -> (Coord, TileType, Direction) {
-        let coord = Coord {
-            x: prev_coord.x,
-            y: prev_coord.y + 1,
-        };
-        let tile = self.tiles[coord.y][coord.x].clone();
+        let coord = prev_coord.south();
+        let tile = self.tiles[coord].clone();
         let direction = Direction::N;
         (coord, tile, direction)
     }
 
     fn move_left(&self, prev_coord: &Coord) -> (Coord, TileType, Direction) {
-        let coord = Coord {
-            x: prev_coord.x - 1,
-            y: prev_coord.y,
-        };
-        let tile = self.tiles[coord.y][coord.x].clone();
+        let coord = prev_coord.west();
+        let tile = self.tiles[coord].clone();
         let direction = Direction::E;
         (coord, tile, direction)
     }
 
     fn move_right(&self, prev_coord: &Coord) -> (Coord, TileType, Direction) {
-        let coord = Coord {
-            x: prev_coord.x + 1,
-            y: prev_coord.y,
-        };
-        let tile = self.tiles[coord.y][coord.x].clone();
+        let coord = prev_coord.east();
+        let tile = self.tiles[coord].clone();
         let direction = Direction::W;
         (coord, tile, direction)
     }
@@ -211,7 +168,7 @@ impl Maze {
     /// Detect and mark the main loop using DFS. Returns the length of the loop.
     fn detect_loop(&mut self) -> u32 {
         let mut loop_len = 0;
-        let mut current_coord = self.start_coord.clone();
+        let mut current_coord = self.start_coord;
         let mut current_tile = self.discover_start_tile_type(&current_coord);
         let mut current_direction = pick_start_direction(&current_tile);
 
@@ -235,41 +192,41 @@ impl Maze {
 
     fn mark_loop_minitiles(&mut self, coord: &Coord, tile: &TileType) {
         // Coordinates of mini-tiles corresponding to the tile of `coord` (without the 4 corners)
-        let n = (coord.x * 3 + 1, coord.y * 3);
-        let w = (coord.x * 3, coord.y * 3 + 1);
-        let c = (coord.x * 3 + 1, coord.y * 3 + 1);
-        let e = (coord.x * 3 + 2, coord.y * 3 + 1);
-        let s = (coord.x * 3 + 1, coord.y * 3 + 2);
+        let n = Coord::new(coord.x * 3 + 1, coord.y * 3);
+        let w = Coord::new(coord.x * 3, coord.y * 3 + 1);
+        let c = Coord::new(coord.x * 3 + 1, coord.y * 3 + 1);
+        let e = Coord::new(coord.x * 3 + 2, coord.y * 3 + 1);
+        let s = Coord::new(coord.x * 3 + 1, coord.y * 3 + 2);
 
         match tile {
             TileType::NS => {
-                for (x, y) in [n, c, s] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [n, c, s] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             TileType::NE => {
-                for (x, y) in [n, c, e] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [n, c, e] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             TileType::NW => {
-                for (x, y) in [n, c, w] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [n, c, w] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             TileType::SE => {
-                for (x, y) in [s, c, e] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [s, c, e] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             TileType::SW => {
-                for (x, y) in [s, c, w] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [s, c, w] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             TileType::EW => {
-                for (x, y) in [e, c, w] {
-                    self.mini_tiles[y][x] = MiniTileType::Loop;
+                for mini_coord in [e, c, w] {
+                    self.mini_tiles[mini_coord] = MiniTileType::Loop;
                 }
             },
             _ => panic!("Unexpected tile in the loop"),
@@ -278,37 +235,17 @@ impl Maze {
 
     /// Return the unvisited neighbors of the mini-tile
     fn minitile_neighbors(&self, coord: Coord) -> Vec<Coord> {
-        let Coord { x, y } = coord;
-        let mut neighbors = Vec::new();
-
-        let (m, n) = (self.mini_tiles.len(), self.mini_tiles[0].len());
-
-        // N
-        if x != 0 {
-            neighbors.push(Coord::from((x - 1, y)));
-        }
-        // S
-        if x != n - 1 {
-            neighbors.push(Coord::from((x + 1, y)));
-        }
-        // W
-        if y != 0 {
-            neighbors.push(Coord::from((x, y - 1)));
-        }
-        // E
-        if y != m - 1 {
-            neighbors.push(Coord::from((x, y + 1)));
-        }
-
-        neighbors.retain(|Coord { x, y }| self.mini_tiles[*y][*x] == MiniTileType::Unvisited);
-        neighbors
+        self.mini_tiles
+            .neighbours4(coord)
+            .filter(|&coord| self.mini_tiles[coord] == MiniTileType::Unvisited)
+            .collect()
     }
 
     /// Use a flood fill algorithm to detect all the mini-tiles that are outside the loop. Starts
     /// from the top-left corner, since that mini-tile is always on the outside (the corner
     /// mini-tiles are not part of the loop).
     fn detect_outside(&mut self) {
-        let start = Coord::from((0, 0));
+        let start = Coord::new(0, 0);
         let mut queue: VecDeque<Coord> = VecDeque::new();
         queue.push_back(start);
 
@@ -318,11 +255,11 @@ impl Maze {
             match coord {
                 None => break,
                 Some(coord) => {
-                    self.mini_tiles[coord.y][coord.x] = MiniTileType::Outside;
+                    self.mini_tiles[coord] = MiniTileType::Outside;
 
                     let neighbors = self.minitile_neighbors(coord);
-                    for Coord { x, y } in &neighbors {
-                        self.mini_tiles[*y][*x] = MiniTileType::Visited;
+                    for &neighbor in &neighbors {
+                        self.mini_tiles[neighbor] = MiniTileType::Visited;
                     }
                     queue.extend(neighbors);
                 },
@@ -334,15 +271,12 @@ impl Maze {
     /// to the number of Inside tiles.
     fn count_inside_tiles(&self) -> u32 {
         let mut count: u32 = 0;
-        let (m, n) = (self.tiles.len(), self.tiles[0].len());
-
-        for i in 0..n {
-            for j in 0..m {
-                // Coordinate of the central minitile for the tile on (i, j)
-                let (x, y) = (i * 3 + 1, j * 3 + 1);
-                if self.mini_tiles[y][x] == MiniTileType::Unvisited {
-                    count += 1;
-                }
+
+        for coord in self.tiles.coords() {
+            // Coordinate of the central minitile for the tile on `coord`
+            let center = Coord::new(coord.x * 3 + 1, coord.y * 3 + 1);
+            if self.mini_tiles[center] == MiniTileType::Unvisited {
+                count += 1;
             }
         }
 
@@ -362,7 +296,7 @@ fn pick_start_direction(start_tile: &TileType) -> Direction {
 
 /// Return the pair:
 /// - Longest distance from the start tile in the main loop. This is equal to half of the
-/// length of the loop.
+///   length of the loop.
 /// - Number of tiles inside of the main loop.
 pub fn solve(maze: &mut Maze) -> (u32, u32) {
     let loop_length = maze.detect_loop();
@@ -374,6 +308,26 @@ pub fn solve(maze: &mut Maze) -> (u32, u32) {
     (solution_part1, solution_part2)
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Maze;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Maze::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve(&mut input.clone()).0
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve(&mut input.clone()).1)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,7 +343,7 @@ mod tests {
             L|-JF
         "};
 
-        let mut maze = Maze::load_from_input(&input);
+        let mut maze = Maze::load_from_input(input).unwrap();
         let (result_part1, _) = solve(&mut maze);
         assert_eq!(result_part1, 4);
     }
@@ -404,7 +358,7 @@ mod tests {
             LJ.LJ
         "};
 
-        let mut maze = Maze::load_from_input(&input);
+        let mut maze = Maze::load_from_input(input).unwrap();
         let (result_part1, _) = solve(&mut maze);
         assert_eq!(result_part1, 8);
     }
@@ -423,7 +377,7 @@ mod tests {
             ..........
         "};
 
-        let mut maze = Maze::load_from_input(&input);
+        let mut maze = Maze::load_from_input(input).unwrap();
         let (_, result_part2) = solve(&mut maze);
         assert_eq!(result_part2, 4);
     }
@@ -442,8 +396,24 @@ mod tests {
             ....L---J.LJ.LJLJ...
         "};
 
-        let mut maze = Maze::load_from_input(&input);
+        let mut maze = Maze::load_from_input(input).unwrap();
         let (_, result_part2) = solve(&mut maze);
         assert_eq!(result_part2, 8);
     }
+
+    #[test]
+    fn load_error() {
+        let input = indoc! {"
+            .....
+            .S-7.
+            .|.X.
+        "};
+
+        let error = Maze::load_from_input(input).err().unwrap();
+        assert_eq!((error.line, error.column), (3, 4));
+        assert_eq!(error.expected, "one of \"|-LJ7F.S\"");
+
+        let error = Maze::load_from_input("..\n.-\n").err().unwrap();
+        assert_eq!(error.expected, "a start tile");
+    }
 }
@@ -1,14 +1,20 @@
+use common::InputSource;
 use day10_pipe_maze::{solve, Maze};
-use std::{fs, path::Path};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
-    let mut maze = Maze::load_from_input(&input);
+    let mut maze = Maze::load_from_input(&input).expect("Invalid input");
 
     let (result_part1, result_part2) = solve(&mut maze);
-    maze.print();
+    // Save the rendering to the image or text file given as the second argument, or print it
+    #[cfg(feature = "viz")]
+    match std::env::args().nth(2) {
+        Some(output_path) => render::save(&maze, output_path).expect("Failed to save the output"),
+        None => render::print(&maze).expect("Failed to print the output"),
+    }
     println!("Part one result: {result_part1}");
     println!("Part two result: {result_part2}");
 }
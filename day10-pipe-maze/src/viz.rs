@@ -0,0 +1,19 @@
+//! Picture of the magnified maze, showing which tiles are inside the loop.
+
+use crate::{Maze, MiniTileType};
+use render::{Canvas, Cell, Color, Render};
+
+/// Draws the magnified maze, with the loop in blue, the outside in green and the inside in red.
+impl Render for Maze {
+    fn render(&self) -> Canvas {
+        self.mini_tiles.map(|minitile| {
+            let color = match minitile {
+                MiniTileType::Loop => Color::Blue,
+                MiniTileType::Outside => Color::Green,
+                // The remainder is Inside
+                _ => Color::Red,
+            };
+            Cell::new('.').fg(color)
+        })
+    }
+}
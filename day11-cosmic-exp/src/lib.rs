@@ -1,65 +1,53 @@
 //! https://adventofcode.com/2023/day/11
 
+use common::{ParseError, Solution};
+use grid::{Coord, Grid};
 use std::collections::HashSet;
 
-#[derive(Clone)]
-struct Coord {
-    x: u64,
-    y: u64,
-}
-
-impl From<(u64, u64)> for Coord {
-    fn from((x, y): (u64, u64)) -> Coord {
-        Coord { x, y }
-    }
-}
-
 #[derive(Clone)]
 pub struct CosmicImage {
     galaxies: Vec<Coord>,
-    rows: u64,
-    cols: u64,
+    rows: i64,
+    cols: i64,
 }
 
 impl CosmicImage {
-    pub fn load_from_input(input: &str) -> CosmicImage {
-        let mut galaxies: Vec<Coord> = Vec::new();
-        let mut rows: usize = 0;
-        let mut cols: usize = 0;
-
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                if ch == '#' {
-                    galaxies.push(Coord::from((i as u64, j as u64)));
-                }
-                rows = i;
-            }
-            cols = j;
-        }
-
-        CosmicImage {
+    pub fn load_from_input(input: &str) -> Result<CosmicImage, ParseError> {
+        let image = Grid::parse(input, |_, ch| match ch {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("one of \".#\""),
+        })?;
+
+        let galaxies = image
+            .iter()
+            .filter(|(_, &galaxy)| galaxy)
+            .map(|(coord, _)| coord)
+            .collect();
+
+        Ok(CosmicImage {
             galaxies,
-            rows: (rows + 1) as u64,
-            cols: (cols + 1) as u64,
-        }
+            rows: image.height() as i64,
+            cols: image.width() as i64,
+        })
     }
 
     /// Expand the space by moving the coordinates of the galaxies by the amount of empty rows and
     /// columns before them. Factor is a multiplicative factor of how many rows/columns each empty
     /// row/column is worth.
-    fn expand(&mut self, factor: u64) {
+    fn expand(&mut self, factor: i64) {
         use itertools::sorted;
 
-        let mut empty_rows: HashSet<u64> = HashSet::from_iter(0..self.rows);
-        let mut empty_cols: HashSet<u64> = HashSet::from_iter(0..self.cols);
+        let mut empty_rows: HashSet<i64> = HashSet::from_iter(0..self.rows);
+        let mut empty_cols: HashSet<i64> = HashSet::from_iter(0..self.cols);
 
         for Coord { x, y } in &self.galaxies {
             empty_rows.remove(y);
             empty_cols.remove(x);
         }
 
-        let empty_rows: Vec<u64> = sorted(empty_rows.into_iter()).collect();
-        let empty_cols: Vec<u64> = sorted(empty_cols.into_iter()).collect();
+        let empty_rows: Vec<i64> = sorted(empty_rows).collect();
+        let empty_cols: Vec<i64> = sorted(empty_cols).collect();
 
         // For a galaxy at (x, y), shift x by the number of empty cols that are less than x, and
         // shift y by the number of empty rows that are less than y.
@@ -68,11 +56,11 @@ impl CosmicImage {
         // array. This is equal to the number of empty columns before the galaxy. It should never
         // returns the Ok variant, since the column with a galaxy cannot be in the empty columns.
         for galaxy in &mut self.galaxies {
-            let Coord { x, y } = &galaxy;
-            let x_shift = factor * empty_cols.binary_search(x).unwrap_err() as u64;
-            let y_shift = factor * empty_rows.binary_search(y).unwrap_err() as u64;
+            let Coord { x, y } = *galaxy;
+            let x_shift = factor * empty_cols.binary_search(&x).unwrap_err() as i64;
+            let y_shift = factor * empty_rows.binary_search(&y).unwrap_err() as i64;
 
-            *galaxy = Coord::from((x + x_shift, y + y_shift));
+            *galaxy = Coord::new(x + x_shift, y + y_shift);
         }
     }
 }
@@ -86,8 +74,7 @@ pub fn solve_part1(space: &mut CosmicImage) -> u64 {
 
     let mut sum: u64 = 0;
     for pair in space.galaxies.iter().combinations(2) {
-        let (a, b) = (pair[0], pair[1]);
-        sum += b.x.abs_diff(a.x) + b.y.abs_diff(a.y);
+        sum += pair[0].manhattan_distance(*pair[1]);
     }
     sum
 }
@@ -102,12 +89,31 @@ pub fn solve_part2(space: &mut CosmicImage) -> u64 {
 
     let mut sum: u64 = 0;
     for pair in space.galaxies.iter().combinations(2) {
-        let (a, b) = (pair[0], pair[1]);
-        sum += b.x.abs_diff(a.x) + b.y.abs_diff(a.y);
+        sum += pair[0].manhattan_distance(*pair[1]);
     }
     sum
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = CosmicImage;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        CosmicImage::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(&mut input.clone())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(&mut input.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,7 +134,7 @@ mod tests {
             #...#.....
         "};
 
-        let mut space = CosmicImage::load_from_input(&input);
+        let mut space = CosmicImage::load_from_input(input).unwrap();
         let result = solve_part1(&mut space);
         assert_eq!(result, 374);
         let result = solve_part2(&mut space);
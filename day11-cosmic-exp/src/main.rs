@@ -1,11 +1,12 @@
+use common::InputSource;
 use day11_cosmic_exp::{solve_part1, solve_part2, CosmicImage};
-use std::{fs, path::Path};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
-    let space = CosmicImage::load_from_input(&input);
+    let space = CosmicImage::load_from_input(&input).expect("Invalid input");
 
     let result = solve_part1(&mut space.clone());
     println!("Part one result: {result}");
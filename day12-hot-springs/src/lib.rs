@@ -1,7 +1,9 @@
 //! https://adventofcode.com/2023/day/12
 
+use common::{ParseError, ReadError, Solution};
 use std::fmt;
 use std::collections::HashMap;
+use std::io::BufRead;
 
 pub mod parser;
 
@@ -12,13 +14,15 @@ pub enum Status {
     Unknown,
 }
 
-impl From<char> for Status {
-    fn from(ch: char) -> Status {
+impl TryFrom<char> for Status {
+    type Error = &'static str;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            '.' => Status::Operational,
-            '#' => Status::Damaged,
-            '?' => Status::Unknown,
-            _ => panic!("Unexpected Status character"),
+            '.' => Ok(Status::Operational),
+            '#' => Ok(Status::Damaged),
+            '?' => Ok(Status::Unknown),
+            _ => Err("Invalid status symbol"),
         }
     }
 }
@@ -49,6 +53,24 @@ pub struct Record {
     cluster_sizes: Vec<u32>,
 }
 
+impl Record {
+    /// Repeat the seq 5 times with '?' separators, and the cluster sizes 5 times.
+    fn unfold(&self) -> Record {
+        let mut seq = self.seq.clone();
+        for _ in 0..4 {
+            seq.push(Status::Unknown);
+            seq.extend(&self.seq);
+        }
+
+        let mut cluster_sizes = self.cluster_sizes.clone();
+        for _ in 0..4 {
+            cluster_sizes.extend(&self.cluster_sizes);
+        }
+
+        Record { seq, cluster_sizes }
+    }
+}
+
 /// Try to match one cluster in the sequence. If successful, return true. Update `seq` and
 /// `cluster_sizes` to remove the matched cluster.
 fn match_cluster(
@@ -112,7 +134,7 @@ fn count_matches(
     // If the list of cluster sizes is empty, then we have a match only if there are no remaining
     // Damaged statuses in seq (Unknowns will be replaced as Operational).
     if damaged_clusters.is_empty() {
-        match seq.iter().any(|s| *s == Status::Damaged) {
+        match seq.contains(&Status::Damaged) {
             true => return 0,
             false => return 1,
         }
@@ -156,33 +178,54 @@ fn process_records(rows: Vec<Record>) -> u64 {
 
 /// For each record, compute the number of possible operational/damaged spring arrangements
 /// to unknown spots. Return the sum of arrangements.
-pub fn solve_part1(records: &Vec<Record>) -> u64 {
-    process_records(records.clone())
+pub fn solve_part1(records: &[Record]) -> u64 {
+    process_records(records.to_vec())
 }
 
 /// First unfold each record, by repeating the seq 5 times with '?' separators, and repeating the
 /// cluster sizes 5 times as well. Then compute the same thing as in part 1, namely the sum of all
 /// possible arrangements of operational/damaged springs to unknown spots.
-pub fn solve_part2(records: &Vec<Record>) -> u64 {
-    let records: Vec<Record> = records
-        .iter()
-        .map(|record| {
-            let mut seq = record.seq.clone();
-            for _ in 0..4 {
-                seq.push(Status::Unknown);
-                seq.extend(&record.seq);
-            }
+pub fn solve_part2(records: &[Record]) -> u64 {
+    process_records(records.iter().map(Record::unfold).collect())
+}
 
-            let mut cluster_sizes = record.cluster_sizes.clone();
-            for _ in 0..4 {
-                cluster_sizes.extend(&record.cluster_sizes);
-            }
+/// Solve both parts while reading the records one at a time.
+pub fn solve_reader(reader: impl BufRead) -> Result<(u64, u64), ReadError> {
+    let mut sum1: u64 = 0;
+    let mut sum2: u64 = 0;
+    let mut memo = HashMap::new();
 
-            Record { seq, cluster_sizes }
-        })
-        .collect();
+    for record in parser::parse_reader(reader) {
+        let record = record?;
+        let unfolded = record.unfold();
 
-    process_records(records)
+        // Records rarely share their memoized results, so don't let the memo grow with the input
+        memo.clear();
+        sum1 += count_matches(record.seq, record.cluster_sizes, &mut memo);
+        sum2 += count_matches(unfolded.seq, unfolded.cluster_sizes, &mut memo);
+    }
+
+    Ok((sum1, sum2))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Vec<Record>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -201,10 +244,13 @@ mod tests {
             ?###???????? 3,2,1
         "};
 
-        let rows = parser::parse_input(&input).unwrap();
+        let rows = parser::parse_input(input).unwrap();
         let result = solve_part1(&rows);
         assert_eq!(result, 21);
         let result = solve_part2(&rows);
         assert_eq!(result, 525152);
+
+        let result = solve_reader(input.as_bytes()).unwrap();
+        assert_eq!(result, (21, 525152));
     }
 }
@@ -1,26 +1,20 @@
 use crate::{Record, Status};
-use itertools::Itertools;
+use common::parse::{self, one_of, parse_lines, tag, IResult};
+use common::{ParseError, ReadError};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{one_of, space1, u32},
+    character::complete::{space1, u32},
+    combinator::map_res,
     multi::{many1, separated_list1},
     sequence::separated_pair,
-    IResult,
 };
+use std::io::BufRead;
 
 fn parse_number_list(input: &str) -> IResult<&str, Vec<u32>> {
     separated_list1(tag(","), u32)(input)
 }
 
 fn parse_statuses(input: &str) -> IResult<&str, Vec<Status>> {
-    let (remainder, statuses) = many1(one_of(".#?"))(input)?;
-
-    let statuses = statuses
-        .into_iter()
-        .map(|ch| Status::from(ch))
-        .collect();
-
-    Ok((remainder, statuses))
+    many1(map_res(one_of(".#?"), Status::try_from))(input)
 }
 
 fn parse_record(input: &str) -> IResult<&str, Record> {
@@ -35,13 +29,13 @@ fn parse_record(input: &str) -> IResult<&str, Record> {
     Ok((remainder, record))
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Record>, Box<dyn std::error::Error + '_>> {
-    let records = input
-        .lines()
-        .map(|line| parse_record(line).map(|(_, record)| record))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<Vec<Record>, ParseError> {
+    parse_lines(input, parse_record)
+}
 
-    Ok(records)
+/// Parse the records one at a time, while reading them.
+pub fn parse_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Record, ReadError>> {
+    parse::parse_reader(reader, parse_record)
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 //! https://adventofcode.com/2023/day/13
 
+use common::{ParseError, Solution};
+
 pub mod parser;
 
 #[derive(Debug)]
@@ -22,7 +24,7 @@ struct ReflectionLine {
 
 trait LineComparator {
     /// Compare two lines
-    fn cmp_line(line1: &Vec<char>, line2: &Vec<char>) -> bool;
+    fn cmp_line(line1: &[char], line2: &[char]) -> bool;
 
     /// Compare two sets of lines to find if they are a mirror match
     fn mirror_match(lines1: &[Vec<char>], lines2: &[Vec<char>]) -> bool;
@@ -31,7 +33,7 @@ trait LineComparator {
 struct StrictLineComparator {}
 
 impl LineComparator for StrictLineComparator {
-    fn cmp_line(line1: &Vec<char>, line2: &Vec<char>) -> bool {
+    fn cmp_line(line1: &[char], line2: &[char]) -> bool {
         line1 == line2
     }
 
@@ -49,7 +51,7 @@ impl LineComparator for StrictLineComparator {
 struct SmudgeLineComparator {}
 
 impl LineComparator for SmudgeLineComparator {
-    fn cmp_line(line1: &Vec<char>, line2: &Vec<char>) -> bool {
+    fn cmp_line(line1: &[char], line2: &[char]) -> bool {
         line1 == line2 || is_off_by_one(line1, line2)
     }
 
@@ -67,7 +69,7 @@ impl LineComparator for SmudgeLineComparator {
     }
 }
 
-fn is_off_by_one(line1: &Vec<char>, line2: &Vec<char>) -> bool {
+fn is_off_by_one(line1: &[char], line2: &[char]) -> bool {
     let count_diffs = line1
         .iter()
         .zip(line2.iter())
@@ -78,7 +80,7 @@ fn is_off_by_one(line1: &Vec<char>, line2: &Vec<char>) -> bool {
 }
 
 /// Check a candidate reflection line by verifing that outside lines are also mirrored.
-fn check_candidate_reflection_line<C>(lines: &Vec<Vec<char>>, candidate: &ReflectionLine) -> bool
+fn check_candidate_reflection_line<C>(lines: &[Vec<char>], candidate: &ReflectionLine) -> bool
 where
     C: LineComparator,
 {
@@ -92,7 +94,7 @@ where
 }
 
 fn find_reflection_line_inner<C>(
-    lines: &Vec<Vec<char>>,
+    lines: &[Vec<char>],
     orientation: Orientation,
 ) -> Option<ReflectionLine>
 where
@@ -125,7 +127,7 @@ where
 /// of each pattern as follows. For horizonal ones, return the number of rows above it multiplied
 /// by 100. For vertical ones, return the number of columns to the left of it. Return the sum of
 /// these values.
-fn process_patterns<C>(patterns: &Vec<Pattern>) -> u32
+fn process_patterns<C>(patterns: &[Pattern]) -> u32
 where
     C: LineComparator,
 {
@@ -145,16 +147,36 @@ where
 /// For each pattern find the reflection line. For horizonal ones, return the number of rows above
 /// it multiplied by 100. For vertical ones, return the number of columns to the left of it. Return
 /// the sum of these values.
-pub fn solve_part1(patterns: &Vec<Pattern>) -> u32 {
+pub fn solve_part1(patterns: &[Pattern]) -> u32 {
     process_patterns::<StrictLineComparator>(patterns)
 }
 
 /// Flip a single symbol on each pattern that reveals a different reflection line. Then compute the
 /// same sum as in part 1.
-pub fn solve_part2(patterns: &Vec<Pattern>) -> u32 {
+pub fn solve_part2(patterns: &[Pattern]) -> u32 {
     process_patterns::<SmudgeLineComparator>(patterns)
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<Pattern>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,7 +202,7 @@ mod tests {
             #....#..#
         "};
 
-        let patterns = parser::parse_input(&input).unwrap();
+        let patterns = parser::parse_input(input).unwrap();
         let result = solve_part1(&patterns);
         assert_eq!(result, 405);
         let result = solve_part2(&patterns);
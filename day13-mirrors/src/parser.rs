@@ -1,8 +1,10 @@
 use crate::Pattern;
+use common::parse::{fail, one_of, parse_all, IResult};
+use common::ParseError;
 use nom::{
-    character::complete::{line_ending, multispace1, one_of},
+    character::complete::{line_ending, multispace1},
+    combinator::recognize,
     multi::{many1, separated_list1},
-    IResult,
 };
 
 fn transpose<T: Clone>(input: &Vec<Vec<T>>) -> Vec<Vec<T>> {
@@ -20,8 +22,17 @@ fn transpose<T: Clone>(input: &Vec<Vec<T>>) -> Vec<Vec<T>> {
 }
 
 fn parse_pattern(input: &str) -> IResult<&str, Pattern> {
-    let (remainder, rows) = separated_list1(line_ending, many1(one_of(".#")))(input)?;
+    let (remainder, lines) = separated_list1(line_ending, recognize(many1(one_of(".#"))))(input)?;
+
+    let width = lines[0].len();
+    if let Some(line) = lines.iter().find(|line| line.len() != width) {
+        return fail(
+            &line[width.min(line.len())..],
+            format!("a row of length {width}"),
+        );
+    }
 
+    let rows: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
     let cols: Vec<Vec<char>> = transpose(&rows);
 
     let pattern = Pattern { rows, cols };
@@ -29,8 +40,6 @@ fn parse_pattern(input: &str) -> IResult<&str, Pattern> {
     Ok((remainder, pattern))
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Pattern>, Box<dyn std::error::Error + '_>> {
-    let (_, patterns) = separated_list1(multispace1, parse_pattern)(input)?;
-
-    Ok(patterns)
+pub fn parse_input(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    parse_all(input, separated_list1(multispace1, parse_pattern))
 }
@@ -1,48 +1,33 @@
 //! https://adventofcode.com/2023/day/14
 
+use common::{ParseError, Simulation, Snapshot, Solution};
+use grid::{Direction, Grid};
 use std::collections::HashMap;
 
 pub mod parser;
+#[cfg(feature = "viz")]
+mod viz;
 
+/// The platform is stored transposed, so that each row of the grid is a column of the platform.
 #[derive(Clone, PartialEq)]
 pub struct Platform {
-    columns: Vec<Vec<char>>,
+    columns: Grid<char>,
 }
 
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let rows = transpose(&self.columns);
-        for row in rows {
-            writeln!(f, "{}", String::from_iter(row))?;
-        }
-        Ok(())
+        write!(f, "{}", self.columns.transpose())
     }
 }
 
-fn transpose<T: Copy>(input: &Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let mut output: Vec<Vec<T>> = Vec::new();
-    let row_len = input[0].len();
-
-    for i in 0..row_len {
-        let mut col: Vec<T> = Vec::new();
-        for row in input {
-            col.push(row[i])
-        }
-        output.push(col);
-    }
-    output
-}
-
-fn swap(line: &mut Vec<char>, i: usize, j: usize) {
+fn swap(line: &mut [char], i: usize, j: usize) {
     if i != j {
-        let tmp = line[i];
-        line[i] = line[j];
-        line[j] = tmp;
+        line.swap(i, j);
     }
 }
 
 /// Shift all 'O' rocks as far as they will go to the beginning of the line
-fn shift_rocks_left(line: &mut Vec<char>) {
+fn shift_rocks_left(line: &mut [char]) {
     let mut swap_index: usize = 0;
     for i in 0..line.len() {
         match line[i] {
@@ -51,7 +36,7 @@ fn shift_rocks_left(line: &mut Vec<char>) {
             },
             'O' => {
                 swap(line, i, swap_index);
-                swap_index = swap_index + 1;
+                swap_index += 1;
             },
             '.' => { /* do nothing */ },
             _ => panic!("Unexpected symbol"),
@@ -60,7 +45,7 @@ fn shift_rocks_left(line: &mut Vec<char>) {
 }
 
 /// Shift all 'O' rocks as far as they will go to the end of the line
-fn shift_rocks_right(line: &mut Vec<char>) {
+fn shift_rocks_right(line: &mut [char]) {
     let mut swap_index: usize = line.len() - 1;
     for i in (0..line.len()).rev() {
         match line[i] {
@@ -78,7 +63,7 @@ fn shift_rocks_right(line: &mut Vec<char>) {
 }
 
 /// Compute the load of a column
-fn compute_load(column: &Vec<char>) -> usize {
+fn compute_load(column: &[char]) -> usize {
     column
         .iter()
         .enumerate()
@@ -92,74 +77,155 @@ fn compute_load(column: &Vec<char>) -> usize {
         .sum()
 }
 
-fn cycle(platform: &mut Platform) {
-    // tip N
-    platform.columns.iter_mut().for_each(|mut column| shift_rocks_left(&mut column));
-    platform.columns = transpose(&platform.columns);
-
-    // tip W
-    platform.columns.iter_mut().for_each(|mut column| shift_rocks_left(&mut column));
-    platform.columns = transpose(&platform.columns);
+/// Compute the total load on the north support beams
+fn north_load(platform: &Platform) -> u32 {
+    platform
+        .columns
+        .rows()
+        .map(|column| compute_load(column) as u32)
+        .sum()
+}
 
-    // tip S
-    platform.columns.iter_mut().for_each(|mut column| shift_rocks_right(&mut column));
-    platform.columns = transpose(&platform.columns);
+/// Order of the tilts in a spin cycle
+pub const CYCLE: [Direction; 4] = [Direction::N, Direction::W, Direction::S, Direction::E];
 
-    // tip E
-    platform.columns.iter_mut().for_each(|mut column| shift_rocks_right(&mut column));
-    platform.columns = transpose(&platform.columns);
+/// Tilt the platform so that all 'O' rocks roll as far as they will go in the direction
+pub fn tilt(platform: &mut Platform, direction: Direction) {
+    match direction {
+        Direction::N => platform.columns.rows_mut().for_each(shift_rocks_left),
+        Direction::S => platform.columns.rows_mut().for_each(shift_rocks_right),
+        Direction::W | Direction::E => {
+            let mut rows = platform.columns.transpose();
+            match direction {
+                Direction::W => rows.rows_mut().for_each(shift_rocks_left),
+                _ => rows.rows_mut().for_each(shift_rocks_right),
+            }
+            platform.columns = rows.transpose();
+        },
+    }
 }
 
 /// Compute the total load on the platform after tipping it north
 pub fn solve_part1(platform: &Platform) -> u32 {
-    let columns = platform.columns.clone();
-    
+    let mut columns = platform.columns.clone();
+    columns.rows_mut().for_each(shift_rocks_left);
+
     columns
-        .into_iter()
-        .map(|mut column| {
-            shift_rocks_left(&mut column);
-            compute_load(&column) as u32
-        })
+        .rows()
+        .map(|column| compute_load(column) as u32)
         .sum()
 }
 
 /// Compute the total load on the platform after 1_000_000_000 cycles
 pub fn solve_part2(platform: &Platform) -> u32 {
-    let mut platform: Platform = platform.clone();
+    const CYCLES: usize = 1_000_000_000;
+    let mut simulation = SpinCycle::new(platform);
 
-    // Track in which loop iteration each platform snapshot is first seen
-    let mut snapshots: HashMap<String, usize> = HashMap::new();
-    let mut last_iteration: usize = 0;
-    let mut loop_length: usize = 0;
+    // Track after how many cycles each platform is first seen
+    let mut seen: HashMap<Grid<char>, usize> = HashMap::new();
 
-    for i in 0..1_000_000_000 {
-        cycle(&mut platform);
+    for cycles in 1..=CYCLES {
+        simulation.cycle();
 
-        let key = platform.to_string();
-        match snapshots.get(&key) {
-            None => {
-                snapshots.insert(key, i);
-            }
-            Some(prev_iteration) => {
-                last_iteration = i;
-                loop_length = i - *prev_iteration;
-                break;
+        let key = simulation.state.platform.columns.clone();
+        if let Some(first_seen) = seen.insert(key, cycles) {
+            // The platforms repeat from here on, so skip all the whole loops
+            let remainder = (CYCLES - cycles) % (cycles - first_seen);
+            for _ in 0..remainder {
+                simulation.cycle();
             }
+            break;
         }
     }
 
-    if loop_length != 0 {
-        let remainder = (1_000_000_000 - 1 - last_iteration) % loop_length;
-        for _ in 0..remainder {
-            cycle(&mut platform);
+    north_load(&simulation.state.platform)
+}
+
+/// Spin cycles of the platform, one tilt per step. The spinning never ends.
+pub struct SpinCycle {
+    state: SpinState,
+}
+
+impl SpinCycle {
+    pub fn new(platform: &Platform) -> SpinCycle {
+        SpinCycle {
+            state: SpinState {
+                platform: platform.clone(),
+                tilts: 0,
+            },
         }
     }
 
-    platform
-        .columns
-        .iter()
-        .map(|column| compute_load(&column) as u32)
-        .sum()
+    /// Run a whole spin cycle, one tilt in each direction.
+    fn cycle(&mut self) {
+        for _ in CYCLE {
+            self.step();
+        }
+    }
+}
+
+impl Simulation for SpinCycle {
+    type Snapshot = SpinState;
+
+    fn snapshot(&self) -> SpinState {
+        self.state.clone()
+    }
+
+    fn step(&mut self) -> bool {
+        let direction = CYCLE[self.state.tilts % CYCLE.len()];
+        tilt(&mut self.state.platform, direction);
+        self.state.tilts += 1;
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct SpinState {
+    pub platform: Platform,
+    /// Number of tilts so far
+    pub tilts: usize,
+}
+
+impl SpinState {
+    pub fn last_tilt(&self) -> Option<Direction> {
+        self.tilts
+            .checked_sub(1)
+            .map(|tilt| CYCLE[tilt % CYCLE.len()])
+    }
+}
+
+impl Snapshot for SpinState {
+    fn fields(&self) -> Vec<(String, String)> {
+        let last_tilt = self
+            .last_tilt()
+            .map_or("-".to_string(), |dir| format!("{dir:?}"));
+        vec![
+            ("tilts".to_string(), self.tilts.to_string()),
+            ("cycles".to_string(), (self.tilts / CYCLE.len()).to_string()),
+            ("last_tilt".to_string(), last_tilt),
+            ("load".to_string(), north_load(&self.platform).to_string()),
+        ]
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Platform;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +245,77 @@ mod tests {
         assert_eq!(String::from_iter(&column), "..OO#..O#...O");
     }
 
+    #[test]
+    fn spin_cycle() {
+        let input = indoc! {"
+            O....#....
+            O.OO#....#
+            .....##...
+            OO.#O....O
+            .O.....O#.
+            O.#..O.#.#
+            ..O..#O..O
+            .......O..
+            #....###..
+            #OO..#....
+        "};
+        let platform = parser::parse_input(input).unwrap();
+        let mut simulation = SpinCycle::new(&platform);
+
+        // The platform after each of the first three cycles, from the puzzle
+        let cycles = [
+            indoc! {"
+                .....#....
+                ....#...O#
+                ...OO##...
+                .OO#......
+                .....OOO#.
+                .O#...O#.#
+                ....O#....
+                ......OOOO
+                #...O###..
+                #..OO#....
+            "},
+            indoc! {"
+                .....#....
+                ....#...O#
+                .....##...
+                ..O#......
+                .....OOO#.
+                .O#...O#.#
+                ....O#...O
+                .......OOO
+                #..OO###..
+                #.OOO#...O
+            "},
+            indoc! {"
+                .....#....
+                ....#...O#
+                .....##...
+                ..O#......
+                .....OOO#.
+                .O#...O#.#
+                ....O#...O
+                .......OOO
+                #...O###.O
+                #.OOO#...O
+            "},
+        ];
+        for expected in cycles {
+            for _ in 0..CYCLE.len() {
+                assert!(simulation.step());
+            }
+            let expected = parser::parse_input(expected).unwrap();
+            assert!(simulation.snapshot().platform == expected);
+        }
+
+        simulation.step();
+        let fields = simulation.snapshot().fields();
+        assert_eq!(fields[0], ("tilts".to_string(), "13".to_string()));
+        assert_eq!(fields[1], ("cycles".to_string(), "3".to_string()));
+        assert_eq!(fields[2], ("last_tilt".to_string(), "N".to_string()));
+    }
+
     #[test]
     fn test1() {
         let input = indoc! {"
@@ -194,7 +331,7 @@ mod tests {
             #OO..#....
         "};
 
-        let platform = parser::parse_input(&input).unwrap();
+        let platform = parser::parse_input(input).unwrap();
         let result = solve_part1(&platform);
         assert_eq!(result, 136);
         let result = solve_part2(&platform);
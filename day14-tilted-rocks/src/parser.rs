@@ -1,22 +1,33 @@
-use crate::{transpose, Platform};
+use crate::Platform;
+use common::parse::{fail, one_of, parse_all, IResult};
+use common::ParseError;
+use grid::Grid;
 use nom::{
-    character::complete::{line_ending, one_of},
+    character::complete::line_ending,
+    combinator::recognize,
     multi::{many1, separated_list1},
-    IResult,
 };
 
 pub fn parse_platform(input: &str) -> IResult<&str, Platform> {
-    let (remainder, rows) = separated_list1(line_ending, many1(one_of(".#O")))(input)?;
+    let (remainder, lines) = separated_list1(line_ending, recognize(many1(one_of(".#O"))))(input)?;
+
+    let width = lines[0].len();
+    if let Some(line) = lines.iter().find(|line| line.len() != width) {
+        return fail(
+            &line[width.min(line.len())..],
+            format!("a row of length {width}"),
+        );
+    }
+
+    let rows = lines.iter().map(|line| line.chars().collect());
 
     let platform = Platform {
-        columns: transpose(&rows),
+        columns: Grid::from_rows(rows).transpose(),
     };
 
     Ok((remainder, platform))
 }
 
-pub fn parse_input(input: &str) -> Result<Platform, Box<dyn std::error::Error + '_>> {
-    let (_, platform) = parse_platform(input)?;
-
-    Ok(platform)
+pub fn parse_input(input: &str) -> Result<Platform, ParseError> {
+    parse_all(input, parse_platform)
 }
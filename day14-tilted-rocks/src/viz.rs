@@ -0,0 +1,18 @@
+//! Picture of the platform during the spin cycles.
+
+use crate::SpinState;
+use render::{Canvas, Cell, Color, Render};
+
+/// The platform with the rounded rocks in yellow.
+impl Render for SpinState {
+    fn render(&self) -> Canvas {
+        self.platform
+            .columns
+            .transpose()
+            .map(|&symbol| match symbol {
+                'O' => Cell::new(symbol).fg(Color::Yellow),
+                '#' => Cell::new(symbol).fg(Color::Blue),
+                _ => Cell::new(symbol),
+            })
+    }
+}
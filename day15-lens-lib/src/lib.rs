@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/15
 
+use common::{ParseError, Solution};
 use indexmap::map::IndexMap;
 
 pub mod parser;
@@ -14,12 +15,14 @@ pub enum Operation {
     Replace,
 }
 
-impl From<char> for Operation {
-    fn from(ch: char) -> Operation {
+impl TryFrom<char> for Operation {
+    type Error = &'static str;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
         match ch {
-            '-' => Operation::Remove,
-            '=' => Operation::Replace,
-            _ => panic!("Unrecognized operation character"),
+            '-' => Ok(Operation::Remove),
+            '=' => Ok(Operation::Replace),
+            _ => Err("Invalid operation symbol"),
         }
     }
 }
@@ -36,7 +39,7 @@ fn hash(command: &[char]) -> u32 {
         let ascii_code = *ch as u32;
         hash += ascii_code;
         hash *= 17;
-        hash = hash % 256;
+        hash %= 256;
     }
     hash
 }
@@ -76,6 +79,26 @@ pub fn solve_part2(seq: &InitSeq) -> u32 {
     sum
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = InitSeq;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,10 +110,24 @@ mod tests {
             rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
         "};
 
-        let seq = parser::parse_input(&input).unwrap();
+        let seq = parser::parse_input(input).unwrap();
         let result = solve_part1(&seq);
         assert_eq!(result, 1320);
         let result = solve_part2(&seq);
         assert_eq!(result, 145);
     }
+
+    #[test]
+    fn test_focal_length() {
+        let seq = parser::parse_input("rn=1,cm-,qp=3").unwrap();
+        let focus: Vec<u32> = seq.commands_v2.iter().map(|cmd| cmd.focus_value).collect();
+        assert_eq!(focus, [1, 0, 3]);
+
+        // The focal length is required after `=` and must fit in a u32
+        let error = parser::parse_input("rn=").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 4));
+        let error = parser::parse_input("rn=99999999999").err().unwrap();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert!(parser::parse_input("rn=4294967295").is_ok());
+    }
 }
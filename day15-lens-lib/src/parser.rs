@@ -1,11 +1,11 @@
 use crate::{Command, InitSeq, Operation};
+use common::parse::{one_of, parse_all, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, digit0, one_of},
-    combinator::recognize,
+    character::complete::{alpha1, digit0, u32},
+    combinator::{map_res, recognize},
     multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
 
 fn parse_command_v1(input: &str) -> IResult<&str, &str> {
@@ -16,13 +16,20 @@ fn parse_command_list_v1(input: &str) -> IResult<&str, Vec<&str>> {
     separated_list1(tag(","), parse_command_v1)(input)
 }
 
+/// Parse `<label>-` or `<label>=<focal length>`. The `-` takes no focal length, while the `=`
+/// needs one which fits in a `u32`.
 fn parse_command_v2(input: &str) -> IResult<&str, Command> {
-    let (remainder, (tag, op, focus)) = tuple((alpha1, one_of("-="), digit0))(input)?;
+    let (input, (tag, operation)) =
+        tuple((alpha1, map_res(one_of("-="), Operation::try_from)))(input)?;
+    let (remainder, focus_value) = match operation {
+        Operation::Remove => (input, 0),
+        Operation::Replace => u32(input)?,
+    };
 
     let command = Command {
         tag: tag.chars().collect(),
-        operation: Operation::from(op),
-        focus_value: focus.parse::<u32>().unwrap_or(0),
+        operation,
+        focus_value,
     };
 
     Ok((remainder, command))
@@ -32,14 +39,14 @@ fn parse_command_list_v2(input: &str) -> IResult<&str, Vec<Command>> {
     separated_list1(tag(","), parse_command_v2)(input)
 }
 
-pub fn parse_input(input: &str) -> Result<InitSeq, Box<dyn std::error::Error + '_>> {
-    let (_, commands) = parse_command_list_v1(input.trim())?;
+pub fn parse_input(input: &str) -> Result<InitSeq, ParseError> {
+    let commands = parse_all(input, parse_command_list_v1)?;
     let commands_v1 = commands
         .into_iter()
         .map(|cmd| cmd.chars().collect())
         .collect();
 
-    let (_, commands_v2) = parse_command_list_v2(input.trim())?;
+    let commands_v2 = parse_all(input, parse_command_list_v2)?;
 
     Ok(InitSeq {
         commands_v1,
@@ -1,199 +1,197 @@
 //! https://adventofcode.com/2023/day/16
 
+use common::{ParseError, Simulation, Snapshot, Solution};
+use grid::{Coord, Direction, Grid};
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
+#[cfg(feature = "viz")]
+mod viz;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
+#[derive(Clone)]
+pub struct Layout {
+    tiles: Grid<char>,
 }
 
-impl Coord {
-    pub fn next(&self, direction: Direction) -> Coord {
-        match direction {
-            Direction::N => Coord {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::S => Coord {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::E => Coord {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Direction::W => Coord {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
+impl Layout {
+    pub fn load_from_input(input: &str) -> Result<Layout, ParseError> {
+        let tiles = Grid::parse(input, |_, ch| match ch {
+            '.' | '|' | '-' | '/' | '\\' => Ok(ch),
+            _ => Err("a mirror, a splitter or empty space"),
+        })?;
 
-    pub fn to_usize_pair(&self) -> (usize, usize) {
-        (self.x as usize, self.y as usize)
+        Ok(Layout { tiles })
     }
 }
 
-impl From<(i32, i32)> for Coord {
-    fn from((x, y): (i32, i32)) -> Coord {
-        Coord { x, y }
+/// Directions in which a beam entering the tile in the given direction leaves it. Splitters give
+/// two directions.
+fn deflect(tile: char, direction: Direction) -> (Direction, Option<Direction>) {
+    use Direction::*;
+
+    match (tile, direction) {
+        ('/', N) => (E, None),
+        ('/', S) => (W, None),
+        ('/', E) => (N, None),
+        ('/', W) => (S, None),
+        ('\\', N) => (W, None),
+        ('\\', S) => (E, None),
+        ('\\', E) => (S, None),
+        ('\\', W) => (N, None),
+        ('-', N | S) => (E, Some(W)),
+        ('|', E | W) => (N, Some(S)),
+        _ => (direction, None),
     }
 }
 
-#[derive(Clone)]
-pub struct Layout {
-    tiles: Vec<Vec<char>>,
-    energized_tiles: HashSet<(Coord, Direction)>,
+/// Beams of light going through the layout, each moving by one tile per step. A beam stops when
+/// it leaves the layout or enters a loop, and the simulation ends when all beams have stopped.
+pub struct Beams<'a> {
+    layout: &'a Layout,
+    /// Tile and direction of the front of each moving beam
+    fronts: Vec<(Coord, Direction)>,
+    /// Tiles entered by a beam so far, with the direction of the beam
+    visited: HashSet<(Coord, Direction)>,
+    energized: Grid<bool>,
+    energized_count: u32,
+    steps: usize,
 }
 
-impl Layout {
-    pub fn load_from_input(input: &str) -> Layout {
-        let mut tiles: Vec<Vec<char>> = Vec::new();
+impl<'a> Beams<'a> {
+    pub fn new(layout: &'a Layout, start_coord: Coord, direction: Direction) -> Beams<'a> {
+        let tiles = &layout.tiles;
+        let mut beams = Beams {
+            layout,
+            fronts: Vec::new(),
+            visited: HashSet::new(),
+            energized: Grid::filled(tiles.width(), tiles.height(), false),
+            energized_count: 0,
+            steps: 0,
+        };
+        beams.enter(start_coord, direction);
+        beams
+    }
 
-        for line in input.lines() {
-            tiles.push(line.chars().collect());
+    /// Move the front of a beam to the tile, unless the beam stops there.
+    fn enter(&mut self, coord: Coord, direction: Direction) {
+        // Stop if out of bounds of the layout, or if the beam is entering a loop
+        if !self.layout.tiles.contains(coord) || !self.visited.insert((coord, direction)) {
+            return;
         }
 
-        Layout {
-            tiles,
-            energized_tiles: HashSet::new(),
+        if !self.energized[coord] {
+            self.energized[coord] = true;
+            self.energized_count += 1;
         }
+        self.fronts.push((coord, direction));
     }
+}
 
-    fn contains(&self, coord: &Coord) -> bool {
-        let max_x = self.tiles[0].len() as i32;
-        let max_y = self.tiles.len() as i32;
+impl<'a> Simulation for Beams<'a> {
+    type Snapshot = BeamState<'a>;
 
-        if coord.x < 0 || coord.x >= max_x {
-            false
-        } else if coord.y < 0 || coord.y >= max_y {
-            false
-        } else {
-            true
+    fn snapshot(&self) -> BeamState<'a> {
+        BeamState {
+            tiles: &self.layout.tiles,
+            fronts: self.fronts.clone(),
+            energized: self.energized.clone(),
+            energized_count: self.energized_count,
+            steps: self.steps,
         }
     }
 
-    fn track_beam(&mut self, coord: Coord, direction: Direction) {
-        // Stop if out of bounds of the layout
-        if !self.contains(&coord) {
-            return;
+    fn step(&mut self) -> bool {
+        if self.fronts.is_empty() {
+            return false;
         }
 
-        // Stop if the beam is entering a loop
-        if self.energized_tiles.contains(&(coord, direction)) {
-            return;
+        for (coord, direction) in std::mem::take(&mut self.fronts) {
+            let (first, second) = deflect(self.layout.tiles[coord], direction);
+            for direction in std::iter::once(first).chain(second) {
+                self.enter(coord.step(direction), direction);
+            }
         }
+        self.steps += 1;
+        true
+    }
+}
 
-        self.energized_tiles.insert((coord, direction));
-
-        let (x, y) = coord.to_usize_pair();
-        match self.tiles[y][x] {
-            '.' => {
-                self.track_beam(coord.next(direction), direction);
-            },
-            '/' => match direction {
-                Direction::N => {
-                    self.track_beam(coord.next(Direction::E), Direction::E);
-                },
-                Direction::S => {
-                    self.track_beam(coord.next(Direction::W), Direction::W);
-                },
-                Direction::E => {
-                    self.track_beam(coord.next(Direction::N), Direction::N);
-                },
-                Direction::W => {
-                    self.track_beam(coord.next(Direction::S), Direction::S);
-                },
-            },
-            '\\' => match direction {
-                Direction::N => {
-                    self.track_beam(coord.next(Direction::W), Direction::W);
-                },
-                Direction::S => {
-                    self.track_beam(coord.next(Direction::E), Direction::E);
-                },
-                Direction::E => {
-                    self.track_beam(coord.next(Direction::S), Direction::S);
-                },
-                Direction::W => {
-                    self.track_beam(coord.next(Direction::N), Direction::N);
-                },
-            },
-            '-' => match direction {
-                Direction::E | Direction::W => {
-                    self.track_beam(coord.next(direction), direction);
-                },
-                Direction::N | Direction::S => {
-                    self.track_beam(coord.next(Direction::E), Direction::E);
-                    self.track_beam(coord.next(Direction::W), Direction::W);
-                },
-            },
-            '|' => match direction {
-                Direction::N | Direction::S => {
-                    self.track_beam(coord.next(direction), direction);
-                },
-                Direction::E | Direction::W => {
-                    self.track_beam(coord.next(Direction::N), Direction::N);
-                    self.track_beam(coord.next(Direction::S), Direction::S);
-                },
-            },
-            _ => unreachable!(),
-        }
+#[derive(Clone)]
+pub struct BeamState<'a> {
+    pub tiles: &'a Grid<char>,
+    /// Tile and direction of the front of each moving beam
+    pub fronts: Vec<(Coord, Direction)>,
+    pub energized: Grid<bool>,
+    pub energized_count: u32,
+    pub steps: usize,
+}
+
+impl Snapshot for BeamState<'_> {
+    fn fields(&self) -> Vec<(String, String)> {
+        vec![
+            ("steps".to_string(), self.steps.to_string()),
+            ("beams".to_string(), self.fronts.len().to_string()),
+            ("energized".to_string(), self.energized_count.to_string()),
+        ]
     }
 }
 
 /// Count the energized tiles for the given start tile and direction.
-fn count_energized(mut layout: Layout, start_coord: Coord, direction: Direction) -> u32 {
-    layout.track_beam(start_coord, direction);
-
-    // Count each energized tile once
-    layout
-        .energized_tiles
-        .iter()
-        .map(|(coord, _)| coord)
-        .collect::<HashSet<_>>()
-        .len() as u32
+fn count_energized(layout: &Layout, start_coord: Coord, direction: Direction) -> u32 {
+    let mut beams = Beams::new(layout, start_coord, direction);
+    while beams.step() {}
+    beams.energized_count
 }
 
 /// Discover all tiles that become energized in the layout and count them. Start from the top-left
 /// corner going to the right.
 pub fn solve_part1(layout: &Layout) -> u32 {
-    count_energized(layout.clone(), Coord::from((0, 0)), Direction::E)
+    count_energized(layout, Coord::new(0, 0), Direction::E)
 }
 
 /// Discover the starting point on the edge that maximizes the energized tiles. Return the max
 /// energized tiles.
 pub fn solve_part2(layout: &Layout) -> u32 {
-    let max_x = layout.tiles[0].len() as i32;
-    let max_y = layout.tiles.len() as i32;
+    let max_x = layout.tiles.width() as i64;
+    let max_y = layout.tiles.height() as i64;
 
     let mut start_configurations = Vec::new();
     for x in 0..max_x {
-        start_configurations.push((Coord::from((x, 0)), Direction::S));
-        start_configurations.push((Coord::from((x, max_y - 1)), Direction::N));
+        start_configurations.push((Coord::new(x, 0), Direction::S));
+        start_configurations.push((Coord::new(x, max_y - 1), Direction::N));
     }
     for y in 0..max_y {
-        start_configurations.push((Coord::from((0, y)), Direction::E));
-        start_configurations.push((Coord::from((max_x - 1, y)), Direction::W));
+        start_configurations.push((Coord::new(0, y), Direction::E));
+        start_configurations.push((Coord::new(max_x - 1, y), Direction::W));
     }
 
     let mut max_energized: u32 = 0;
     for (start_coord, direction) in start_configurations {
-        let count = count_energized(layout.clone(), start_coord, direction);
+        let count = count_energized(layout, start_coord, direction);
         max_energized = std::cmp::max(max_energized, count);
     }
     max_energized
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Layout;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Layout::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,10 +212,42 @@ mod tests {
             ..//.|....
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         let result = solve_part1(&layout);
         assert_eq!(result, 46);
         let result = solve_part2(&layout);
         assert_eq!(result, 51);
     }
+
+    #[test]
+    fn beams() {
+        let input = indoc! {r"
+            .\.
+            .-.
+            ...
+        "};
+        let layout = Layout::load_from_input(input).unwrap();
+        let mut beams = Beams::new(&layout, Coord::new(0, 0), Direction::E);
+
+        let fronts: Vec<_> = (0..4)
+            .map(|_| {
+                beams.step();
+                beams.snapshot().fronts
+            })
+            .collect();
+        assert_eq!(fronts[0], [(Coord::new(1, 0), Direction::E)]);
+        assert_eq!(fronts[1], [(Coord::new(1, 1), Direction::S)]);
+        assert_eq!(
+            fronts[2],
+            [
+                (Coord::new(2, 1), Direction::E),
+                (Coord::new(0, 1), Direction::W)
+            ]
+        );
+        assert_eq!(fronts[3], []);
+
+        assert!(!beams.step());
+        assert_eq!(beams.snapshot().steps, 4);
+        assert_eq!(beams.snapshot().energized_count, 5);
+    }
 }
@@ -1,11 +1,12 @@
+use common::InputSource;
 use day16_beams::{solve_part1, solve_part2, Layout};
-use std::{fs, path::Path};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
-    let layout = Layout::load_from_input(&input);
+    let layout = Layout::load_from_input(&input).expect("Invalid input");
 
     let result = solve_part1(&layout);
     println!("Part one result: {result}");
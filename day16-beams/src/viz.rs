@@ -0,0 +1,26 @@
+//! Picture of the beams going through the layout.
+
+use crate::BeamState;
+use grid::Direction;
+use render::{Canvas, Cell, Color, Render};
+
+/// The layout with the energized tiles in yellow and the front of each beam as a red arrow.
+impl Render for BeamState<'_> {
+    fn render(&self) -> Canvas {
+        let mut canvas = self.tiles.map(|&tile| Cell::new(tile));
+        for coord in self.tiles.coords().filter(|&coord| self.energized[coord]) {
+            canvas[coord].bg = Some(Color::Yellow);
+        }
+
+        for &(coord, direction) in &self.fronts {
+            let arrow = match direction {
+                Direction::N => '^',
+                Direction::E => '>',
+                Direction::S => 'v',
+                Direction::W => '<',
+            };
+            canvas[coord] = Cell::new(arrow).fg(Color::Red).bg(Color::Yellow);
+        }
+        canvas
+    }
+}
@@ -9,16 +9,16 @@
 //! perpendicular orientation. E.g. if the current node is ((5, 5), horizontal), we generate the
 //! neighbour only on the vertical axis: (5, 4), (5, 3), (5, 2), (5, 6), (5, 7), and (5, 8).
 
+use common::{Ignore, Observer, ParseError, Solution};
+use grid::{Coord, Direction, Grid};
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
+use std::collections::BinaryHeap;
+
+#[cfg(feature = "viz")]
+mod viz;
+
+#[cfg(feature = "viz")]
+pub use viz::LayoutPath;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Orientation {
@@ -26,59 +26,16 @@ enum Orientation {
     Vertical,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    pub fn next(&self, direction: Direction) -> Coord {
-        match direction {
-            Direction::N => Coord {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Direction::S => Coord {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Direction::E => Coord {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Direction::W => Coord {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
-}
-
-impl From<(i32, i32)> for Coord {
-    fn from((x, y): (i32, i32)) -> Coord {
-        Coord { x, y }
-    }
-}
-
-/// Coordinates between 'from' and 'to', including 'to', but not 'from'. 'from' and 'to' must be on
-/// the same line, otherwise the output is meaningless.
+/// Coordinates between 'from' and 'to', in order, including 'to', but not 'from'. 'from' and 'to'
+/// must be on the same line, otherwise the output is meaningless.
 fn coords_between(from: Coord, to: Coord) -> Vec<Coord> {
-    let mut coords = Vec::new();
-
-    // Try both directions - one will be an empty range and thus a noop.
-    for x in from.x..to.x {
-        coords.push(Coord::from((x + 1, from.y)));
-    }
-    for x in to.x..from.x {
-        coords.push(Coord::from((x, from.y)));
-    }
+    let step = Coord::new((to.x - from.x).signum(), (to.y - from.y).signum());
 
-    for y in from.y..to.y {
-        coords.push(Coord::from((from.x, y + 1)));
-    }
-    for y in to.y..from.y {
-        coords.push(Coord::from((from.x, y)));
+    let mut coords = Vec::new();
+    let mut coord = from;
+    for _ in 0..from.manhattan_distance(to) {
+        coord = coord + step;
+        coords.push(coord);
     }
 
     coords
@@ -154,70 +111,42 @@ impl PartialEq for TileHeapInfo {
     }
 }
 
+/// Each coordinate holds two tiles, indexed by `Orientation`.
 #[derive(Debug, Clone)]
 pub struct Layout {
-    tiles: HashMap<TilePosition, Tile>,
-    max_x: i32,
-    max_y: i32,
+    tiles: Grid<[Tile; 2]>,
     min_tiles: usize,
     max_tiles: usize,
 }
 
 impl Layout {
-    pub fn load_from_input(input: &str) -> Layout {
-        let mut tiles: HashMap<TilePosition, Tile> = HashMap::new();
-        let mut max_x: usize = 0;
-        let mut max_y: usize = 0;
-
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                let cost = ch.to_digit(10).unwrap();
-                let coord = Coord::from((i as i32, j as i32));
-
-                let tile = Tile {
-                    cost,
-                    path_cost: u32::MAX,
-                    prev: None,
-                    visited: false,
-                };
-                let position = TilePosition::horizontal(coord);
-                tiles.insert(position, tile.clone());
-                let position = TilePosition::vertical(coord);
-                tiles.insert(position, tile);
-
-                max_x = i;
-            }
-            max_y = j;
-        }
-
-        Layout {
+    pub fn load_from_input(input: &str) -> Result<Layout, ParseError> {
+        let tiles = Grid::parse(input, |_, ch| {
+            let cost = ch.to_digit(10).ok_or("a digit")?;
+            let tile = Tile {
+                cost,
+                path_cost: u32::MAX,
+                prev: None,
+                visited: false,
+            };
+            Ok([tile.clone(), tile])
+        })?;
+
+        Ok(Layout {
             tiles,
-            max_x: max_x as i32 + 1,
-            max_y: max_y as i32 + 1,
             min_tiles: 1,
             max_tiles: 1,
-        }
+        })
     }
 
-    fn print_path(&self, path: &Vec<Coord>) {
-        use colored::Colorize;
-
-        let path: HashSet<Coord> = path.iter().copied().collect();
-
-        for j in 0..self.max_y {
-            for i in 0..self.max_x {
-                let coord = Coord::from((i, j));
-                let position = TilePosition::horizontal(coord);
-                let cost = self.tiles.get(&position).unwrap().cost;
+    fn tile(&self, position: TilePosition) -> Option<&Tile> {
+        let tiles = self.tiles.get(position.coord)?;
+        Some(&tiles[position.orientation as usize])
+    }
 
-                if path.contains(&coord) {
-                    print!("{}", cost.to_string().blue());
-                } else {
-                    print!("{}", cost);
-                }
-            }
-            println!()
-        }
+    fn tile_mut(&mut self, position: TilePosition) -> Option<&mut Tile> {
+        let tiles = self.tiles.get_mut(position.coord)?;
+        Some(&mut tiles[position.orientation as usize])
     }
 
     fn configure_steps(&mut self, min: usize, max: usize) {
@@ -244,10 +173,10 @@ impl Layout {
 
         // Skip the first `MIN_TILES - 1` tiles, but still accumulate their cost
         for _ in 1..self.min_tiles() {
-            coord = coord.next(direction);
+            coord = coord.step(direction);
             let position = TilePosition { coord, orientation };
 
-            match self.tiles.get(&position) {
+            match self.tile(position) {
                 Some(tile) => {
                     cost += tile.cost;
                 },
@@ -256,11 +185,11 @@ impl Layout {
         }
 
         for _ in self.min_tiles()..=self.max_tiles() {
-            coord = coord.next(direction);
+            coord = coord.step(direction);
 
             let position = TilePosition { coord, orientation };
 
-            match self.tiles.get(&position) {
+            match self.tile(position) {
                 Some(tile) => {
                     cost += tile.cost;
                     neighbours.push((position, cost))
@@ -307,7 +236,7 @@ impl Layout {
         }
     }
 
-    fn dijkstra(&mut self, start_coord: Coord) {
+    fn dijkstra(&mut self, start_coord: Coord, observer: &mut impl Observer<Event>) {
         let mut to_visit: BinaryHeap<TileHeapInfo> = BinaryHeap::new();
 
         // All path costs were initialized to infinity when creating Layout, so we only need to
@@ -316,7 +245,7 @@ impl Layout {
             TilePosition::horizontal(start_coord),
             TilePosition::vertical(start_coord),
         ] {
-            let start_tile = self.tiles.get_mut(&position).unwrap();
+            let start_tile = self.tile_mut(position).unwrap();
             start_tile.path_cost = 0;
             to_visit.push(start_tile.heap_info(position));
         }
@@ -329,14 +258,18 @@ impl Layout {
             // Given that we may enter the same tile repeatedly into the min heap, we could have
             // already found a better path previously. We must check for this so as not to worsen
             // the path costs for neighbors.
-            let tile = self.tiles.get_mut(&position).unwrap();
+            let tile = self.tile_mut(position).unwrap();
             if tile.path_cost < path_cost || (tile.path_cost == path_cost && tile.visited) {
                 continue;
             }
             tile.visited = true;
+            observer.observe(Event::Visited {
+                coord: position.coord,
+                heat_loss: path_cost,
+            });
 
             for (neighbor_position, cost_to_neighbor) in self.generate_neighbors(position) {
-                let neighbor = self.tiles.get_mut(&neighbor_position).unwrap();
+                let neighbor = self.tile_mut(neighbor_position).unwrap();
 
                 let new_path_cost = path_cost + cost_to_neighbor;
                 if new_path_cost <= neighbor.path_cost {
@@ -349,56 +282,105 @@ impl Layout {
         }
     }
 
+    /// Coordinates of the path which ends at the position, from the end back to the start.
     fn construct_path(&self, position: TilePosition) -> Vec<Coord> {
         let mut path_coords: Vec<Coord> = Vec::new();
         path_coords.push(position.coord);
 
-        let mut tile = self.tiles.get(&position).unwrap();
+        let mut tile = self.tile(position).unwrap();
         let mut last_coord = position.coord;
 
         while let Some(position) = tile.prev {
             path_coords.extend(coords_between(last_coord, position.coord));
-            tile = self.tiles.get(&position).unwrap();
+            tile = self.tile(position).unwrap();
             last_coord = position.coord;
         }
         path_coords
     }
 }
 
+/// Progress of the search for the min-cost path, reported to the observer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Dijkstra's algorithm settled the min heat loss of reaching the coordinate, when entering it
+    /// in one of the two orientations.
+    Visited { coord: Coord, heat_loss: u32 },
+    /// The min-cost path, from the start to the end coordinate. Its heat loss is the answer.
+    Path { coords: Vec<Coord>, heat_loss: u32 },
+}
+
 /// Finds the min-cost path from top-left corner to the bottom-right corner. Min and max steps in
 /// the same direction are defined by `min_steps` and `max_steps` parameters. Also reconstruct the
-/// path and print it.
-fn solve(layout: &mut Layout, min_steps: usize, max_steps: usize) -> u32 {
-    let start_coord = Coord::from((0, 0));
-    let end_coord = Coord::from((layout.max_x - 1, layout.max_y - 1));
+/// path and report it to the observer.
+fn solve(
+    layout: &mut Layout,
+    min_steps: usize,
+    max_steps: usize,
+    observer: &mut impl Observer<Event>,
+) -> u32 {
+    let start_coord = Coord::new(0, 0);
+    let end_coord = Coord::from((layout.tiles.width() - 1, layout.tiles.height() - 1));
 
     layout.configure_steps(min_steps, max_steps);
-    layout.dijkstra(start_coord);
+    layout.dijkstra(start_coord, observer);
 
-    let res1 = layout.tiles.get(&TilePosition::horizontal(end_coord)).unwrap();
-    let res2 = layout.tiles.get(&TilePosition::vertical(end_coord)).unwrap();
+    let res1 = layout.tile(TilePosition::horizontal(end_coord)).unwrap();
+    let res2 = layout.tile(TilePosition::vertical(end_coord)).unwrap();
 
-    if res1.path_cost < res2.path_cost {
-        let path = layout.construct_path(TilePosition::horizontal(end_coord));
-        layout.print_path(&path);
-        res1.path_cost
+    let end_position = if res1.path_cost < res2.path_cost {
+        TilePosition::horizontal(end_coord)
     } else {
-        let path = layout.construct_path(TilePosition::vertical(end_coord));
-        layout.print_path(&path);
-        res2.path_cost
-    }
+        TilePosition::vertical(end_coord)
+    };
+
+    let heat_loss = layout.tile(end_position).unwrap().path_cost;
+    let mut coords = layout.construct_path(end_position);
+    coords.reverse();
+    observer.observe(Event::Path { coords, heat_loss });
+
+    heat_loss
 }
 
 /// Finds the min-cost path from top-left corner to the bottom-right corner, while moving no more
 /// than 3 tiles in the same direction.
 pub fn solve_part1(layout: &Layout) -> u32 {
-    solve(&mut layout.clone(), 1, 3)
+    solve_part1_with(layout, &mut Ignore)
+}
+
+/// Same as `solve_part1`, reporting the progress and the path to the observer.
+pub fn solve_part1_with(layout: &Layout, observer: &mut impl Observer<Event>) -> u32 {
+    solve(&mut layout.clone(), 1, 3, observer)
 }
 
 /// Finds the min-cost path from top-left corner to the bottom-right corner, while moving at
 /// minimum 4 tiles and at maximum 10 tiles in the same direction.
 pub fn solve_part2(layout: &Layout) -> u32 {
-    solve(&mut layout.clone(), 4, 10)
+    solve_part2_with(layout, &mut Ignore)
+}
+
+/// Same as `solve_part2`, reporting the progress and the path to the observer.
+pub fn solve_part2_with(layout: &Layout, observer: &mut impl Observer<Event>) -> u32 {
+    solve(&mut layout.clone(), 4, 10, observer)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Layout;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Layout::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -424,7 +406,7 @@ mod tests {
             4322674655533
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         let result = solve_part1(&layout);
         assert_eq!(result, 102);
         let result = solve_part2(&layout);
@@ -441,8 +423,34 @@ mod tests {
             999999999991
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         let result = solve_part2(&layout);
         assert_eq!(result, 71);
     }
+
+    #[test]
+    fn test_observed_path() {
+        let input = indoc! {r"
+            111111111111
+            999999999991
+            999999999991
+            999999999991
+            999999999991
+        "};
+
+        let layout = Layout::load_from_input(input).unwrap();
+        let mut events = Vec::new();
+        let result = solve_part2_with(&layout, &mut events);
+
+        let Some(Event::Path { coords, heat_loss }) = events.pop() else {
+            panic!("Expected the path as the last event");
+        };
+        assert_eq!(heat_loss, result);
+        assert_eq!(coords.first(), Some(&Coord::new(0, 0)));
+        assert_eq!(coords.last(), Some(&Coord::new(11, 4)));
+        assert_eq!(coords.len(), 16);
+        assert!(events
+            .iter()
+            .all(|event| matches!(event, Event::Visited { .. })));
+    }
 }
@@ -1,15 +1,27 @@
-use day17_crucible::{solve_part1, solve_part2, Layout};
-use std::{fs, path::Path};
+use common::InputSource;
+use day17_crucible::{solve_part1_with, solve_part2_with, Layout};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
-    let layout = Layout::load_from_input(&input);
+    let layout = Layout::load_from_input(&input).expect("Invalid input");
 
-    let result = solve_part1(&layout);
+    #[cfg(feature = "viz")]
+    let mut print_path = |event| {
+        use day17_crucible::{Event, LayoutPath};
+
+        if let Event::Path { coords, .. } = event {
+            render::print(&LayoutPath::new(&layout, coords)).expect("Failed to print the path");
+        }
+    };
+    #[cfg(not(feature = "viz"))]
+    let mut print_path = common::Ignore;
+
+    let result = solve_part1_with(&layout, &mut print_path);
     println!("Part one result: {result}");
 
-    let result = solve_part2(&layout);
+    let result = solve_part2_with(&layout, &mut print_path);
     println!("Part two result: {result}");
 }
@@ -0,0 +1,33 @@
+//! Picture of the min-cost path of the crucible through the city.
+
+use crate::Layout;
+use grid::Coord;
+use render::{Canvas, Cell, Color, Render};
+use std::collections::HashSet;
+
+/// The layout with a path through it marked in blue.
+pub struct LayoutPath<'a> {
+    layout: &'a Layout,
+    path: HashSet<Coord>,
+}
+
+impl<'a> LayoutPath<'a> {
+    pub fn new(layout: &'a Layout, path: impl IntoIterator<Item = Coord>) -> LayoutPath<'a> {
+        LayoutPath {
+            layout,
+            path: path.into_iter().collect(),
+        }
+    }
+}
+
+impl Render for LayoutPath<'_> {
+    fn render(&self) -> Canvas {
+        let mut canvas = self.layout.tiles.map(|[tile, _]| {
+            Cell::new(char::from_digit(tile.cost, 10).expect("Tile costs are single digits"))
+        });
+        for &coord in &self.path {
+            canvas[coord].fg = Some(Color::Blue);
+        }
+        canvas
+    }
+}
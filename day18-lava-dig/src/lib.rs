@@ -1,49 +1,9 @@
 //! https://adventofcode.com/2023/day/18
 
-pub mod parser;
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    N,
-    S,
-    E,
-    W,
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Coord {
-    x: i64,
-    y: i64,
-}
+use common::{ParseError, Solution};
+use grid::{Coord, Direction};
 
-impl Coord {
-    fn shift(&self, direction: Direction, distance: i64) -> Coord {
-        match direction {
-            Direction::N => Coord {
-                x: self.x,
-                y: self.y - distance,
-            },
-            Direction::S => Coord {
-                x: self.x,
-                y: self.y + distance,
-            },
-            Direction::E => Coord {
-                x: self.x + distance,
-                y: self.y,
-            },
-            Direction::W => Coord {
-                x: self.x - distance,
-                y: self.y,
-            },
-        }
-    }
-}
-
-impl From<(i64, i64)> for Coord {
-    fn from((x, y): (i64, i64)) -> Coord {
-        Coord { x, y }
-    }
-}
+pub mod parser;
 
 #[derive(Debug)]
 struct Edge {
@@ -59,10 +19,10 @@ pub struct EdgeDesc {
 
 fn generate_edges(edge_descs: &Vec<EdgeDesc>) -> Vec<Edge> {
     let mut edges = Vec::new();
-    let mut start_coord = Coord::from((0, 0));
+    let mut start_coord = Coord::new(0, 0);
 
     for desc in edge_descs {
-        let end_coord = start_coord.shift(desc.direction, desc.length);
+        let end_coord = start_coord.step_by(desc.direction, desc.length);
         edges.push(Edge {
             start: start_coord,
             end: end_coord,
@@ -103,29 +63,48 @@ fn compute_polygon_area(edges: &Vec<Edge>, perimeter: i64) -> i64 {
     }
 
     let correction = 3 + (perimeter - 4) / 2;
-    let area = area.abs() / 2 + correction;
-    area
+    area.abs() / 2 + correction
 }
 
 /// Compute the area of the polygon defined by the dug edges.
 pub fn solve(edge_descs: &Vec<EdgeDesc>) -> i64 {
     let edges = generate_edges(edge_descs);
     let perimeter = edge_descs.iter().map(|desc| desc.length).sum();
-    compute_polygon_area(&edges, perimeter) as i64
+    compute_polygon_area(&edges, perimeter)
 }
 
 /// Solve using the first set of instructions (direction and length).
-pub fn solve_part1(edge_descs: &Vec<(EdgeDesc, EdgeDesc)>) -> i64 {
+pub fn solve_part1(edge_descs: &[(EdgeDesc, EdgeDesc)]) -> i64 {
     let descs = edge_descs.iter().cloned().map(|(desc, _)| desc).collect();
     solve(&descs)
 }
 
 /// Solve using the second set of instructions (direction and length), decoded from color codes.
-pub fn solve_part2(edge_descs: &Vec<(EdgeDesc, EdgeDesc)>) -> i64 {
+pub fn solve_part2(edge_descs: &[(EdgeDesc, EdgeDesc)]) -> i64 {
     let descs = edge_descs.iter().cloned().map(|(_, desc)| desc).collect();
     solve(&descs)
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<(EdgeDesc, EdgeDesc)>;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,7 +129,7 @@ mod tests {
             U 2 (#7a21e3)
         "};
 
-        let edges = parser::parse_input(&input).unwrap();
+        let edges = parser::parse_input(input).unwrap();
         let result = solve_part1(&edges);
         assert_eq!(result, 62);
         let result = solve_part2(&edges);
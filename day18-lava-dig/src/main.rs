@@ -1,9 +1,10 @@
+use common::InputSource;
 use day18_lava_dig::{parser::parse_input, solve_part1, solve_part2};
-use std::{fs, path::Path};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
     let edges = parse_input(&input).expect("Invalid input");
 
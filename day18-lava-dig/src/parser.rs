@@ -1,15 +1,24 @@
-use crate::{Direction, EdgeDesc};
-use itertools::Itertools;
+use crate::EdgeDesc;
+use common::parse::{one_of, parse_lines, tag, IResult};
+use common::ParseError;
+use grid::Direction;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{anychar, i64, one_of, space1},
-    multi::count,
+    bytes::complete::take_while_m_n,
+    character::complete::{i64, space1},
+    combinator::map_res,
+    error::context,
     sequence::{delimited, pair, tuple},
-    IResult,
 };
 
 fn parse_color_code(input: &str) -> IResult<&str, EdgeDesc> {
-    let (remainder, (length, direction)) = pair(count(anychar, 5), one_of("0123"))(input)?;
+    let hex_length = context(
+        "a 5-digit hex number",
+        map_res(
+            take_while_m_n(5, 5, |ch: char| ch.is_ascii_hexdigit()),
+            |hex| u32::from_str_radix(hex, 16),
+        ),
+    );
+    let (remainder, (length, direction)) = pair(hex_length, one_of("0123"))(input)?;
 
     let direction = match direction {
         '0' => Direction::E,
@@ -18,9 +27,6 @@ fn parse_color_code(input: &str) -> IResult<&str, EdgeDesc> {
         '3' => Direction::N,
         _ => panic!("Unrecognized direction"),
     };
-    let length =
-        u32::from_str_radix(String::from_iter(length).as_str(), 16).expect("Invalid hex number");
-
     let edge = EdgeDesc {
         direction,
         length: length as i64,
@@ -51,13 +57,6 @@ fn parse_edge(input: &str) -> IResult<&str, (EdgeDesc, EdgeDesc)> {
     Ok((remainder, (edge_desc_1, edge_desc_2)))
 }
 
-pub fn parse_input(
-    input: &str,
-) -> Result<Vec<(EdgeDesc, EdgeDesc)>, Box<dyn std::error::Error + '_>> {
-    let edges = input
-        .lines()
-        .map(|line| parse_edge(line).map(|(_, edge_pair)| edge_pair))
-        .try_collect()?;
-
-    Ok(edges)
+pub fn parse_input(input: &str) -> Result<Vec<(EdgeDesc, EdgeDesc)>, ParseError> {
+    parse_lines(input, parse_edge)
 }
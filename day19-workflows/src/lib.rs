@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/19
 
+use common::{ParseError, Solution};
 use std::collections::HashMap;
 use std::ops::Range;
 
@@ -40,8 +41,8 @@ pub struct GenericPart {
     pub s: Range<u32>,
 }
 
-impl GenericPart {
-    pub fn default() -> Self {
+impl Default for GenericPart {
+    fn default() -> Self {
         GenericPart {
             x: 1..4001,
             m: 1..4001,
@@ -49,7 +50,9 @@ impl GenericPart {
             s: 1..4001,
         }
     }
+}
 
+impl GenericPart {
     pub fn split_greater(mut self, category: &PartCategory, threshold: u32) -> (Self, Self) {
         let mut split = self.clone();
 
@@ -146,7 +149,7 @@ impl State {
         for rule in &workflow.rules {
             match rule {
                 Rule::ForwardUnconditionally { name } => {
-                    return self.process(part, &name);
+                    return self.process(part, name);
                 },
                 Rule::ForwardIfGreater {
                     category,
@@ -154,7 +157,7 @@ impl State {
                     name,
                 } => {
                     if part.value_of(category) > *threshold {
-                        return self.process(part, &name);
+                        return self.process(part, name);
                     }
                 },
                 Rule::ForwardIfLess {
@@ -163,7 +166,7 @@ impl State {
                     name,
                 } => {
                     if part.value_of(category) < *threshold {
-                        return self.process(part, &name);
+                        return self.process(part, name);
                     }
                 },
             }
@@ -200,7 +203,7 @@ impl State {
         for rule in &workflow.rules {
             match rule {
                 Rule::ForwardUnconditionally { name } => {
-                    self.process_generic_part(part, &name, accepted);
+                    self.process_generic_part(part, name, accepted);
                     return;
                 },
                 Rule::ForwardIfGreater {
@@ -209,7 +212,7 @@ impl State {
                     name,
                 } => {
                     let (remainder, split) = part.split_greater(category, *threshold);
-                    self.process_generic_part(split, &name, accepted);
+                    self.process_generic_part(split, name, accepted);
                     part = remainder;
                 },
                 Rule::ForwardIfLess {
@@ -218,7 +221,7 @@ impl State {
                     name,
                 } => {
                     let (remainder, split) = part.split_less(category, *threshold);
-                    self.process_generic_part(split, &name, accepted);
+                    self.process_generic_part(split, name, accepted);
                     part = remainder;
                 },
             }
@@ -252,6 +255,26 @@ pub fn solve_part2(state: &State) -> u64 {
     combinations
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = State;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,7 +302,7 @@ mod tests {
             {x=2127,m=1623,a=2188,s=1013}
         "};
 
-        let state = parser::parse_input(&input).unwrap();
+        let state = parser::parse_input(input).unwrap();
         let result = solve_part1(&state);
         assert_eq!(result, 19114);
         let result = solve_part2(&state);
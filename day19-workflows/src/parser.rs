@@ -1,11 +1,11 @@
 use crate::{Part, PartCategory, Rule, State, Workflow};
+use common::parse::{one_of, parse_all, tag, IResult};
+use common::ParseError;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{alpha1, char, line_ending, multispace1, one_of, u32},
+    character::complete::{alpha1, char, line_ending, multispace1, u32},
     multi::separated_list1,
     sequence::{delimited, pair, preceded, separated_pair, tuple},
-    IResult,
 };
 
 // Values x, m, a, s are always given in the same order in the input.
@@ -88,12 +88,15 @@ fn parse_workflow(input: &str) -> IResult<&str, Workflow> {
     Ok((remainder, workflow))
 }
 
-pub fn parse_input(input: &str) -> Result<State, Box<dyn std::error::Error + '_>> {
-    let (_, (workflows, parts)) = separated_pair(
-        separated_list1(line_ending, parse_workflow),
-        multispace1,
-        separated_list1(line_ending, parse_part),
-    )(input)?;
+pub fn parse_input(input: &str) -> Result<State, ParseError> {
+    let (workflows, parts) = parse_all(
+        input,
+        separated_pair(
+            separated_list1(line_ending, parse_workflow),
+            multispace1,
+            separated_list1(line_ending, parse_part),
+        ),
+    )?;
 
     let workflows = workflows
         .into_iter()
@@ -1,10 +1,13 @@
 //! https://adventofcode.com/2023/day/20
 
+use common::{ParseError, Simulation, Snapshot, Solution};
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::{cell::RefCell, rc::Rc};
 
 pub mod parser;
+#[cfg(feature = "viz")]
+mod viz;
 
 #[derive(Clone)]
 pub struct Configuration {
@@ -235,19 +238,169 @@ impl Runner {
         }
     }
 
+    /// Deliver the first pulse in the queue to its receiver. Return `false` if the queue is empty.
+    fn deliver_next(&mut self, cfg: &mut Configuration) -> bool {
+        let Some(msg) = self.fifo.pop_front() else {
+            return false;
+        };
+
+        if let Some(recv_module) = cfg.modules.get_mut(&msg.receiver) {
+            match recv_module {
+                Module::Broadcast(module) => self.process_broadcast(module, msg),
+                Module::FlipFlop(module) => self.process_flip_flop(module, msg),
+                Module::Conjunction(module) => self.process_conjunction(module, msg),
+            }
+        }
+        true
+    }
+
     pub fn run_logic(&mut self, cfg: &mut Configuration) {
         self.push_button();
+        while self.deliver_next(cfg) {}
+    }
+}
 
-        while let Some(msg) = self.fifo.pop_front() {
-            if let Some(recv_module) = cfg.modules.get_mut(&msg.receiver) {
-                match recv_module {
-                    Module::Broadcast(module) => self.process_broadcast(module, msg),
-                    Module::FlipFlop(module) => self.process_flip_flop(module, msg),
-                    Module::Conjunction(module) => self.process_conjunction(module, msg),
-                }
-            }
+/// Pulses going through the modules, delivering one pulse per step. When all pulses have been
+/// delivered, the next step pushes the button, so the simulation never ends.
+pub struct Pulses {
+    cfg: Configuration,
+    runner: Runner,
+    counts: Rc<RefCell<CountTracker>>,
+    presses: u32,
+    last: String,
+    last_receiver: Option<String>,
+}
+
+impl Pulses {
+    pub fn new(cfg: &Configuration) -> Pulses {
+        let counts = Rc::new(RefCell::new(CountTracker::new()));
+        Pulses {
+            cfg: cfg.clone(),
+            runner: Runner::new(counts.clone() as Rc<RefCell<dyn Tracker>>),
+            counts,
+            presses: 0,
+            last: "-".to_string(),
+            last_receiver: None,
+        }
+    }
+}
+
+impl Simulation for Pulses {
+    type Snapshot = PulseState;
+
+    fn snapshot(&self) -> PulseState {
+        let mut modules: Vec<ModuleState> = self
+            .cfg
+            .modules
+            .values()
+            .map(|module| match module {
+                Module::Broadcast(module) => ModuleState {
+                    name: module.name.clone(),
+                    kind: ' ',
+                    state: String::new(),
+                    outputs: module.outputs.clone(),
+                },
+                Module::FlipFlop(module) => ModuleState {
+                    name: module.name.clone(),
+                    kind: '%',
+                    state: match module.state {
+                        FlipFlopState::On => "on".to_string(),
+                        FlipFlopState::Off => "off".to_string(),
+                    },
+                    outputs: module.outputs.clone(),
+                },
+                Module::Conjunction(module) => {
+                    let inputs = module.inputs.values();
+                    let high = inputs.filter(|&&pulse| pulse == PulseType::High).count();
+                    ModuleState {
+                        name: module.name.clone(),
+                        kind: '&',
+                        state: format!("{high}/{}", module.inputs.len()),
+                        outputs: module.outputs.clone(),
+                    }
+                },
+            })
+            .collect();
+        modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let (low_count, high_count) = self.counts.borrow().counts();
+        PulseState {
+            presses: self.presses,
+            low_count,
+            high_count,
+            last: self.last.clone(),
+            last_receiver: self.last_receiver.clone(),
+            queue: self
+                .runner
+                .fifo
+                .iter()
+                .map(|msg| format!("{msg:?}"))
+                .collect(),
+            modules,
         }
     }
+
+    fn step(&mut self) -> bool {
+        match self.runner.fifo.front() {
+            Some(msg) => {
+                self.last = format!("{msg:?}");
+                self.last_receiver = Some(msg.receiver.clone());
+                self.runner.deliver_next(&mut self.cfg);
+            },
+            None => {
+                self.runner.push_button();
+                self.presses += 1;
+                self.last = format!("press {}", self.presses);
+                self.last_receiver = None;
+            },
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleState {
+    pub name: String,
+    /// '%' for flip-flops, '&' for conjunctions and ' ' for the broadcaster
+    pub kind: char,
+    /// "on" or "off" for flip-flops, and the number of inputs remembered as high out of all inputs
+    /// for conjunctions, e.g. "1/4"
+    pub state: String,
+    pub outputs: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PulseState {
+    pub presses: u32,
+    pub low_count: u32,
+    pub high_count: u32,
+    /// The last step, either a delivered pulse or a push of the button
+    pub last: String,
+    /// Module to which the last pulse was delivered
+    pub last_receiver: Option<String>,
+    /// Pulses waiting to be delivered, first to last
+    pub queue: Vec<String>,
+    /// Modules sorted by name
+    pub modules: Vec<ModuleState>,
+}
+
+impl Snapshot for PulseState {
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = vec![
+            ("presses".to_string(), self.presses.to_string()),
+            ("low".to_string(), self.low_count.to_string()),
+            ("high".to_string(), self.high_count.to_string()),
+            ("queued".to_string(), self.queue.len().to_string()),
+            ("last".to_string(), self.last.clone()),
+        ];
+        fields.extend(
+            self.modules
+                .iter()
+                .filter(|module| module.kind != ' ')
+                .map(|module| (module.name.clone(), module.state.clone())),
+        );
+        fields
+    }
 }
 
 /// Execute the module logic 1000 times. Count the high and low pulses and return their product.
@@ -308,6 +461,26 @@ pub fn solve_part2(cfg: &Configuration) -> u64 {
     lcm(lcm(lcm(res1, res2), res3), res4)
 }
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = Configuration;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,7 +496,7 @@ mod tests {
             &inv -> a
         "};
 
-        let cfg = parser::parse_input(&input).unwrap();
+        let cfg = parser::parse_input(input).unwrap();
         let result = solve_part1(&cfg);
         assert_eq!(result, 32000000);
     }
@@ -338,8 +511,50 @@ mod tests {
             &con -> output
         "};
 
-        let cfg = parser::parse_input(&input).unwrap();
+        let cfg = parser::parse_input(input).unwrap();
         let result = solve_part1(&cfg);
         assert_eq!(result, 11687500);
     }
+
+    #[test]
+    fn pulses() {
+        let input = indoc! {"
+            broadcaster -> a, b, c
+            %a -> b
+            %b -> c
+            %c -> inv
+            &inv -> a
+        "};
+        let cfg = parser::parse_input(input).unwrap();
+        let mut pulses = Pulses::new(&cfg);
+
+        // The first press sends 8 low and 4 high pulses, the first one from the button
+        for _ in 0..12 {
+            assert!(pulses.step());
+        }
+        let state = pulses.snapshot();
+        assert_eq!(
+            (state.presses, state.low_count, state.high_count),
+            (1, 8, 4)
+        );
+        assert_eq!(state.last, "c --low--> inv");
+        assert_eq!(state.queue, ["inv --high--> a"]);
+
+        pulses.step();
+        let fields = pulses.snapshot().fields();
+        assert_eq!(fields[0], ("presses".to_string(), "1".to_string()));
+        assert_eq!(fields[3], ("queued".to_string(), "0".to_string()));
+        assert_eq!(
+            &fields[5..],
+            [
+                ("a".to_string(), "off".to_string()),
+                ("b".to_string(), "off".to_string()),
+                ("c".to_string(), "off".to_string()),
+                ("inv".to_string(), "0/1".to_string()),
+            ]
+        );
+
+        pulses.step();
+        assert_eq!(pulses.snapshot().presses, 2);
+    }
 }
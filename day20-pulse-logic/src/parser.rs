@@ -1,11 +1,11 @@
 use crate::{BroadcastModule, Configuration, ConjunctionModule, FlipFlopModule, Module};
+use common::parse::{one_of, parse_all, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alpha1, multispace1, one_of},
+    character::complete::{alpha1, multispace1},
     combinator::opt,
     multi::separated_list1,
     sequence::{pair, separated_pair},
-    IResult,
 };
 use std::collections::HashMap;
 
@@ -28,10 +28,7 @@ fn module_inputs<'a>(descs: &Vec<ModuleDesc<'a>>) -> HashMap<&'a str, Vec<&'a st
 
     for desc in descs {
         for output in &desc.outputs {
-            inputs_by_module
-                .entry(output)
-                .or_insert_with(|| Vec::new())
-                .push(desc.name);
+            inputs_by_module.entry(output).or_default().push(desc.name);
         }
     }
     inputs_by_module
@@ -54,7 +51,13 @@ fn create_modules<'a>(descs: Vec<ModuleDesc<'a>>) -> HashMap<String, Module> {
                 Module::FlipFlop(FlipFlopModule::new(name, outputs))
             },
             ModuleKind::Conjunction => {
-                let inputs = inputs_by_module[desc.name].iter().cloned().map(String::from).collect();
+                let inputs = inputs_by_module
+                    .get(desc.name)
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .map(String::from)
+                    .collect();
                 Module::Conjunction(ConjunctionModule::new(name, outputs, inputs))
             },
         };
@@ -85,8 +88,8 @@ fn parse_module<'a>(input: &'a str) -> IResult<&'a str, ModuleDesc<'a>> {
     Ok((remainder, module_desc))
 }
 
-pub fn parse_input(input: &str) -> Result<Configuration, Box<dyn std::error::Error + '_>> {
-    let (_, module_descs) = separated_list1(multispace1, parse_module)(input)?;
+pub fn parse_input(input: &str) -> Result<Configuration, ParseError> {
+    let module_descs = parse_all(input, separated_list1(multispace1, parse_module))?;
 
     Ok(Configuration {
         modules: create_modules(module_descs),
@@ -0,0 +1,64 @@
+//! Picture of the modules and the pulses waiting to be delivered.
+
+use crate::PulseState;
+use grid::Grid;
+use render::{Canvas, Cell, Color, Render};
+
+/// A line of text in the colour.
+fn line(text: &str, color: Option<Color>) -> Vec<Cell> {
+    text.chars()
+        .map(|symbol| Cell {
+            symbol,
+            fg: color,
+            bg: None,
+        })
+        .collect()
+}
+
+/// One line per module with its state and outputs, followed by the queue of pulses. Modules that
+/// are on, or remember only high inputs, are green, and the module which received the last pulse
+/// is red.
+impl Render for PulseState {
+    fn render(&self) -> Canvas {
+        let name_width = self
+            .modules
+            .iter()
+            .map(|module| module.name.len() + 1)
+            .max()
+            .unwrap_or(0);
+        let state_width = self
+            .modules
+            .iter()
+            .map(|module| module.state.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = Vec::new();
+        for module in &self.modules {
+            let name = format!("{}{}", module.kind, module.name);
+            let mut cells = line(&format!("{name:name_width$}"), None);
+            if self.last_receiver.as_ref() == Some(&module.name) {
+                cells.iter_mut().for_each(|cell| cell.fg = Some(Color::Red));
+            }
+
+            let active = match module.state.split_once('/') {
+                Some((high, inputs)) => high == inputs,
+                None => module.state == "on",
+            };
+            let state = format!(" {:state_width$} ", module.state);
+            cells.extend(line(&state, active.then_some(Color::Green)));
+            cells.extend(line(&format!("-> {}", module.outputs.join(", ")), None));
+            lines.push(cells);
+        }
+
+        lines.push(Vec::new());
+        lines.push(line(&format!("Queue ({} pulses):", self.queue.len()), None));
+        lines.extend(self.queue.iter().map(|msg| line(msg, None)));
+
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        Grid::from_rows(lines.into_iter().map(|mut cells| {
+            cells.resize(width, Cell::new(' '));
+            cells
+        }))
+    }
+}
@@ -21,6 +21,7 @@
 //! of the corresponding plots in the 3x3 grid of layouts (plots that have the same coordinate
 //! within a layout). The layout is of size 17.
 //!
+//! ```text
 //!          +----------+-----------+----------+
 //!          |  NW: 41  |   N: 24   |  NE: 35  |
 //!          +----------+-----------+----------+
@@ -28,7 +29,8 @@
 //!          +----------+-----------+----------+
 //!          |  SW: 33  |   S: 16   |  SE: 27  |
 //!          +----------+-----------+----------+
-//!  
+//! ```
+//!
 //! We obviously have to compute the distance of the center plot using BFS. Due to the fact that
 //! the plot will be closer to one corner of the layout that the others, we also have to compute
 //! the distance from the plots in layouts in the opposite corner using BFS. For example, if the
@@ -52,6 +54,7 @@
 //!
 //! Let's add the remaining 4 layouts of distance 2 from the Center layout:
 //!
+//! ```text
 //!                       +-----------+
 //!                       |  NN: 41   |
 //!            +----------+-----------+----------+
@@ -63,6 +66,7 @@
 //!            +----------+-----------+----------+
 //!                       |  SS: 33   |
 //!                       +-----------+
+//! ```
 //!
 //! Here we can see how the layouts expand in circles. The first circle is the Center itself. The
 //! second circle is the 4 layouts of distance 1 from the Center: N, E, S, W. Circle 2 has the edge
@@ -73,155 +77,103 @@
 //! simply by incrementing the min neighbor by layout size.
 //!
 
-use std::collections::{HashMap, HashSet, VecDeque};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    pub fn north(&self) -> Coord {
-        Coord {
-            x: self.x,
-            y: self.y - 1,
-        }
-    }
-
-    pub fn south(&self) -> Coord {
-        Coord {
-            x: self.x,
-            y: self.y + 1,
-        }
-    }
-
-    pub fn east(&self) -> Coord {
-        Coord {
-            x: self.x + 1,
-            y: self.y,
-        }
-    }
-
-    pub fn west(&self) -> Coord {
-        Coord {
-            x: self.x - 1,
-            y: self.y,
-        }
-    }
-}
+use common::{ParseError, Solution};
+use grid::{Coord, Grid};
+use std::collections::VecDeque;
 
-impl From<(i32, i32)> for Coord {
-    fn from((x, y): (i32, i32)) -> Coord {
-        Coord { x, y }
-    }
-}
+/// Distance of the plots which cannot be reached from the start, including rocks.
+const UNREACHABLE: u32 = u32::MAX;
 
 #[derive(Debug, Clone)]
 pub struct Layout {
-    plots: HashSet<Coord>,
+    /// Garden plots are `true`, rocks are `false`.
+    plots: Grid<bool>,
     start_coord: Coord,
-    size: i32,
+    size: i64,
 }
 
 impl Layout {
-    pub fn load_from_input(input: &str) -> Layout {
-        let mut plots: HashSet<Coord> = HashSet::new();
+    pub fn load_from_input(input: &str) -> Result<Layout, ParseError> {
         let mut start_coord: Option<Coord> = None;
 
-        let size = {
-            let size_y = input.lines().count();
-            let size_x = input.lines().next().unwrap().chars().count();
-            assert!(
-                size_x == size_y,
-                "Assertion failed: Input is not a square grid"
-            );
+        let plots = Grid::parse(input, |coord, ch| match ch {
+            '.' => Ok(true),
+            '#' => Ok(false),
+            'S' if start_coord.is_none() => {
+                start_coord = Some(coord);
+                Ok(true)
+            },
+            'S' => Err("a single start tile"),
+            _ => Err("one of \".#S\""),
+        })?;
+
+        if plots.width() != plots.height() {
+            let expected = format!("{} rows, to form a square grid", plots.width());
+            return Err(ParseError::at_offset(input, input.len(), expected));
+        }
 
-            size_x as i32
+        let Some(start_coord) = start_coord else {
+            return Err(ParseError::at_offset(input, input.len(), "a start tile"));
         };
 
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                // Skip rocks
-                if ch == '#' {
-                    continue;
-                }
-
-                let coord = Coord::from((i as i32, j as i32));
-
-                if ch == 'S' {
-                    start_coord = Some(coord);
-                }
-
-                plots.insert(coord);
-            }
-        }
-
-        Layout {
+        Ok(Layout {
+            size: plots.width() as i64,
             plots,
-            start_coord: start_coord.expect("Missing start coordinate"),
-            size,
-        }
+            start_coord,
+        })
     }
 
-    /// For a given coordinate, return it along with 8 copies of itself shifted by the layout size
-    /// to each of the 8 directions around it.
-    #[rustfmt::skip]
+    /// Return the 9 coordinates of the 3x3 expanded layout corresponding to the coordinate of the
+    /// initial layout, one in each of the 9 copies of the layout.
     fn expand_coord(&self, coord: Coord) -> [Coord; 9] {
         let Coord { x, y } = coord;
+        let (near, far) = (self.size, 2 * self.size);
 
         [
-            Coord::from((x - self.size, y - self.size)),
-            Coord::from((x,             y - self.size)),
-            Coord::from((x + self.size, y - self.size)),
-            Coord::from((x - self.size, y)),
-            Coord::from((x,             y)),
-            Coord::from((x + self.size, y)),
-            Coord::from((x - self.size, y + self.size)),
-            Coord::from((x,             y + self.size)),
-            Coord::from((x + self.size, y + self.size)),
+            Coord::new(x, y),
+            Coord::new(x + near, y),
+            Coord::new(x + far, y),
+            Coord::new(x, y + near),
+            Coord::new(x + near, y + near),
+            Coord::new(x + far, y + near),
+            Coord::new(x, y + far),
+            Coord::new(x + near, y + far),
+            Coord::new(x + far, y + far),
         ]
     }
 
+    /// Surround the layout with 8 copies of itself, one in each direction, keeping the start in
+    /// the central copy.
     fn expand_3x3(&self) -> Layout {
-        // For each coordinate, add 8 more, one in each direction
-        let coords_iter = self
-            .plots
-            .iter()
-            .flat_map(|coord| self.expand_coord(*coord));
-
         Layout {
-            plots: HashSet::from_iter(coords_iter),
-            start_coord: self.start_coord,
+            plots: self.plots.tiled(3, 3),
+            start_coord: self.start_coord + Coord::new(self.size, self.size),
             size: self.size,
         }
     }
 
-    fn generate_neighbors(&self, coord: &Coord) -> Vec<Coord> {
-        [coord.north(), coord.south(), coord.east(), coord.west()]
-            .into_iter()
-            .filter(|coord| self.plots.contains(&coord))
-            .collect()
+    fn generate_neighbors(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.plots
+            .neighbours4(coord)
+            .filter(|&coord| self.plots[coord])
     }
 
-    /// Return the mapping { coordinate -> distance from the start coordinate } for each plot in
-    /// the layout.
-    fn bfs_distances(&self) -> HashMap<Coord, u32> {
-        let mut distances: HashMap<Coord, u32> = HashMap::new();
-        let mut queue: VecDeque<(Coord, u32)> = VecDeque::new();
-        let mut visited: HashSet<Coord> = HashSet::new();
+    /// Return the distance from the start coordinate for each plot in the layout.
+    fn bfs_distances(&self) -> Grid<u32> {
+        let mut distances = self.plots.map(|_| UNREACHABLE);
+        let mut queue: VecDeque<Coord> = VecDeque::new();
 
         // Initialize the algorithm with the start tile
-        queue.push_back((self.start_coord, 0));
-        visited.insert(self.start_coord);
+        queue.push_back(self.start_coord);
+        distances[self.start_coord] = 0;
 
-        while let Some((coord, distance)) = queue.pop_front() {
-            distances.insert(coord, distance);
+        while let Some(coord) = queue.pop_front() {
+            let distance = distances[coord];
 
-            for neighbor_coord in self.generate_neighbors(&coord) {
-                if !visited.contains(&neighbor_coord) {
-                    queue.push_back((neighbor_coord, distance + 1));
-                    visited.insert(neighbor_coord);
+            for neighbor_coord in self.generate_neighbors(coord) {
+                if distances[neighbor_coord] == UNREACHABLE {
+                    queue.push_back(neighbor_coord);
+                    distances[neighbor_coord] = distance + 1;
                 }
             }
         }
@@ -239,15 +191,15 @@ fn is_reachable(distance: u32, steps: u32) -> bool {
     if distance > steps {
         return false;
     }
-    (steps - distance) % 2 == 0
+    (steps - distance).is_multiple_of(2)
 }
 
 /// Return the number of plots reachable after N steps from the starting position.
 fn count_reachable(layout: &Layout, steps: u32) -> u32 {
     let mut count: u32 = 0;
 
-    for distance in layout.bfs_distances().into_values() {
-        if is_reachable(distance, steps) {
+    for (_, &distance) in layout.bfs_distances().iter() {
+        if distance != UNREACHABLE && is_reachable(distance, steps) {
             count += 1;
         }
     }
@@ -268,14 +220,14 @@ fn count_reachable_infinite(layout: &Layout, steps: u32) -> u64 {
     let layout_size = layout.size as u32;
     let mut count: u64 = 0;
 
-    for &coord in layout.plots.iter() {
+    for coord in layout.plots.coords() {
         // Distances for the 9 plots in the 3x3 expanded layout for the coord
         let [nw, n, ne, w, center, e, sw, s, se] = layout
             .expand_coord(coord)
-            .map(|coord| distances_by_coord.get(&coord).copied().unwrap_or(u32::MAX));
+            .map(|coord| distances_by_coord[coord]);
 
-        // Skip unreachable plots (i.e. those surrounded with rocks)
-        if center == u32::MAX {
+        // Skip rocks and unreachable plots (i.e. those surrounded with rocks)
+        if center == UNREACHABLE {
             continue;
         }
 
@@ -372,6 +324,26 @@ pub fn solve_part2(layout: &Layout) -> u64 {
     count_reachable_infinite(layout, 26501365)
 }
 
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    type Input = Layout;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Layout::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,7 +365,7 @@ mod tests {
             ...........
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         assert_eq!(count_reachable(&layout, 1), 2);
         assert_eq!(count_reachable(&layout, 2), 4);
         assert_eq!(count_reachable(&layout, 3), 6);
@@ -424,7 +396,7 @@ mod tests {
             .................
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         assert_eq!(count_reachable_infinite(&layout, 7), 52);
         assert_eq!(count_reachable_infinite(&layout, 8), 68);
         assert_eq!(count_reachable_infinite(&layout, 25), 576);
@@ -1,8 +1,11 @@
 //! https://adventofcode.com/2023/day/22
 
+use common::{ParseError, Simulation, Snapshot, Solution};
 use std::collections::{HashMap, HashSet};
 
 pub mod parser;
+#[cfg(feature = "viz")]
+mod viz;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Coord {
@@ -77,33 +80,105 @@ impl Brick {
     }
 }
 
-/// Move all bricks down as far as they can go and return this new set of bricks.
-fn land_bricks(bricks: &Vec<Brick>) -> Vec<Brick> {
-    let mut bricks_ordered_by_z = bricks.clone();
-    bricks_ordered_by_z.sort_unstable_by_key(|brick| brick.start.z);
-
+/// Bricks falling down as far as they can go, one brick per step, in the order of their lowest
+/// z-coordinate.
+pub struct Settling {
+    /// All bricks in the order in which they fall
+    bricks: Vec<Brick>,
+    /// Number of bricks which have already fallen
+    landed: usize,
+    /// How far the last brick fell
+    last_fall: u32,
     // Keep track of the highest currently occupied z-position for each xy-position. This
     // information lets us compute how far down we can push each succeeding brick.
-    let mut z_coord_by_xy_position: HashMap<(u32, u32), u32> = HashMap::new();
+    z_coord_by_xy_position: HashMap<(u32, u32), u32>,
+}
+
+impl Settling {
+    pub fn new(bricks: &[Brick]) -> Settling {
+        let mut bricks_ordered_by_z = bricks.to_vec();
+        bricks_ordered_by_z.sort_unstable_by_key(|brick| brick.start.z);
+
+        Settling {
+            bricks: bricks_ordered_by_z,
+            landed: 0,
+            last_fall: 0,
+            z_coord_by_xy_position: HashMap::new(),
+        }
+    }
+}
 
-    for brick in &mut bricks_ordered_by_z {
+impl Simulation for Settling {
+    type Snapshot = SettlingState;
+
+    fn snapshot(&self) -> SettlingState {
+        SettlingState {
+            bricks: self.bricks.clone(),
+            landed: self.landed,
+            last_fall: self.last_fall,
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(brick) = self.bricks.get_mut(self.landed) else {
+            return false;
+        };
         let xy_projection = brick.xy_projection();
 
         let highest_z_coord = xy_projection
             .iter()
-            .map(|xy_pos| z_coord_by_xy_position.get(xy_pos).copied().unwrap_or(0))
+            .map(|xy_pos| {
+                self.z_coord_by_xy_position
+                    .get(xy_pos)
+                    .copied()
+                    .unwrap_or(0)
+            })
             .max()
             .unwrap();
 
+        self.last_fall = brick.start.z - (highest_z_coord + 1);
         brick.move_downwards_to(highest_z_coord + 1);
 
         // Update highest z coords
         for xy_pos in xy_projection {
-            z_coord_by_xy_position.insert(xy_pos, brick.end.z);
+            self.z_coord_by_xy_position.insert(xy_pos, brick.end.z);
         }
+
+        self.landed += 1;
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SettlingState {
+    /// All bricks in the order in which they fall
+    pub bricks: Vec<Brick>,
+    /// Number of bricks which have already fallen, which are the first bricks
+    pub landed: usize,
+    /// How far the last brick fell
+    pub last_fall: u32,
+}
+
+impl Snapshot for SettlingState {
+    fn fields(&self) -> Vec<(String, String)> {
+        let height = self.bricks[..self.landed]
+            .iter()
+            .map(|brick| brick.end.z)
+            .max();
+        vec![
+            ("landed".to_string(), self.landed.to_string()),
+            ("bricks".to_string(), self.bricks.len().to_string()),
+            ("last_fall".to_string(), self.last_fall.to_string()),
+            ("height".to_string(), height.unwrap_or(0).to_string()),
+        ]
     }
+}
 
-    bricks_ordered_by_z
+/// Move all bricks down as far as they can go and return this new set of bricks.
+fn land_bricks(bricks: &[Brick]) -> Vec<Brick> {
+    let mut settling = Settling::new(bricks);
+    while settling.step() {}
+    settling.bricks
 }
 
 /// For a given brick, return how many other bricks it supports, i.e. how many bricks would fall
@@ -131,7 +206,7 @@ fn count_supported_recursive(
     if let Some(above) = bricks_above.get(&brick) {
         for above_brick in above {
             if let Some(below) = bricks_below.get(above_brick) {
-                if below.is_subset(&supported_bricks) {
+                if below.is_subset(supported_bricks) {
                     // Add the above brick to the count of supported bricks, plus the count of the
                     // bricks that it supports.
                     count += 1 + count_supported_recursive(
@@ -160,7 +235,7 @@ fn is_support_brick(
 /// Return the number of bricks that can be safely disintegrated. A brick can be safely
 /// disintegrated if it's not a support brick, i.e. if it is not the only brick that supports
 /// another brick.
-pub fn solve_part1(bricks: &Vec<Brick>) -> u32 {
+pub fn solve_part1(bricks: &[Brick]) -> u32 {
     let bricks = land_bricks(bricks);
 
     let mut bricks_above: HashMap<Brick, HashSet<Brick>> = HashMap::new();
@@ -184,7 +259,7 @@ pub fn solve_part1(bricks: &Vec<Brick>) -> u32 {
 
 /// For each brick determine how many other bricks would fall if it were disintegrated. Return the
 /// sum of these values.
-pub fn solve_part2(bricks: &Vec<Brick>) -> u32 {
+pub fn solve_part2(bricks: &[Brick]) -> u32 {
     let bricks = land_bricks(bricks);
 
     let mut bricks_above: HashMap<Brick, HashSet<Brick>> = HashMap::new();
@@ -206,6 +281,26 @@ pub fn solve_part2(bricks: &Vec<Brick>) -> u32 {
         .sum()
 }
 
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    type Input = Vec<Brick>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,10 +318,33 @@ mod tests {
             1,1,8~1,1,9
         "};
 
-        let bricks = parser::parse_input(&input).unwrap();
+        let bricks = parser::parse_input(input).unwrap();
         let result = solve_part1(&bricks);
         assert_eq!(result, 5);
         let result = solve_part2(&bricks);
         assert_eq!(result, 7);
     }
+
+    #[test]
+    fn settling() {
+        let input = indoc! {"
+            1,1,8~1,1,9
+            1,0,1~1,2,1
+            0,0,2~2,0,2
+        "};
+        let bricks = parser::parse_input(input).unwrap();
+        let mut settling = Settling::new(&bricks);
+
+        assert!(settling.step());
+        assert!(settling.step());
+        let state = settling.snapshot();
+        assert_eq!(state.landed, 2);
+        assert_eq!(state.bricks[1].start, Coord::from((0, 0, 2)));
+
+        assert!(settling.step());
+        assert!(!settling.step());
+        let fields = settling.snapshot().fields();
+        assert_eq!(fields[2], ("last_fall".to_string(), "6".to_string()));
+        assert_eq!(fields[3], ("height".to_string(), "3".to_string()));
+    }
 }
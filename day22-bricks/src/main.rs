@@ -1,11 +1,12 @@
-use day22_bricks::{parser::parse_input, solve_part1, solve_part2};
-use std::{fs, path::Path};
+use common::InputSource;
+use day22_bricks::{parser::parse_reader, solve_part1, solve_part2};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args().open().expect("Input missing");
 
-    let bricks = parse_input(&input).expect("Invalid input");
+    let bricks = parse_reader(input)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Invalid input");
 
     let result = solve_part1(&bricks);
     println!("Part one result: {result}");
@@ -1,11 +1,12 @@
 use crate::{Brick, Coord};
-use itertools::Itertools;
+use common::parse::{self, parse_lines, tag, IResult};
+use common::{ParseError, ReadError};
 use nom::{
-    bytes::complete::tag,
     character::complete::u32,
+    combinator::map_res,
     sequence::{separated_pair, tuple},
-    IResult,
 };
+use std::io::BufRead;
 
 fn parse_coord(input: &str) -> IResult<&str, Coord> {
     let (remainder, (x, _, y, _, z)) = tuple((u32, tag(","), u32, tag(","), u32))(input)?;
@@ -14,18 +15,19 @@ fn parse_coord(input: &str) -> IResult<&str, Coord> {
 }
 
 fn parse_brick(input: &str) -> IResult<&str, Brick> {
-    let (remainder, (start, end)) = separated_pair(parse_coord, tag("~"), parse_coord)(input)?;
-
-    let brick = Brick::try_new(start, end).unwrap();
-
-    Ok((remainder, brick))
+    map_res(
+        separated_pair(parse_coord, tag("~"), parse_coord),
+        |(start, end)| {
+            Brick::try_new(start, end).map_err(|reason| format!("a valid brick ({reason})"))
+        },
+    )(input)
 }
 
-pub fn parse_input(input: &str) -> Result<Vec<Brick>, Box<dyn std::error::Error + '_>> {
-    let bricks = input
-        .lines()
-        .map(|line| parse_brick(line).map(|(_, brick)| brick))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<Vec<Brick>, ParseError> {
+    parse_lines(input, parse_brick)
+}
 
-    Ok(bricks)
+/// Parse the bricks one at a time, while reading them.
+pub fn parse_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Brick, ReadError>> {
+    parse::parse_reader(reader, parse_brick)
 }
@@ -0,0 +1,62 @@
+//! Picture of the bricks from the front and from the side, like in the puzzle description.
+
+use crate::SettlingState;
+use grid::{Coord, Grid};
+use render::{Canvas, Cell, Color, Render};
+
+/// Number of columns between the two views.
+const GAP: usize = 3;
+
+/// The x-z view on the left and the y-z view on the right, with each brick labelled by a letter.
+/// Where bricks are behind each other, the one closest to the viewer is shown. Landed bricks are
+/// green, except the last one which is red.
+impl Render for SettlingState {
+    fn render(&self) -> Canvas {
+        let max = |axis: fn(&crate::Coord) -> u32| {
+            self.bricks
+                .iter()
+                .map(|brick| axis(&brick.end))
+                .max()
+                .unwrap_or(0) as usize
+        };
+        let (width_x, width_y, height) = (max(|c| c.x) + 1, max(|c| c.y) + 1, max(|c| c.z));
+
+        // One extra row for the ground
+        let mut canvas = Grid::filled(width_x + GAP + width_y, height + 1, Cell::new('.'));
+        let mut depths = Grid::filled(canvas.width(), canvas.height(), u32::MAX);
+
+        for x in width_x..width_x + GAP {
+            for y in 0..=height {
+                canvas[Coord::from((x, y))] = Cell::new(' ');
+            }
+        }
+        for x in (0..width_x).chain(width_x + GAP..canvas.width()) {
+            canvas[Coord::from((x, height))] = Cell::new('-');
+        }
+
+        for (index, brick) in self.bricks.iter().enumerate() {
+            let mut cell = Cell::new((b'A' + (index % 26) as u8) as char);
+            if index + 1 == self.landed {
+                cell = cell.fg(Color::Red);
+            } else if index < self.landed {
+                cell = cell.fg(Color::Green);
+            }
+
+            for (x, y) in brick.xy_projection() {
+                for z in brick.start.z..=brick.end.z {
+                    let row = height - z as usize;
+                    let views = [(x as usize, y), (width_x + GAP + y as usize, x)];
+
+                    for (column, depth) in views {
+                        let coord = Coord::from((column, row));
+                        if depth < depths[coord] {
+                            depths[coord] = depth;
+                            canvas[coord] = cell;
+                        }
+                    }
+                }
+            }
+        }
+        canvas
+    }
+}
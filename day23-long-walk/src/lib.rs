@@ -29,172 +29,103 @@
 //! The linear solution would work as such:
 //! - Create a topological ordering of vertices using a marking recursive DFS algorithm
 //! - For each vertex in the topological ordering, compute the longest path by taking the max of
-//! paths from the incoming neighbors
+//!   paths from the incoming neighbors
 //!
 //! The problem description: https://en.wikipedia.org/wiki/Longest_path_problem
 //! The linear time solution for DAGs: https://en.wikipedia.org/wiki/Longest_path_problem#Acyclic_graphs
 //! The marking DFS algorithm: https://en.wikipedia.org/wiki/Topological_sorting#Depth-first_search
 
+use common::{Ignore, Observer, ParseError, Solution};
+use grid::{Coord, Direction, Grid};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
-
-impl Coord {
-    pub fn north(&self) -> Coord {
-        Coord {
-            x: self.x,
-            y: self.y - 1,
-        }
-    }
+#[cfg(feature = "viz")]
+mod viz;
 
-    pub fn south(&self) -> Coord {
-        Coord {
-            x: self.x,
-            y: self.y + 1,
-        }
-    }
+#[cfg(feature = "viz")]
+pub use viz::LayoutPath;
 
-    pub fn east(&self) -> Coord {
-        Coord {
-            x: self.x + 1,
-            y: self.y,
-        }
-    }
-
-    pub fn west(&self) -> Coord {
-        Coord {
-            x: self.x - 1,
-            y: self.y,
-        }
-    }
-
-    pub fn neighbors(&self) -> [Coord; 4] {
-        [self.north(), self.east(), self.south(), self.west()]
-    }
-}
-
-impl From<(i32, i32)> for Coord {
-    fn from((x, y): (i32, i32)) -> Coord {
-        Coord { x, y }
-    }
-}
-
-impl fmt::Debug for Coord {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Forest,
+    Path,
+    /// Path tile which can only be left in the given direction.
+    Slope(Direction),
 }
 
 #[derive(Debug, Clone)]
 pub struct Layout {
-    path: HashSet<Coord>,
-    slope_n: HashSet<Coord>,
-    slope_s: HashSet<Coord>,
-    slope_e: HashSet<Coord>,
-    slope_w: HashSet<Coord>,
+    tiles: Grid<Tile>,
     start_coord: Coord,
     end_coord: Coord,
-    size: i32,
 }
 
 impl Layout {
-    pub fn load_from_input(input: &str) -> Layout {
-        let mut path: HashSet<Coord> = HashSet::new();
-        let mut slope_n: HashSet<Coord> = HashSet::new();
-        let mut slope_s: HashSet<Coord> = HashSet::new();
-        let mut slope_e: HashSet<Coord> = HashSet::new();
-        let mut slope_w: HashSet<Coord> = HashSet::new();
-        let mut start_coord: Option<Coord> = None;
-        let mut end_coord: Option<Coord> = None;
-
-        let size = {
-            let size_y = input.lines().count();
-            let size_x = input.lines().next().unwrap().chars().count();
-            assert!(
-                size_x == size_y,
-                "Assertion failed: Input is not a square grid"
-            );
-
-            size_x
-        };
-
-        for (j, line) in input.lines().enumerate() {
-            for (i, ch) in line.chars().enumerate() {
-                // Skip forest tiles
-                if ch == '#' {
-                    continue;
-                }
+    pub fn load_from_input(input: &str) -> Result<Layout, ParseError> {
+        let tiles = Grid::parse(input, |_, ch| match ch {
+            '#' => Ok(Tile::Forest),
+            '.' => Ok(Tile::Path),
+            '^' => Ok(Tile::Slope(Direction::N)),
+            'v' => Ok(Tile::Slope(Direction::S)),
+            '>' => Ok(Tile::Slope(Direction::E)),
+            '<' => Ok(Tile::Slope(Direction::W)),
+            _ => Err("one of \".#^v<>\""),
+        })?;
+
+        if tiles.width() != tiles.height() {
+            let expected = format!("{} rows, to form a square grid", tiles.width());
+            return Err(ParseError::at_offset(input, input.len(), expected));
+        }
 
-                let coord = Coord::from((i as i32, j as i32));
-                path.insert(coord);
-
-                if ch == '^' {
-                    slope_n.insert(coord);
-                } else if ch == 'v' {
-                    slope_s.insert(coord);
-                } else if ch == '>' {
-                    slope_e.insert(coord);
-                } else if ch == '<' {
-                    slope_w.insert(coord);
-                }
+        let last_row = tiles.height() - 1;
+        let Some(start_x) = tiles.row(0).iter().position(|&tile| tile != Tile::Forest) else {
+            return Err(ParseError::at_offset(
+                input,
+                0,
+                "a start tile in the first row",
+            ));
+        };
+        let Some(end_x) = tiles
+            .row(last_row)
+            .iter()
+            .position(|&tile| tile != Tile::Forest)
+        else {
+            return Err(ParseError::at_offset(
+                input,
+                input.len(),
+                "an end tile in the last row",
+            ));
+        };
 
-                if j == 0 {
-                    start_coord = Some(coord);
-                } else if j == size - 1 {
-                    end_coord = Some(coord);
-                }
-            }
-        }
+        Ok(Layout {
+            tiles,
+            start_coord: Coord::from((start_x, 0)),
+            end_coord: Coord::from((end_x, last_row)),
+        })
+    }
 
-        Layout {
-            path,
-            slope_n,
-            slope_s,
-            slope_e,
-            slope_w,
-            start_coord: start_coord.expect("Missing start coordinate"),
-            end_coord: end_coord.expect("Missing end coordinate"),
-            size: size as i32,
-        }
+    fn is_path(&self, coord: Coord) -> bool {
+        self.tiles
+            .get(coord)
+            .is_some_and(|&tile| tile != Tile::Forest)
     }
 
+    /// Edges leave a vertex through the slopes around it which point away from it.
     fn get_edge_starts(&self, vertex: Coord) -> Vec<Coord> {
-        let mut edge_starts = Vec::new();
-
-        let n = vertex.north();
-        if self.slope_n.contains(&n) {
-            edge_starts.push(n);
-        }
-
-        let e = vertex.east();
-        if self.slope_e.contains(&e) {
-            edge_starts.push(e);
-        }
-
-        let s = vertex.south();
-        if self.slope_s.contains(&s) {
-            edge_starts.push(s);
-        }
-
-        let w = vertex.west();
-        if self.slope_w.contains(&w) {
-            edge_starts.push(w);
-        }
-
-        edge_starts
+        Direction::ALL
+            .into_iter()
+            .map(|direction| (vertex.step(direction), direction))
+            .filter(|&(coord, direction)| self.tiles.get(coord) == Some(&Tile::Slope(direction)))
+            .map(|(coord, _)| coord)
+            .collect()
     }
 
     fn next_path_tile(&self, prev_path_tile: Coord, curr_path_tile: Coord) -> Vec<Coord> {
-        curr_path_tile
-            .neighbors()
-            .into_iter()
+        self.tiles
+            .neighbours4(curr_path_tile)
             .filter(|coord| *coord != prev_path_tile)
-            .filter(|coord| self.path.contains(coord))
+            .filter(|coord| self.is_path(*coord))
             .collect()
     }
 
@@ -233,37 +164,6 @@ impl Layout {
             }
         }
     }
-
-    fn print_path(&self, graph: &Graph, path: &Vec<Coord>) {
-        use colored::Colorize;
-
-        let mut path_tiles: HashSet<Coord> = HashSet::new();
-
-        for (u, v) in path.iter().zip(path.iter().skip(1)) {
-            path_tiles.extend(graph.get_path_segment(u, v));
-        }
-
-        for j in 0..self.size {
-            for i in 0..self.size {
-                let coord = Coord::from((i as i32, j as i32));
-
-                let symbol = if graph.vertices.contains(&coord) {
-                    "X"
-                } else if self.path.contains(&coord) {
-                    "."
-                } else {
-                    "#"
-                };
-
-                if path_tiles.contains(&coord) {
-                    print!("{}", symbol.on_blue());
-                } else {
-                    print!("{}", symbol);
-                }
-            }
-            println!();
-        }
-    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
@@ -285,25 +185,16 @@ impl Edge {
 
 impl fmt::Debug for Edge {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?} --> {:?}", self.from, self.to)
+        write!(f, "{} --> {}", self.from, self.to)
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 struct Path {
     vertices: Vec<Coord>,
     length: u32,
 }
 
-impl Default for Path {
-    fn default() -> Path {
-        Path {
-            vertices: Vec::new(),
-            length: 0,
-        }
-    }
-}
-
 impl Path {
     pub fn new(vertex: Coord) -> Path {
         Path {
@@ -368,7 +259,7 @@ impl Graph {
         }
     }
 
-    pub fn to_undirected(self) -> Graph {
+    pub fn into_undirected(self) -> Graph {
         let mut adj_list: HashMap<Coord, Vec<Edge>> = HashMap::new();
 
         for edge in self.adj_list.values().flatten() {
@@ -430,25 +321,95 @@ impl Graph {
     }
 }
 
+/// Intermediate results of the solution, reported to the observer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// The layout transformed into a graph. The vertices are the start, the end and the forks, and
+    /// each edge is given as `(from, to, length)`. In part 2, each edge is given in both
+    /// directions.
+    Graph {
+        vertices: HashSet<Coord>,
+        edges: Vec<(Coord, Coord, u32)>,
+    },
+    /// The longest path from the start to the end coordinate, as the vertices it goes through and
+    /// all the tiles it covers. Its length is the answer.
+    LongestPath {
+        vertices: Vec<Coord>,
+        tiles: HashSet<Coord>,
+        length: u32,
+    },
+}
+
+fn solve(layout: &Layout, graph: Graph, observer: &mut impl Observer<Event>) -> u32 {
+    observer.observe(Event::Graph {
+        vertices: graph.vertices.clone(),
+        edges: graph
+            .adj_list
+            .values()
+            .flatten()
+            .map(|edge| (edge.from, edge.to, edge.length))
+            .collect(),
+    });
+
+    let mut longest_paths = graph.longest_paths(layout.start_coord);
+    let path = longest_paths.remove(&layout.end_coord).unwrap();
+
+    let mut tiles: HashSet<Coord> = HashSet::new();
+    for (u, v) in path.vertices.iter().zip(path.vertices.iter().skip(1)) {
+        tiles.extend(graph.get_path_segment(u, v));
+    }
+
+    observer.observe(Event::LongestPath {
+        vertices: path.vertices,
+        tiles,
+        length: path.length,
+    });
+    path.length
+}
+
 /// Return the length of the longest path from the start coordinate to the end coordinate.
 pub fn solve_part1(layout: &Layout) -> u32 {
-    let graph = Graph::from_layout(layout);
-    let longest_paths = graph.longest_paths(layout.start_coord);
+    solve_part1_with(layout, &mut Ignore)
+}
 
-    let path = &longest_paths[&layout.end_coord];
-    layout.print_path(&graph, &path.vertices);
-    path.length
+/// Same as `solve_part1`, reporting the graph and the longest path to the observer.
+pub fn solve_part1_with(layout: &Layout, observer: &mut impl Observer<Event>) -> u32 {
+    solve(layout, Graph::from_layout(layout), observer)
 }
 
 /// Return the length of the longest path from the start coordinate to the end coordinate if the
 /// slopes don't matter (i.e. if the graph is undirected).
 pub fn solve_part2(layout: &Layout) -> u32 {
-    let graph = Graph::from_layout(layout).to_undirected();
-    let longest_paths = graph.longest_paths(layout.start_coord);
+    solve_part2_with(layout, &mut Ignore)
+}
 
-    let path = &longest_paths[&layout.end_coord];
-    layout.print_path(&graph, &path.vertices);
-    path.length
+/// Same as `solve_part2`, reporting the graph and the longest path to the observer.
+pub fn solve_part2_with(layout: &Layout, observer: &mut impl Observer<Event>) -> u32 {
+    solve(
+        layout,
+        Graph::from_layout(layout).into_undirected(),
+        observer,
+    )
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    type Input = Layout;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Layout::load_from_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -484,10 +445,63 @@ mod tests {
             #####################.#
         "};
 
-        let layout = Layout::load_from_input(&input);
+        let layout = Layout::load_from_input(input).unwrap();
         let result = solve_part1(&layout);
         assert_eq!(result, 94);
         let result = solve_part2(&layout);
         assert_eq!(result, 154);
     }
+
+    #[test]
+    fn test_observed_path() {
+        let input = indoc! {r"
+            #.#####################
+            #.......#########...###
+            #######.#########.#.###
+            ###.....#.>.>.###.#.###
+            ###v#####.#v#.###.#.###
+            ###.>...#.#.#.....#...#
+            ###v###.#.#.#########.#
+            ###...#.#.#.......#...#
+            #####.#.#.#######.#.###
+            #.....#.#.#.......#...#
+            #.#####.#.#.#########v#
+            #.#...#...#...###...>.#
+            #.#.#v#######v###.###v#
+            #...#.>.#...>.>.#.###.#
+            #####v#.#.###v#.#.###.#
+            #.....#...#...#.#.#...#
+            #.#########.###.#.#.###
+            #...###...#...#...#.###
+            ###.###.#.###v#####v###
+            #...#...#.#.>.>.#.>.###
+            #.###.###.#.###.#.#v###
+            #.....###...###...#...#
+            #####################.#
+        "};
+
+        let layout = Layout::load_from_input(input).unwrap();
+        let mut events = Vec::new();
+        let result = solve_part1_with(&layout, &mut events);
+
+        assert_eq!(events.len(), 2);
+        let Event::Graph { vertices, edges } = &events[0] else {
+            panic!("Expected the graph first");
+        };
+        let Event::LongestPath {
+            vertices: path,
+            tiles,
+            length,
+        } = &events[1]
+        else {
+            panic!("Expected the longest path last");
+        };
+        assert_eq!(vertices.len(), 9);
+        assert_eq!(edges.len(), 12);
+        assert_eq!(*length, result);
+        assert_eq!(path.first(), Some(&layout.start_coord));
+        assert_eq!(path.last(), Some(&layout.end_coord));
+        // The path takes 94 steps, so it covers 95 tiles including the start
+        assert_eq!(tiles.len(), 95);
+    }
 }
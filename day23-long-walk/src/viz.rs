@@ -0,0 +1,35 @@
+//! Picture of the longest hike through the forest.
+
+use crate::{Graph, Layout, Tile};
+use grid::Coord;
+use render::{Canvas, Cell, Color, Render};
+use std::collections::HashSet;
+
+/// The layout with a path through it marked in blue. Vertices of the graph are marked with 'X'.
+pub struct LayoutPath<'a> {
+    layout: &'a Layout,
+    path_tiles: &'a HashSet<Coord>,
+}
+
+impl<'a> LayoutPath<'a> {
+    /// Takes the tiles of the path, as reported in `Event::LongestPath`.
+    pub fn new(layout: &'a Layout, path_tiles: &'a HashSet<Coord>) -> LayoutPath<'a> {
+        LayoutPath { layout, path_tiles }
+    }
+}
+
+impl Render for LayoutPath<'_> {
+    fn render(&self) -> Canvas {
+        let mut canvas = self.layout.tiles.map(|&tile| match tile {
+            Tile::Forest => Cell::new('#'),
+            _ => Cell::new('.'),
+        });
+        for coord in Graph::from_layout(self.layout).vertices {
+            canvas[coord].symbol = 'X';
+        }
+        for &coord in self.path_tiles {
+            canvas[coord].bg = Some(Color::Blue);
+        }
+        canvas
+    }
+}
@@ -1,5 +1,6 @@
 //! https://adventofcode.com/2023/day/24
 
+use common::{ParseError, Solution};
 use itertools::Itertools;
 use num_bigint::BigInt;
 use num_traits::{cast::ToPrimitive, Zero};
@@ -153,8 +154,7 @@ fn paths_intersect_2d(h1: &Hailstone, h2: &Hailstone, min_coord: i64, max_coord:
         pi.x >= min_coord && pi.x <= max_coord && pi.y >= min_coord && pi.y <= max_coord;
 
     // Collision point is in the past if any hailstone is moving away from it
-    let in_the_past = false
-        || (h1.position.x > pi.x && h1.velocity.x > 0)
+    let in_the_past = (h1.position.x > pi.x && h1.velocity.x > 0)
         || (h1.position.x < pi.x && h1.velocity.x < 0)
         || (h1.position.y > pi.y && h1.velocity.y > 0)
         || (h1.position.y < pi.y && h1.velocity.y < 0)
@@ -313,6 +313,26 @@ pub fn solve_part2(configuration: &InputConfiguration) -> i64 {
     );
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    type Input = InputConfiguration;
+    type Output = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(solve_part2(input))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -329,7 +349,7 @@ mod tests {
             20, 19, 15 @ 1, -5, -3
         "};
 
-        let cfg = parser::parse_input(&input).unwrap();
+        let cfg = parser::parse_input(input).unwrap();
         let result = count_collisions(&cfg, 7, 27);
         assert_eq!(result, 2);
         let result = solve_part2(&cfg);
@@ -1,10 +1,9 @@
 use crate::{Coord3, Hailstone, InputConfiguration};
-use itertools::Itertools;
+use common::parse::{parse_lines, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
     character::complete::i64,
     sequence::{separated_pair, tuple},
-    IResult,
 };
 
 fn parse_coord(input: &str) -> IResult<&str, Coord3> {
@@ -22,11 +21,8 @@ fn parse_hailstone(input: &str) -> IResult<&str, Hailstone> {
     Ok((remainder, hailstone))
 }
 
-pub fn parse_input(input: &str) -> Result<InputConfiguration, Box<dyn std::error::Error + '_>> {
-    let hailstones = input
-        .lines()
-        .map(|line| parse_hailstone(line).map(|(_, hailstone)| hailstone))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<InputConfiguration, ParseError> {
+    let hailstones = parse_lines(input, parse_hailstone)?;
 
     Ok(InputConfiguration { hailstones })
 }
@@ -13,6 +13,7 @@
 use std::collections::HashMap;
 use std::fmt;
 
+use common::{Ignore, Observer, ParseError, Solution};
 use rand::{rngs::ThreadRng, Rng};
 
 pub mod parser;
@@ -126,24 +127,64 @@ fn kargers(graph: &mut Graph) -> (u32, u32) {
     (neighbors_a.len() as u32, result)
 }
 
+/// Outcome of a single run of Karger's algorithm, reported to the observer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attempt {
+    /// Number of the run, starting from 1.
+    pub iteration: u32,
+    /// Number of edges that define the cut that was found.
+    pub cut_length: u32,
+    /// Product of the sizes of the two subgraphs defined by the cut.
+    pub result: u32,
+}
+
 /// Partition the graph along the min-cut into two subgraphs. Min-cut has three edges. Return the
 /// product of the sizes of subgraphs.
 ///
 /// Run Karger's algorithm until it returns with a cut of size 3 (because we know that is the size
 /// of the min cut).
 pub fn solve_part1(graph: &Graph) -> u32 {
+    solve_part1_with(graph, &mut Ignore)
+}
+
+/// Same as `solve_part1`, reporting every run of Karger's algorithm to the observer.
+pub fn solve_part1_with(graph: &Graph, observer: &mut impl Observer<Attempt>) -> u32 {
     let mut iters = 0;
     loop {
         iters += 1;
         let (cut_length, result) = kargers(&mut graph.clone());
+        observer.observe(Attempt {
+            iteration: iters,
+            cut_length,
+            result,
+        });
 
         if cut_length == 3 {
-            println!("Finished in {} iterations of Karger's algorithm", iters);
             return result;
         }
     }
 }
 
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    type Input = Graph;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parser::parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        solve_part1(input)
+    }
+
+    fn part2(_input: &Self::Input) -> Option<Self::Output> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,8 +208,38 @@ mod tests {
             frs: qnr lhk lsr
         "};
 
-        let graph = parser::parse_input(&input).unwrap();
+        let graph = parser::parse_input(input).unwrap();
         let result = solve_part1(&graph);
         assert_eq!(result, 54);
     }
+
+    #[test]
+    fn test_observed_attempts() {
+        let input = indoc! {"
+            jqt: rhn xhk nvd
+            rsh: frs pzl lsr
+            xhk: hfx
+            cmg: qnr nvd lhk bvb
+            rhn: xhk bvb hfx
+            bvb: xhk hfx
+            pzl: lsr hfx nvd
+            qnr: nvd
+            ntq: jqt hfx bvb xhk
+            nvd: lhk
+            lsr: lhk
+            rzs: qnr cmg lsr rsh
+            frs: qnr lhk lsr
+        "};
+
+        let graph = parser::parse_input(input).unwrap();
+        let mut attempts: Vec<Attempt> = Vec::new();
+        let result = solve_part1_with(&graph, &mut attempts);
+
+        let last = attempts.last().unwrap();
+        assert_eq!(last.iteration as usize, attempts.len());
+        assert_eq!((last.cut_length, last.result), (3, result));
+        assert!(attempts[..attempts.len() - 1]
+            .iter()
+            .all(|attempt| attempt.cut_length > 3));
+    }
 }
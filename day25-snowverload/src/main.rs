@@ -1,12 +1,15 @@
-use day25_snowverload::{parser::parse_input, solve_part1};
-use std::{fs, path::Path};
+use common::InputSource;
+use day25_snowverload::{parser::parse_input, solve_part1_with, Attempt};
 
 fn main() {
-    let file_path = Path::new("./input");
-    let input = fs::read_to_string(file_path).expect("Input file missing");
+    let input = InputSource::from_args()
+        .read_to_string()
+        .expect("Input missing");
 
     let graph = parse_input(&input).expect("Invalid input");
 
-    let result = solve_part1(&graph);
+    let mut iters = 0;
+    let result = solve_part1_with(&graph, &mut |attempt: Attempt| iters = attempt.iteration);
+    println!("Finished in {} iterations of Karger's algorithm", iters);
     println!("Part one result: {result}");
 }
@@ -1,23 +1,23 @@
 use crate::{Component, Graph};
-use itertools::Itertools;
+use common::parse::{parse_lines, tag, IResult};
+use common::ParseError;
 use nom::{
-    bytes::complete::tag,
     character::complete::{alpha1, space1},
+    combinator::map_opt,
+    error::context,
     multi::separated_list1,
     sequence::separated_pair,
-    IResult,
 };
 
 fn parse_component(input: &str) -> IResult<&str, Component> {
-    let (remainder, ident) = alpha1(input)?;
+    let (remainder, ident) = context(
+        "a three-letter component name",
+        map_opt(alpha1, |ident: &str| {
+            ident.chars().collect::<Vec<_>>().try_into().ok()
+        }),
+    )(input)?;
 
-    let component = Component {
-        ident: ident
-            .chars()
-            .collect::<Vec<_>>()
-            .try_into()
-            .expect("Ident length must be 3"),
-    };
+    let component = Component { ident };
 
     Ok((remainder, component))
 }
@@ -32,11 +32,10 @@ fn parse_component_connections(input: &str) -> IResult<&str, (Component, Vec<Com
     Ok((remainder, (component, components)))
 }
 
-pub fn parse_input(input: &str) -> Result<Graph, Box<dyn std::error::Error + '_>> {
-    let adj_list = input
-        .lines()
-        .map(|line| parse_component_connections(line).map(|(_, item)| item))
-        .try_collect()?;
+pub fn parse_input(input: &str) -> Result<Graph, ParseError> {
+    let adj_list = parse_lines(input, parse_component_connections)?
+        .into_iter()
+        .collect();
 
     let graph = Graph { adj_list };
 
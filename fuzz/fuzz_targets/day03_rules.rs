@@ -0,0 +1,35 @@
+#![no_main]
+
+use day03_gear_ratios::rules::{Neighbourhood, Rules, Symbols};
+use grid::Coord;
+use libfuzzer_sys::fuzz_target;
+
+type RulesInput<'a> = (u8, Vec<(i8, i8)>, bool, &'a str, &'a str, u8);
+
+fuzz_target!(|input: (RulesInput, &str)| {
+    let ((neighbourhood, offsets, only, symbols, gear_symbols, gear_part_numbers), input) = input;
+    // Small reaches, since the neighbourhood is checked around every digit
+    let radius = (neighbourhood / 3 % 4) as u32;
+    let neighbourhood = match neighbourhood % 3 {
+        0 => Neighbourhood::Square(radius),
+        1 => Neighbourhood::Diamond(radius),
+        _ => Neighbourhood::Offsets(
+            offsets
+                .into_iter()
+                .take(16)
+                .map(|(x, y)| Coord::new(x as i64, y as i64))
+                .collect(),
+        ),
+    };
+    let symbols = match only {
+        true => Symbols::Only(symbols.to_string()),
+        false => Symbols::AllExcept(symbols.to_string()),
+    };
+    let rules = Rules {
+        neighbourhood,
+        symbols,
+        gear_symbols: gear_symbols.to_string(),
+        gear_part_numbers: gear_part_numbers as u32,
+    };
+    let _ = day03_gear_ratios::Solver::parse_with_rules(input, rules);
+});
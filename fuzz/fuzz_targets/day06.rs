@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day06_boat_race::parser::parse_input_part1(input);
+    let _ = day06_boat_race::parser::parse_input_part2(input);
+});
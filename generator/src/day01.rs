@@ -0,0 +1,54 @@
+//! Inputs for https://adventofcode.com/2023/day/1: lines of letters mixed with digits and spelled
+//! out digits. Every line has at least one real digit, which part 1 needs.
+
+use crate::{scale, LOWERCASE};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub lines: usize,
+    /// Max number of pieces in a line, each being a run of letters, a digit or a spelled out digit.
+    pub max_pieces: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            lines: scale(1000, percent, 1),
+            max_pieces: 8,
+        }
+    }
+}
+
+fn line(rng: &mut impl Rng, params: &Params) -> String {
+    let n_pieces = rng.gen_range(1..=params.max_pieces.max(1));
+    let digit_piece = rng.gen_range(0..n_pieces);
+
+    let mut line = String::new();
+    for piece in 0..n_pieces {
+        match rng.gen_range(0..3) {
+            _ if piece == digit_piece => line.push(rng.gen_range('1'..='9')),
+            0 => line.push(rng.gen_range('1'..='9')),
+            1 => line.push_str(WORDS.choose(rng).unwrap()),
+            _ => {
+                let len = rng.gen_range(1..=6);
+                line.extend((0..len).map(|_| *LOWERCASE.choose(rng).unwrap() as char));
+            },
+        }
+    }
+    line
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+    for _ in 0..params.lines {
+        output.push_str(&line(rng, params));
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,48 @@
+//! Inputs for https://adventofcode.com/2023/day/2: games with a few draws of red, green and blue
+//! cubes each.
+
+use crate::scale;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub games: usize,
+    pub max_draws: usize,
+    /// Max number of cubes of a single colour in a draw.
+    pub max_cubes: u32,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            games: scale(100, percent, 1),
+            max_draws: 6,
+            max_cubes: 20,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+
+    for id in 1..=params.games {
+        let n_draws = rng.gen_range(1..=params.max_draws.max(1));
+        let draws: Vec<String> = (0..n_draws)
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                let n_colors = rng.gen_range(1..=3);
+
+                let cubes: Vec<String> = colors[..n_colors]
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.gen_range(1..=params.max_cubes)))
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+
+        output.push_str(&format!("Game {id}: {}\n", draws.join("; ")));
+    }
+    output
+}
@@ -0,0 +1,58 @@
+//! Inputs for https://adventofcode.com/2023/day/3: a schematic of numbers of up to three digits
+//! and symbols. Numbers never touch each other horizontally, so they stay at most three digits
+//! long and the sum of the gear ratios fits into the answer.
+
+use crate::{grid_to_string, scale};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%-&";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the schematic.
+    pub size: usize,
+    /// Probability that a number starts on a tile.
+    pub number_density: f64,
+    /// Probability that a symbol is placed on a tile.
+    pub symbol_density: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(140, percent, 4),
+            number_density: 0.12,
+            symbol_density: 0.05,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut rows = vec![vec!['.'; params.size]; params.size];
+
+    for row in &mut rows {
+        let mut x = 0;
+        while x < params.size {
+            if rng.gen_bool(params.number_density) {
+                let len = rng.gen_range(1..=3).min(params.size - x);
+                let number = rng.gen_range(10usize.pow(len as u32 - 1)..10usize.pow(len as u32));
+                for (i, digit) in number.to_string().chars().enumerate() {
+                    row[x + i] = digit;
+                }
+                // Leave the tile after the number empty or put a symbol on it
+                x += len + 1;
+                if x - 1 < params.size && rng.gen_bool(params.symbol_density) {
+                    row[x - 1] = *SYMBOLS.choose(rng).unwrap() as char;
+                }
+            } else {
+                if rng.gen_bool(params.symbol_density) {
+                    row[x] = *SYMBOLS.choose(rng).unwrap() as char;
+                }
+                x += 1;
+            }
+        }
+    }
+
+    grid_to_string(&rows)
+}
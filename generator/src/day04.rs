@@ -0,0 +1,77 @@
+//! Inputs for https://adventofcode.com/2023/day/4: scratchcards with ten winning numbers and
+//! twenty-five numbers we have. Cards never win copies past the last card, and cards which
+//! already have many copies win nothing, so that the total number of cards stays bounded.
+
+use crate::scale;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+/// Cards with at least this many copies win no further copies.
+const MAX_COPIES: u64 = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub cards: usize,
+    pub winning_numbers: usize,
+    pub numbers: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            cards: scale(200, percent, 1),
+            winning_numbers: 10,
+            numbers: 25,
+        }
+    }
+}
+
+fn format_numbers(numbers: &[usize]) -> String {
+    let numbers: Vec<String> = numbers
+        .iter()
+        .map(|number| format!("{number:>2}"))
+        .collect();
+    numbers.join(" ")
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+    let mut copies = vec![1; params.cards];
+
+    for card in 0..params.cards {
+        // Draw all the numbers of the card at once, so that they are distinct
+        let total = params.winning_numbers + params.numbers;
+        let mut pool: Vec<usize> = index::sample(rng, 99, total)
+            .into_iter()
+            .map(|number| number + 1)
+            .collect();
+        let (winning, others) = pool.split_at_mut(params.winning_numbers);
+
+        let max_matches = params
+            .winning_numbers
+            .min(params.numbers)
+            .min(params.cards - card - 1);
+        let matches = match rng.gen_bool(0.5) {
+            _ if copies[card] >= MAX_COPIES => 0,
+            true => rng.gen_range(0..=max_matches),
+            false => 0,
+        };
+
+        let mut numbers = winning[..matches].to_vec();
+        numbers.extend(&others[..params.numbers - matches]);
+        numbers.shuffle(rng);
+        winning.shuffle(rng);
+
+        for next in card + 1..=card + matches {
+            copies[next] += copies[card];
+        }
+
+        output.push_str(&format!(
+            "Card {:>3}: {} | {}\n",
+            card + 1,
+            format_numbers(winning),
+            format_numbers(&numbers)
+        ));
+    }
+    output
+}
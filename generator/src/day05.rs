@@ -0,0 +1,85 @@
+//! Inputs for https://adventofcode.com/2023/day/5: seeds followed by the seven `X-to-Y map:`
+//! sections. The source ranges of each map never overlap, all ranges are non-empty and all numbers
+//! are below 2^32, like in the real input. There is an even number of seeds, so that they can be
+//! read as pairs of the start and length of a range in part 2.
+
+use crate::scale;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const MAX_NUMBER: i64 = 1 << 32;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of seed ranges, i.e. half the number of seeds.
+    pub seed_ranges: usize,
+    /// Max number of ranges in each map.
+    pub max_ranges: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            seed_ranges: scale(10, percent, 1),
+            max_ranges: scale(45, percent, 1),
+        }
+    }
+}
+
+/// Return the lines of a map, each as destination start, source start and length.
+fn map_ranges(rng: &mut impl Rng, n_ranges: usize) -> Vec<(i64, i64, i64)> {
+    // Cut the space of numbers into consecutive source ranges and skip some of them, so that there
+    // are gaps between the ranges of the map
+    let mut cuts: Vec<i64> = (0..2 * n_ranges)
+        .map(|_| rng.gen_range(0..MAX_NUMBER))
+        .collect();
+    cuts.push(0);
+    cuts.push(MAX_NUMBER);
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut ranges = Vec::new();
+    for window in cuts.windows(2) {
+        if ranges.len() < n_ranges && rng.gen_bool(0.5) {
+            let (src_start, len) = (window[0], window[1] - window[0]);
+            ranges.push((rng.gen_range(0..=MAX_NUMBER - len), src_start, len));
+        }
+    }
+
+    if ranges.is_empty() {
+        ranges.push((rng.gen_range(0..MAX_NUMBER), 0, 1));
+    }
+    ranges.shuffle(rng);
+    ranges
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let seeds: Vec<String> = (0..params.seed_ranges)
+        .flat_map(|_| {
+            let start = rng.gen_range(0..MAX_NUMBER - 1);
+            let len = rng.gen_range(1..=(MAX_NUMBER - start).min(MAX_NUMBER / 10));
+            [start.to_string(), len.to_string()]
+        })
+        .collect();
+    let mut output = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        output.push_str(&format!("\n{name} map:\n"));
+
+        let n_ranges = rng.gen_range(1..=params.max_ranges);
+        for (dst_start, src_start, len) in map_ranges(rng, n_ranges) {
+            output.push_str(&format!("{dst_start} {src_start} {len}\n"));
+        }
+    }
+    output
+}
@@ -0,0 +1,75 @@
+//! Inputs for https://adventofcode.com/2023/day/6: times and record distances of the races. Every
+//! record can be beaten, both in the separate races of part 1 and in the single race of part 2,
+//! whose time and distance are the concatenated digits of all the races.
+
+use crate::scale;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub races: usize,
+    /// Max time of a single race. The part 2 solution takes time linear in the concatenated time.
+    pub max_time: u64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            races: scale(4, percent, 1),
+            max_time: 99,
+        }
+    }
+}
+
+/// The best distance that can be reached in a race: charge for half of the time, then travel for
+/// the other half.
+fn best_distance(time: u128) -> u128 {
+    (time / 2) * (time - time / 2)
+}
+
+fn concat(numbers: &[u64]) -> u128 {
+    numbers
+        .iter()
+        .map(u64::to_string)
+        .collect::<String>()
+        .parse()
+        .unwrap()
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let (times, distances) = loop {
+        let times: Vec<u64> = (0..params.races)
+            .map(|_| rng.gen_range(2..=params.max_time.max(2)))
+            .collect();
+        let distances: Vec<u64> = times
+            .iter()
+            .map(|&time| {
+                let best = best_distance(time as u128) as u64;
+                rng.gen_range(best / 2..best)
+            })
+            .collect();
+
+        // The concatenated distance can overshoot the best concatenated distance when the
+        // distances have many more digits than the times. Draw again in that case.
+        if concat(&distances) < best_distance(concat(&times)) {
+            break (times, distances);
+        }
+    };
+
+    let width = distances
+        .iter()
+        .map(|distance| distance.to_string().len())
+        .max()
+        .unwrap();
+    let format = |numbers: &[u64]| -> String {
+        numbers
+            .iter()
+            .map(|number| format!("   {number:>width$}"))
+            .collect()
+    };
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        format(&times),
+        format(&distances)
+    )
+}
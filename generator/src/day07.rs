@@ -0,0 +1,38 @@
+//! Inputs for https://adventofcode.com/2023/day/7: hands of five cards, each with its bid.
+
+use crate::scale;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub hands: usize,
+    pub max_bid: u32,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            hands: scale(1000, percent, 1),
+            max_bid: 1000,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+
+    for _ in 0..params.hands {
+        // Draw from a few different cards at most, so that all types of hands are common
+        let n_kinds = rng.gen_range(1..=5);
+        let kinds: Vec<u8> = CARDS.choose_multiple(rng, n_kinds).copied().collect();
+        let hand: String = (0..5)
+            .map(|_| *kinds.choose(rng).unwrap() as char)
+            .collect();
+
+        output.push_str(&format!("{hand} {}\n", rng.gen_range(1..=params.max_bid)));
+    }
+    output
+}
@@ -0,0 +1,88 @@
+//! Inputs for https://adventofcode.com/2023/day/8: left/right instructions and a network of nodes.
+//!
+//! Every ghost walks a cycle of its own: from its start node (ending with `A`) into layers of two
+//! nodes, where both nodes of a layer lead to both nodes of the next layer, and from the last layer
+//! to its end node (ending with `Z`), which leads back into the first layer. The end node is
+//! therefore reached after the same number of steps whatever the instructions, and again after
+//! each cycle, which is what lets the solution take the LCM of the steps of all ghosts. The ghost
+//! starting at `AAA` ends at `ZZZ`.
+
+use crate::{scale, Names};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Last letters of the nodes which are neither start nor end nodes.
+const INNER_LETTERS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub instructions: usize,
+    pub ghosts: usize,
+    /// Max number of steps in the cycle of a ghost.
+    pub max_cycle: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            instructions: scale(263, percent, 1),
+            ghosts: 6,
+            max_cycle: scale(70, percent, 2),
+        }
+    }
+}
+
+/// Return the left and right node in random order.
+fn pair(rng: &mut impl Rng, a: &str, b: &str) -> String {
+    let mut pair = [a, b];
+    pair.shuffle(rng);
+    format!("({}, {})", pair[0], pair[1])
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let instructions: String = (0..params.instructions)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let mut names = Names::default();
+    names.reserve("AAA");
+    names.reserve("ZZZ");
+
+    let mut lines = Vec::new();
+    for ghost in 0..params.ghosts.max(1) {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (
+                names.fresh_with_last(rng, 3, LETTERS, b"A"),
+                names.fresh_with_last(rng, 3, LETTERS, b"Z"),
+            ),
+        };
+
+        let cycle = rng.gen_range(2..=params.max_cycle.max(2));
+        let layers: Vec<[String; 2]> = (1..cycle)
+            .map(|_| [(); 2].map(|_| names.fresh_with_last(rng, 3, LETTERS, INNER_LETTERS)))
+            .collect();
+
+        lines.push(format!(
+            "{start} = {}",
+            pair(rng, &layers[0][0], &layers[0][1])
+        ));
+        lines.push(format!(
+            "{end} = {}",
+            pair(rng, &layers[0][0], &layers[0][1])
+        ));
+        for (i, layer) in layers.iter().enumerate() {
+            let next = layers
+                .get(i + 1)
+                .unwrap_or(&[end.clone(), end.clone()])
+                .clone();
+            for node in layer {
+                lines.push(format!("{node} = {}", pair(rng, &next[0], &next[1])));
+            }
+        }
+    }
+    lines.shuffle(rng);
+
+    format!("{instructions}\n\n{}\n", lines.join("\n"))
+}
@@ -0,0 +1,65 @@
+//! Inputs for https://adventofcode.com/2023/day/9: sequences of values of polynomials, so that
+//! repeatedly taking the differences always ends in zeros. The values, including the extrapolated
+//! ones, stay small enough for their sum to fit into the answer.
+
+use crate::scale;
+use rand::Rng;
+
+/// Max absolute value in a sequence, including the extrapolated values.
+const MAX_VALUE: i64 = 1_000_000;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub sequences: usize,
+    pub len: usize,
+    pub max_degree: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            sequences: scale(200, percent, 1),
+            len: 21,
+            max_degree: 6,
+        }
+    }
+}
+
+/// Return a sequence of the given length with one extra value before and after it.
+fn extended_sequence(rng: &mut impl Rng, len: usize, degree: usize) -> Vec<i64> {
+    // Value of each level of differences at the current position. The last level is constant.
+    let mut diffs: Vec<i64> = (0..=degree).map(|_| rng.gen_range(-5..=5)).collect();
+
+    let mut sequence = Vec::with_capacity(len + 2);
+    for _ in 0..len + 2 {
+        sequence.push(diffs[0]);
+        for level in 0..degree {
+            diffs[level] += diffs[level + 1];
+        }
+    }
+    sequence
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+
+    for _ in 0..params.sequences {
+        // Lower the degree until the values are small enough. Constant sequences always are.
+        let mut degree = rng.gen_range(0..=params.max_degree);
+        let sequence = loop {
+            let sequence = extended_sequence(rng, params.len.max(1), degree);
+            if sequence.iter().all(|value| value.abs() <= MAX_VALUE) {
+                break sequence;
+            }
+            degree -= 1;
+        };
+
+        let values: Vec<String> = sequence[1..=params.len.max(1)]
+            .iter()
+            .map(i64::to_string)
+            .collect();
+        output.push_str(&values.join(" "));
+        output.push('\n');
+    }
+    output
+}
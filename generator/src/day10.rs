@@ -0,0 +1,131 @@
+//! Inputs for https://adventofcode.com/2023/day/10: a field of pipes with a single loop through the
+//! start tile. The loop has the outline of a skyline: a flat bottom and a top made of segments of
+//! different heights. The start tile lies on the bottom, and its neighbours off the loop are
+//! ground, because the solution infers the type of the start tile from its neighbours.
+
+use crate::{grid_to_string, random_grid, scale};
+use rand::seq::index;
+use rand::Rng;
+
+/// Pipes off the loop, with their weights.
+const JUNK: [(char, u32); 7] = [
+    ('.', 6),
+    ('|', 1),
+    ('-', 1),
+    ('L', 1),
+    ('J', 1),
+    ('7', 1),
+    ('F', 1),
+];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the field.
+    pub size: usize,
+    /// Max number of segments in the top of the loop.
+    pub max_segments: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(140, percent, 4),
+            max_segments: scale(30, percent, 1),
+        }
+    }
+}
+
+/// Return the corners of the loop, starting at the left end of the bottom and going up.
+fn skyline(rng: &mut impl Rng, params: &Params) -> Vec<(usize, usize)> {
+    let size = params.size.max(4);
+    let bottom = rng.gen_range((size / 2).max(2)..size);
+    let left = rng.gen_range(0..size / 4 + 1);
+    let right = rng.gen_range(left + 2..size);
+
+    let n_segments = rng.gen_range(1..=params.max_segments.clamp(1, right - left));
+    let mut xs: Vec<usize> = index::sample(rng, right - left - 1, n_segments - 1)
+        .into_iter()
+        .map(|x| x + left + 1)
+        .collect();
+    xs.sort_unstable();
+    xs.insert(0, left);
+    xs.push(right);
+
+    let mut corners = vec![(left, bottom)];
+    let mut prev_top = bottom;
+    for segment in xs.windows(2) {
+        let top = loop {
+            let top = rng.gen_range(0..bottom);
+            if top != prev_top {
+                break top;
+            }
+        };
+        corners.push((segment[0], top));
+        corners.push((segment[1], top));
+        prev_top = top;
+    }
+    corners.push((right, bottom));
+    corners
+}
+
+/// Return the tiles of the loop going through the corners, in order.
+fn trace(corners: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut tiles = Vec::new();
+    for (i, &(x, y)) in corners.iter().enumerate() {
+        let (next_x, next_y) = corners[(i + 1) % corners.len()];
+        let (mut x, mut y) = (x as i64, y as i64);
+        let (dx, dy) = ((next_x as i64 - x).signum(), (next_y as i64 - y).signum());
+
+        while (x, y) != (next_x as i64, next_y as i64) {
+            tiles.push((x as usize, y as usize));
+            x += dx;
+            y += dy;
+        }
+    }
+    tiles
+}
+
+/// Pipe which connects the tile to both of its neighbours on the loop.
+fn pipe((x, y): (usize, usize), neighbours: [(usize, usize); 2]) -> char {
+    let [north, south, west, east] = [
+        neighbours.contains(&(x, y.wrapping_sub(1))),
+        neighbours.contains(&(x, y + 1)),
+        neighbours.contains(&(x.wrapping_sub(1), y)),
+        neighbours.contains(&(x + 1, y)),
+    ];
+
+    match (north, south, west, east) {
+        (true, true, _, _) => '|',
+        (_, _, true, true) => '-',
+        (true, _, true, _) => 'J',
+        (true, _, _, true) => 'L',
+        (_, true, true, _) => '7',
+        _ => 'F',
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let size = params.size.max(4);
+    let mut rows = random_grid(rng, size, size, &JUNK);
+
+    let corners = skyline(rng, params);
+    let tiles = trace(&corners);
+    for (i, &(x, y)) in tiles.iter().enumerate() {
+        let prev = tiles[(i + tiles.len() - 1) % tiles.len()];
+        let next = tiles[(i + 1) % tiles.len()];
+        rows[y][x] = pipe((x, y), [prev, next]);
+    }
+
+    // The start tile is on the bottom, between its two corners
+    let (left, bottom) = corners[0];
+    let right = corners[corners.len() - 1].0;
+    let start_x = rng.gen_range(left + 1..right);
+    rows[bottom][start_x] = 'S';
+    for (x, y) in [(start_x, bottom - 1), (start_x, bottom + 1)] {
+        if y < size && !tiles.contains(&(x, y)) {
+            rows[y][x] = '.';
+        }
+    }
+
+    grid_to_string(&rows)
+}
@@ -0,0 +1,51 @@
+//! Inputs for https://adventofcode.com/2023/day/11: an image of galaxies in empty space, with some
+//! rows and columns left without galaxies so that the universe expands.
+
+use crate::{grid_to_string, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the image.
+    pub size: usize,
+    /// Probability that a tile outside of the empty rows and columns is a galaxy.
+    pub galaxy_density: f64,
+    /// Probability that a row or a column is left empty.
+    pub empty_line_density: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(140, percent, 2),
+            galaxy_density: 0.03,
+            empty_line_density: 0.07,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let empty_rows: Vec<bool> = (0..params.size)
+        .map(|_| rng.gen_bool(params.empty_line_density))
+        .collect();
+    let empty_cols: Vec<bool> = (0..params.size)
+        .map(|_| rng.gen_bool(params.empty_line_density))
+        .collect();
+
+    let rows: Vec<Vec<char>> = (0..params.size)
+        .map(|y| {
+            (0..params.size)
+                .map(|x| {
+                    let empty = empty_rows[y] || empty_cols[x];
+                    if !empty && rng.gen_bool(params.galaxy_density) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    grid_to_string(&rows)
+}
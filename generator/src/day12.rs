@@ -0,0 +1,57 @@
+//! Inputs for https://adventofcode.com/2023/day/12: records of springs with the sizes of the groups
+//! of damaged springs. The records are drawn from a real arrangement of the springs, so each one
+//! has at least one arrangement, and then some of the springs are hidden behind `?`.
+
+use crate::scale;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub records: usize,
+    pub max_len: usize,
+    /// Probability that the condition of a spring is unknown.
+    pub unknown_density: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            records: scale(1000, percent, 1),
+            max_len: 20,
+            unknown_density: 0.5,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut output = String::new();
+
+    for _ in 0..params.records {
+        let len = rng.gen_range(1..=params.max_len.max(1));
+        let mut springs: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+        // There must be at least one group of damaged springs
+        springs[rng.gen_range(0..len)] = true;
+
+        let mut groups = Vec::new();
+        let mut group = 0;
+        for &damaged in springs.iter().chain([false].iter()) {
+            if damaged {
+                group += 1;
+            } else if group > 0 {
+                groups.push(group.to_string());
+                group = 0;
+            }
+        }
+
+        let record: String = springs
+            .iter()
+            .map(|&damaged| match damaged {
+                _ if rng.gen_bool(params.unknown_density) => '?',
+                true => '#',
+                false => '.',
+            })
+            .collect();
+        output.push_str(&format!("{record} {}\n", groups.join(",")));
+    }
+    output
+}
@@ -0,0 +1,86 @@
+//! Inputs for https://adventofcode.com/2023/day/13: patterns of ash and rocks.
+//!
+//! Each pattern is drawn with two lines of reflection, a vertical and a horizontal one. The
+//! vertical line does not split the pattern in half, so some columns are not reflected by it, and
+//! flipping a tile in one of these columns breaks the horizontal reflection without touching the
+//! vertical one. Part 1 then finds the vertical line, and part 2 the horizontal line with the
+//! flipped tile as the smudge.
+
+use crate::{grid_to_string, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub patterns: usize,
+    pub min_size: usize,
+    pub max_size: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            patterns: scale(100, percent, 1),
+            min_size: 5,
+            max_size: 17,
+        }
+    }
+}
+
+/// Return the start and the end of the lines reflected by the line of reflection, which is just
+/// before `line`.
+fn reflected(line: usize, len: usize) -> (usize, usize) {
+    let reach = line.min(len - line);
+    (line - reach, line + reach)
+}
+
+fn pattern(rng: &mut impl Rng, params: &Params) -> Vec<Vec<char>> {
+    let sizes = params.min_size.max(3)..=params.max_size.max(params.min_size).max(3);
+    let (width, height) = (rng.gen_range(sizes.clone()), rng.gen_range(sizes));
+
+    let mut rows: Vec<Vec<char>> = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| if rng.gen_bool(0.5) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    // Columns which are not reflected exist as long as the line is not in the middle
+    let col = loop {
+        let col = rng.gen_range(1..width);
+        if 2 * col != width {
+            break col;
+        }
+    };
+    let (first_col, end_col) = reflected(col, width);
+    for row in &mut rows {
+        for x in col..end_col {
+            row[x] = row[2 * col - 1 - x];
+        }
+    }
+
+    let row = rng.gen_range(1..height);
+    let (first_row, end_row) = reflected(row, height);
+    for y in row..end_row {
+        rows[y] = rows[2 * row - 1 - y].clone();
+    }
+
+    let smudge_x = loop {
+        let x = rng.gen_range(0..width);
+        if !(first_col..end_col).contains(&x) {
+            break x;
+        }
+    };
+    let smudge_y = rng.gen_range(first_row..end_row);
+    let tile = &mut rows[smudge_y][smudge_x];
+    *tile = if *tile == '#' { '.' } else { '#' };
+
+    rows
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let patterns: Vec<String> = (0..params.patterns)
+        .map(|_| grid_to_string(&pattern(rng, params)))
+        .collect();
+    patterns.join("\n")
+}
@@ -0,0 +1,34 @@
+//! Inputs for https://adventofcode.com/2023/day/14: a platform of round rocks, which roll when the
+//! platform is tilted, and cube-shaped rocks, which stay in place.
+
+use crate::{grid_to_string, random_grid, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the platform.
+    pub size: usize,
+    pub round_weight: u32,
+    pub cube_weight: u32,
+    pub empty_weight: u32,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(100, percent, 1),
+            round_weight: 2,
+            cube_weight: 1,
+            empty_weight: 7,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let symbols = [
+        ('O', params.round_weight),
+        ('#', params.cube_weight),
+        ('.', params.empty_weight),
+    ];
+    grid_to_string(&random_grid(rng, params.size, params.size, &symbols))
+}
@@ -0,0 +1,50 @@
+//! Inputs for https://adventofcode.com/2023/day/15: a single line of comma separated steps, each
+//! either inserting a lens of focal length 1 to 9 or removing the lens with the label. The labels
+//! are drawn from a limited set, so that lenses get replaced and removed.
+
+use crate::{scale, Names, LOWERCASE};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub steps: usize,
+    pub labels: usize,
+    pub max_label_len: usize,
+    /// Probability that a step removes a lens.
+    pub removals: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            steps: scale(4000, percent, 1),
+            labels: scale(500, percent, 1),
+            max_label_len: 6,
+            removals: 0.4,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut names = Names::default();
+    let labels: Vec<String> = (0..params.labels)
+        .map(|_| {
+            let len = rng.gen_range(2..=params.max_label_len.max(2));
+            names.fresh(rng, len, LOWERCASE)
+        })
+        .collect();
+
+    let steps: Vec<String> = (0..params.steps)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(params.removals) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.gen_range(1..=9))
+            }
+        })
+        .collect();
+
+    format!("{}\n", steps.join(","))
+}
@@ -0,0 +1,36 @@
+//! Inputs for https://adventofcode.com/2023/day/16: a contraption of empty space, mirrors and
+//! splitters.
+
+use crate::{grid_to_string, random_grid, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the contraption.
+    pub size: usize,
+    /// Probability that a tile is a mirror or a splitter.
+    pub density: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(110, percent, 1),
+            density: 0.1,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    // Each of the four mirrors and splitters is equally likely
+    let weight = (params.density.clamp(0.0, 1.0) * 250.0) as u32;
+    let empty = 1000 - 4 * weight;
+    let symbols = [
+        ('.', empty),
+        ('/', weight),
+        ('\\', weight),
+        ('|', weight),
+        ('-', weight),
+    ];
+    grid_to_string(&random_grid(rng, params.size, params.size, &symbols))
+}
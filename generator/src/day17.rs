@@ -0,0 +1,26 @@
+//! Inputs for https://adventofcode.com/2023/day/17: a map of the heat loss of each city block,
+//! from 1 to 9.
+
+use crate::{grid_to_string, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the map.
+    pub size: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(141, percent, 5),
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let rows: Vec<Vec<char>> = (0..params.size)
+        .map(|_| (0..params.size).map(|_| rng.gen_range('1'..='9')).collect())
+        .collect();
+    grid_to_string(&rows)
+}
@@ -0,0 +1,86 @@
+//! Inputs for https://adventofcode.com/2023/day/18: a dig plan where each line describes an edge
+//! of two different trenches, one through the direction and length, the other through the colour
+//! code. Both trenches are simple loops with the outline of a skyline, rotated at random, and have
+//! the same number of edges.
+
+use crate::scale;
+use rand::Rng;
+
+/// Max length of an edge of the second trench, which is written with five hex digits.
+const MAX_HEX_LENGTH: i64 = 0xfffff;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of segments in the top of the skyline. Each trench has twice as many edges, plus 2.
+    pub segments: usize,
+    /// Max length of an edge of the first trench.
+    pub max_length: i64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            segments: scale(330, percent, 1),
+            max_length: 10,
+        }
+    }
+}
+
+/// Return the edges of a skyline of `n_segments` as pairs of direction and length, with the
+/// directions going clockwise from 0 = right.
+fn skyline(
+    rng: &mut impl Rng,
+    n_segments: usize,
+    max_width: i64,
+    max_height: i64,
+) -> Vec<(usize, i64)> {
+    let widths: Vec<i64> = (0..n_segments)
+        .map(|_| rng.gen_range(1..=max_width))
+        .collect();
+
+    let mut heights: Vec<i64> = Vec::new();
+    while heights.len() < n_segments {
+        let height = rng.gen_range(1..=max_height.max(2));
+        if heights.last() != Some(&height) {
+            heights.push(height);
+        }
+    }
+
+    let (right, down, left, up) = (0, 1, 2, 3);
+    let mut edges = vec![(up, heights[0])];
+    for i in 0..n_segments {
+        edges.push((right, widths[i]));
+        match heights.get(i + 1) {
+            Some(&next) if next > heights[i] => edges.push((up, next - heights[i])),
+            Some(&next) => edges.push((down, heights[i] - next)),
+            None => edges.push((down, heights[i])),
+        }
+    }
+    edges.push((left, widths.iter().sum()));
+
+    // Rotate the whole trench and start digging at a random edge
+    let rotation = rng.gen_range(0..4);
+    let start = rng.gen_range(0..edges.len());
+    edges.rotate_left(start);
+    edges
+        .into_iter()
+        .map(|(direction, length)| ((direction + rotation) % 4, length))
+        .collect()
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let segments = params.segments.max(1);
+    let max_length = params.max_length.max(1);
+    let edges1 = skyline(rng, segments, max_length, max_length);
+    // The bottom edge, which is as long as all the segments together, has to fit as well
+    let max_width = (MAX_HEX_LENGTH / segments as i64).max(1);
+    let edges2 = skyline(rng, segments, max_width, MAX_HEX_LENGTH);
+
+    let mut output = String::new();
+    for ((direction1, length1), (direction2, length2)) in edges1.into_iter().zip(edges2) {
+        let direction1 = ['R', 'D', 'L', 'U'][direction1];
+        let color = format!("#{length2:05x}{direction2}");
+        output.push_str(&format!("{direction1} {length1} ({color})\n"));
+    }
+    output
+}
@@ -0,0 +1,121 @@
+//! Inputs for https://adventofcode.com/2023/day/19: workflows followed by parts.
+//!
+//! The workflows form a tree rooted at `in`. Each workflow is generated knowing the ranges of
+//! ratings that can reach it, and only compares ratings with thresholds strictly inside these
+//! ranges, so that every rule splits the ranges into two non-empty parts, as the solution of part
+//! 2 expects.
+
+use crate::{scale, Names, LOWERCASE};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::VecDeque;
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+const MAX_RATING: u32 = 4000;
+
+/// Inclusive ranges of the ratings of each category.
+type Ranges = [(u32, u32); 4];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub workflows: usize,
+    /// Max number of conditional rules in a workflow, besides the final unconditional rule.
+    pub max_rules: usize,
+    pub parts: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            workflows: scale(580, percent, 1),
+            max_rules: 3,
+            parts: scale(200, percent, 1),
+        }
+    }
+}
+
+struct WorkflowTree<'a, R> {
+    rng: &'a mut R,
+    names: Names,
+    /// Workflows which have been named, but not generated yet, with the ranges that reach them.
+    queue: VecDeque<(String, Ranges)>,
+    n_workflows: usize,
+    max_workflows: usize,
+}
+
+impl<R: Rng> WorkflowTree<'_, R> {
+    /// Target of a rule: a new workflow, if there is room for more, or accept or reject.
+    fn target(&mut self, ranges: Ranges) -> String {
+        if self.n_workflows < self.max_workflows && self.rng.gen_bool(0.6) {
+            let len = self.rng.gen_range(2..=3);
+            let name = self.names.fresh(self.rng, len, LOWERCASE);
+            self.queue.push_back((name.clone(), ranges));
+            self.n_workflows += 1;
+            name
+        } else if self.rng.gen_bool(0.5) {
+            "A".to_string()
+        } else {
+            "R".to_string()
+        }
+    }
+
+    fn workflow(&mut self, name: &str, mut ranges: Ranges, max_rules: usize) -> String {
+        let mut rules = Vec::new();
+
+        for _ in 0..self.rng.gen_range(1..=max_rules.max(1)) {
+            let categories: Vec<usize> = (0..4)
+                .filter(|&category| ranges[category].1 - ranges[category].0 >= 2)
+                .collect();
+            let Some(&category) = categories.choose(self.rng) else {
+                break;
+            };
+
+            let (low, high) = ranges[category];
+            let threshold = self.rng.gen_range(low + 1..high);
+            let (op, matching, remaining) = if self.rng.gen_bool(0.5) {
+                ('<', (low, threshold - 1), (threshold, high))
+            } else {
+                ('>', (threshold + 1, high), (low, threshold))
+            };
+
+            let mut matching_ranges = ranges;
+            matching_ranges[category] = matching;
+            ranges[category] = remaining;
+
+            let target = self.target(matching_ranges);
+            rules.push(format!("{}{op}{threshold}:{target}", CATEGORIES[category]));
+        }
+
+        rules.push(self.target(ranges));
+        format!("{name}{{{}}}", rules.join(","))
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let mut names = Names::default();
+    names.reserve("in");
+
+    let mut tree = WorkflowTree {
+        rng,
+        names,
+        queue: VecDeque::from([("in".to_string(), [(1, MAX_RATING); 4])]),
+        n_workflows: 1,
+        max_workflows: params.workflows,
+    };
+
+    let mut workflows = Vec::new();
+    while let Some((name, ranges)) = tree.queue.pop_front() {
+        workflows.push(tree.workflow(&name, ranges, params.max_rules));
+    }
+    workflows.shuffle(tree.rng);
+
+    let rng = tree.rng;
+    let parts: Vec<String> = (0..params.parts)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=MAX_RATING));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect();
+
+    format!("{}\n\n{}\n", workflows.join("\n"), parts.join("\n"))
+}
@@ -0,0 +1,81 @@
+//! Inputs for https://adventofcode.com/2023/day/20: a network of flip-flop and conjunction modules.
+//!
+//! The network has the shape that the solution of part 2 relies on. The broadcaster starts four
+//! binary counters, each a chain of flip-flops. The flip-flops of the bits which are set in the
+//! period of the counter feed a conjunction, which resets the counter once it reaches the period,
+//! and at the same time sends a low pulse to an inverter: one of `rk`, `cd`, `zf` and `qx`. The
+//! four inverters feed the conjunction `gh`, which sends a low pulse to `rx` once the counters all
+//! reach their periods at the same time.
+
+use crate::{scale, Names, LOWERCASE};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const INVERTERS: [&str; 4] = ["rk", "cd", "zf", "qx"];
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of flip-flops in each counter. The periods of the counters have this many bits.
+    pub bits: usize,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            bits: scale(12, percent, 4),
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    // There must be at least four odd periods with the highest bit set
+    let bits = params.bits.clamp(4, 63);
+
+    let mut names = Names::default();
+    for name in INVERTERS.iter().chain(&["gh", "rx"]) {
+        names.reserve(name);
+    }
+
+    let mut periods: Vec<u64> = Vec::new();
+    while periods.len() < INVERTERS.len() {
+        let period = rng.gen_range(1 << (bits - 1)..1 << bits) | 1;
+        if !periods.contains(&period) {
+            periods.push(period);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut first_flip_flops = Vec::new();
+
+    for (inverter, period) in INVERTERS.into_iter().zip(periods) {
+        let flip_flops: Vec<String> = (0..bits).map(|_| names.fresh(rng, 2, LOWERCASE)).collect();
+        let hub = names.fresh(rng, 2, LOWERCASE);
+
+        let mut hub_outputs = vec![flip_flops[0].clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs: Vec<&str> = Vec::new();
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next);
+            }
+            if period & (1 << bit) != 0 {
+                outputs.push(&hub);
+            } else {
+                hub_outputs.push(flip_flop.clone());
+            }
+            outputs.shuffle(rng);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+
+        hub_outputs.push(inverter.to_string());
+        hub_outputs.shuffle(rng);
+        lines.push(format!("&{hub} -> {}", hub_outputs.join(", ")));
+        lines.push(format!("&{inverter} -> gh"));
+        first_flip_flops.push(flip_flops[0].clone());
+    }
+
+    lines.push(format!("broadcaster -> {}", first_flip_flops.join(", ")));
+    lines.push("&gh -> rx".to_string());
+    lines.shuffle(rng);
+
+    format!("{}\n", lines.join("\n"))
+}
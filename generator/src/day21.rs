@@ -0,0 +1,47 @@
+//! Inputs for https://adventofcode.com/2023/day/21: a square garden with the start in its centre.
+//! The side is odd, and the row and the column of the start as well as the edges of the garden
+//! are free of rocks, which the solution of part 2 relies on to count the plots of the infinitely
+//! repeating garden circle by circle.
+
+use crate::{grid_to_string, scale};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Width and height of the garden. Rounded up to an odd number.
+    pub size: usize,
+    pub rock_density: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            size: scale(131, percent, 5),
+            rock_density: 0.12,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let size = params.size.max(3) | 1;
+    let center = size / 2;
+
+    let rows: Vec<Vec<char>> = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let clear = [x, y]
+                        .iter()
+                        .any(|&i| i == 0 || i == center || i == size - 1);
+                    match (x, y) {
+                        _ if (x, y) == (center, center) => 'S',
+                        _ if !clear && rng.gen_bool(params.rock_density) => '#',
+                        _ => '.',
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    grid_to_string(&rows)
+}
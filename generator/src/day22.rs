@@ -0,0 +1,67 @@
+//! Inputs for https://adventofcode.com/2023/day/22: a snapshot of falling bricks. Each brick is a
+//! line of cubes along a single axis, above the ground, and no two bricks overlap.
+
+use crate::scale;
+use rand::Rng;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub bricks: usize,
+    /// Width and depth of the area where the bricks fall.
+    pub area: u32,
+    /// Max number of cubes in a brick.
+    pub max_len: u32,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            bricks: scale(1200, percent, 1),
+            area: 10,
+            max_len: 5,
+        }
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let area = params.area.max(1);
+    // Bricks are spread out over a height which leaves some room between them
+    let max_z = (params.bricks as u32 * 30 / (area * area)).max(10);
+
+    let mut occupied: HashSet<[u32; 3]> = HashSet::new();
+    let mut output = String::new();
+    let mut n_bricks = 0;
+
+    while n_bricks < params.bricks {
+        let mut start = [
+            rng.gen_range(0..area),
+            rng.gen_range(0..area),
+            rng.gen_range(1..=max_z),
+        ];
+        let axis = rng.gen_range(0..3);
+        let len = rng.gen_range(1..=params.max_len.max(1));
+        // Keep horizontal bricks inside the area
+        if axis < 2 {
+            start[axis] = start[axis].min(area.saturating_sub(len));
+        }
+
+        let cubes: Vec<[u32; 3]> = (0..len)
+            .map(|i| {
+                let mut cube = start;
+                cube[axis] += i;
+                cube
+            })
+            .collect();
+        if cubes.iter().any(|cube| occupied.contains(cube)) {
+            continue;
+        }
+        occupied.extend(&cubes);
+
+        let [x1, y1, z1] = cubes[0];
+        let [x2, y2, z2] = cubes[cubes.len() - 1];
+        output.push_str(&format!("{x1},{y1},{z1}~{x2},{y2},{z2}\n"));
+        n_bricks += 1;
+    }
+    output
+}
@@ -0,0 +1,128 @@
+//! Inputs for https://adventofcode.com/2023/day/23: a maze of paths one tile wide.
+//!
+//! The forks of the maze lie on a lattice, and straight paths connect neighbouring forks going east
+//! or south. The slopes next to the forks point east or south as well, so the paths form a DAG, as
+//! in the real input. Some paths are left out, but every fork keeps a way in and a way out, so all
+//! of them lie on a path from the start in the first row to the end in the last row.
+
+use crate::{grid_to_string, scale};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// Number of forks in each row and each column of the lattice.
+    pub forks: usize,
+    /// Max distance between neighbouring forks.
+    pub max_distance: usize,
+    /// Probability that a path between neighbouring forks is left out.
+    pub removals: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            forks: scale(6, percent, 2),
+            max_distance: 40,
+            removals: 0.3,
+        }
+    }
+}
+
+/// Path between the fork at `(i, j)` in the lattice and its neighbour to the east or south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Path {
+    East(usize, usize),
+    South(usize, usize),
+}
+
+impl Path {
+    fn ends(self) -> ((usize, usize), (usize, usize)) {
+        match self {
+            Path::East(i, j) => ((i, j), (i + 1, j)),
+            Path::South(i, j) => ((i, j), (i, j + 1)),
+        }
+    }
+}
+
+/// Return the positions of the forks along one axis.
+fn positions(rng: &mut impl Rng, n_forks: usize, max_distance: usize) -> Vec<usize> {
+    let mut positions = vec![1];
+    for _ in 1..n_forks {
+        let last = positions[positions.len() - 1];
+        positions.push(last + rng.gen_range(2..=max_distance.max(2)));
+    }
+    positions
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let n = params.forks.max(2);
+    let xs = positions(rng, n, params.max_distance);
+    let ys = positions(rng, n, params.max_distance);
+    let size = (xs[n - 1] + 2).max(ys[n - 1] + 3);
+
+    let mut paths: Vec<Path> = Vec::new();
+    for i in 0..n {
+        for j in 0..n {
+            if i + 1 < n {
+                paths.push(Path::East(i, j));
+            }
+            if j + 1 < n {
+                paths.push(Path::South(i, j));
+            }
+        }
+    }
+
+    // The first fork is entered from the start and the last one leads to the end
+    let mut ins = vec![vec![0; n]; n];
+    let mut outs = vec![vec![0; n]; n];
+    ins[0][0] = 1;
+    outs[n - 1][n - 1] = 1;
+    for path in &paths {
+        let ((i1, j1), (i2, j2)) = path.ends();
+        outs[i1][j1] += 1;
+        ins[i2][j2] += 1;
+    }
+
+    let mut candidates = paths.clone();
+    candidates.shuffle(rng);
+    for path in candidates {
+        let ((i1, j1), (i2, j2)) = path.ends();
+        if outs[i1][j1] > 1 && ins[i2][j2] > 1 && rng.gen_bool(params.removals) {
+            outs[i1][j1] -= 1;
+            ins[i2][j2] -= 1;
+            paths.retain(|&other| other != path);
+        }
+    }
+
+    let mut rows = vec![vec!['#'; size]; size];
+    for (i, j) in (0..n).flat_map(|i| (0..n).map(move |j| (i, j))) {
+        rows[ys[j]][xs[i]] = '.';
+    }
+    for path in paths {
+        match path {
+            Path::East(i, j) => {
+                let row = &mut rows[ys[j]];
+                row[xs[i] + 1..xs[i + 1]].fill('.');
+                row[xs[i] + 1] = '>';
+                row[xs[i + 1] - 1] = '>';
+            },
+            Path::South(i, j) => {
+                for row in &mut rows[ys[j] + 1..ys[j + 1]] {
+                    row[xs[i]] = '.';
+                }
+                rows[ys[j] + 1][xs[i]] = 'v';
+                rows[ys[j + 1] - 1][xs[i]] = 'v';
+            },
+        }
+    }
+
+    // Start above the first fork, and leave the last fork through a slope down to the end
+    rows[0][xs[0]] = '.';
+    for row in rows.iter_mut().skip(ys[n - 1] + 1) {
+        row[xs[n - 1]] = '.';
+    }
+    rows[ys[n - 1] + 1][xs[n - 1]] = 'v';
+
+    grid_to_string(&rows)
+}
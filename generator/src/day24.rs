@@ -0,0 +1,65 @@
+//! Inputs for https://adventofcode.com/2023/day/24: positions and velocities of hailstones.
+//!
+//! The hailstones are placed on the path of a rock thrown with a velocity whose components are
+//! non-zero and small enough for the brute force search of part 2 to find them, so that the rock
+//! hits every hailstone at its own time.
+
+use crate::scale;
+use rand::Rng;
+
+/// Range of the coordinates of the rock's position, which is also the range of coordinates where
+/// part 1 looks for collisions.
+const MIN_COORD: i64 = 200_000_000_000_000;
+const MAX_COORD: i64 = 400_000_000_000_000;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub hailstones: usize,
+    /// Max absolute value of the components of the rock's velocity. Part 2 searches up to 300.
+    pub max_rock_velocity: i64,
+    /// Max absolute value of the components of the velocities of the hailstones.
+    pub max_velocity: i64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            hailstones: scale(300, percent, 5),
+            max_rock_velocity: 100,
+            max_velocity: 300,
+        }
+    }
+}
+
+/// Return a non-zero value up to the max in absolute value.
+fn nonzero(rng: &mut impl Rng, max: i64) -> i64 {
+    let value = rng.gen_range(1..=max.max(1));
+    if rng.gen_bool(0.5) {
+        value
+    } else {
+        -value
+    }
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    let rock_position = [(); 3].map(|_| rng.gen_range(MIN_COORD..=MAX_COORD));
+    let rock_velocity = [(); 3].map(|_| nonzero(rng, params.max_rock_velocity));
+
+    let mut output = String::new();
+    for _ in 0..params.hailstones.max(3) {
+        // Relative to the rock, every hailstone needs to move along each axis
+        let velocity = rock_velocity.map(|rock_v| loop {
+            let v = rng.gen_range(-params.max_velocity..=params.max_velocity);
+            if v != rock_v {
+                break v;
+            }
+        });
+        let time = rng.gen_range(100_000_000_000..1_000_000_000_000);
+
+        let [px, py, pz] = [0, 1, 2]
+            .map(|axis| rock_position[axis] + time * (rock_velocity[axis] - velocity[axis]));
+        let [vx, vy, vz] = velocity;
+        output.push_str(&format!("{px}, {py}, {pz} @ {vx}, {vy}, {vz}\n"));
+    }
+    output
+}
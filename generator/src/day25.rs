@@ -0,0 +1,88 @@
+//! Inputs for https://adventofcode.com/2023/day/25: connections between components.
+//!
+//! The components form two groups, connected to each other by exactly three wires. Within a group,
+//! the components are connected in a ring where each one is wired to the next two, plus some
+//! random wires, so that at least four wires have to be cut to split a group. The cut of three
+//! wires is therefore the only minimum cut, which is what the solution searches for.
+
+use crate::{scale, Names, LOWERCASE};
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub components: usize,
+    /// Number of random wires within each group, per component.
+    pub extra_wires: f64,
+}
+
+impl Params {
+    pub fn scaled(percent: usize) -> Params {
+        Params {
+            components: scale(1500, percent, 10),
+            extra_wires: 0.5,
+        }
+    }
+}
+
+/// Wire up the components into a group which cannot be split by cutting three wires.
+fn group(rng: &mut impl Rng, components: &[usize], extra_wires: f64) -> HashSet<(usize, usize)> {
+    let n = components.len();
+    let wire = |a: usize, b: usize| (a.min(b), a.max(b));
+
+    let mut wires = HashSet::new();
+    for i in 0..n {
+        wires.insert(wire(components[i], components[(i + 1) % n]));
+        wires.insert(wire(components[i], components[(i + 2) % n]));
+    }
+
+    let n_extra = (n as f64 * extra_wires) as usize;
+    while wires.len() < 2 * n + n_extra.min(n * (n - 1) / 2 - 2 * n) {
+        let (a, b) = (
+            *components.choose(rng).unwrap(),
+            *components.choose(rng).unwrap(),
+        );
+        if a != b {
+            wires.insert(wire(a, b));
+        }
+    }
+    wires
+}
+
+pub fn generate(rng: &mut impl Rng, params: &Params) -> String {
+    // Each group needs at least five components for the ring to be wired to the next two
+    let n = params.components.max(10);
+    let mut components: Vec<usize> = (0..n).collect();
+    components.shuffle(rng);
+    let (group1, group2) = components.split_at(rng.gen_range(5..=n - 5));
+
+    let mut wires = group(rng, group1, params.extra_wires);
+    wires.extend(group(rng, group2, params.extra_wires));
+
+    let ends1 = index::sample(rng, group1.len(), 3);
+    let ends2 = index::sample(rng, group2.len(), 3);
+    for (a, b) in ends1.into_iter().zip(ends2) {
+        wires.insert((group1[a], group2[b]));
+    }
+
+    let mut names = Names::default();
+    let names: Vec<String> = (0..n).map(|_| names.fresh(rng, 3, LOWERCASE)).collect();
+
+    // Each wire is listed once, on the line of either of its components
+    let mut wires: Vec<(usize, usize)> = wires.into_iter().collect();
+    wires.sort_unstable();
+    let mut connections: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (a, b) in wires {
+        let (from, to) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        connections.entry(from).or_default().push(&names[to]);
+    }
+
+    let mut lines: Vec<String> = connections
+        .into_iter()
+        .map(|(from, to)| format!("{}: {}", names[from], to.join(" ")))
+        .collect();
+    lines.shuffle(rng);
+
+    format!("{}\n", lines.join("\n"))
+}
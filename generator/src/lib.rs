@@ -0,0 +1,196 @@
+//! Random puzzle inputs for every day, for benchmarks and property tests.
+//!
+//! Each day has a module with the knobs of its generator in `Params` and a `generate` function
+//! which writes an input in the exact format accepted by the parser of the day. Besides the
+//! format, the inputs also keep the properties of the real inputs that the solutions rely on, e.g.
+//! that the module network of day 20 ends with four counters feeding into `rx`.
+//!
+//! The generators only draw from the given RNG, so the same seed always gives the same input.
+//! `Params::scaled` sets the knobs relative to the size of the real input, in percent.
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// Create the RNG used by all generators, seeded so that the output is reproducible.
+pub fn rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Generate an input for the given day from the seed, with the size relative to the real input in
+/// percent. Return `None` if there is no such day.
+pub fn generate(day: u8, seed: u64, scale: usize) -> Option<String> {
+    let mut rng = rng(seed);
+
+    let input = match day {
+        1 => day01::generate(&mut rng, &day01::Params::scaled(scale)),
+        2 => day02::generate(&mut rng, &day02::Params::scaled(scale)),
+        3 => day03::generate(&mut rng, &day03::Params::scaled(scale)),
+        4 => day04::generate(&mut rng, &day04::Params::scaled(scale)),
+        5 => day05::generate(&mut rng, &day05::Params::scaled(scale)),
+        6 => day06::generate(&mut rng, &day06::Params::scaled(scale)),
+        7 => day07::generate(&mut rng, &day07::Params::scaled(scale)),
+        8 => day08::generate(&mut rng, &day08::Params::scaled(scale)),
+        9 => day09::generate(&mut rng, &day09::Params::scaled(scale)),
+        10 => day10::generate(&mut rng, &day10::Params::scaled(scale)),
+        11 => day11::generate(&mut rng, &day11::Params::scaled(scale)),
+        12 => day12::generate(&mut rng, &day12::Params::scaled(scale)),
+        13 => day13::generate(&mut rng, &day13::Params::scaled(scale)),
+        14 => day14::generate(&mut rng, &day14::Params::scaled(scale)),
+        15 => day15::generate(&mut rng, &day15::Params::scaled(scale)),
+        16 => day16::generate(&mut rng, &day16::Params::scaled(scale)),
+        17 => day17::generate(&mut rng, &day17::Params::scaled(scale)),
+        18 => day18::generate(&mut rng, &day18::Params::scaled(scale)),
+        19 => day19::generate(&mut rng, &day19::Params::scaled(scale)),
+        20 => day20::generate(&mut rng, &day20::Params::scaled(scale)),
+        21 => day21::generate(&mut rng, &day21::Params::scaled(scale)),
+        22 => day22::generate(&mut rng, &day22::Params::scaled(scale)),
+        23 => day23::generate(&mut rng, &day23::Params::scaled(scale)),
+        24 => day24::generate(&mut rng, &day24::Params::scaled(scale)),
+        25 => day25::generate(&mut rng, &day25::Params::scaled(scale)),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Scale the size of the real input by the percentage, without going below the minimum.
+fn scale(real: usize, percent: usize, min: usize) -> usize {
+    (real * percent / 100).max(min)
+}
+
+/// Join the rows of characters into lines.
+fn grid_to_string(rows: &[Vec<char>]) -> String {
+    let mut output = String::new();
+    for row in rows {
+        output.extend(row);
+        output.push('\n');
+    }
+    output
+}
+
+/// Grid where each tile is picked at random from the weighted symbols.
+fn random_grid(
+    rng: &mut impl Rng,
+    width: usize,
+    height: usize,
+    symbols: &[(char, u32)],
+) -> Vec<Vec<char>> {
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| {
+                    symbols
+                        .choose_weighted(rng, |(_, weight)| *weight)
+                        .unwrap()
+                        .0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Source of random names which are never repeated.
+#[derive(Debug, Default)]
+struct Names {
+    used: HashSet<String>,
+}
+
+impl Names {
+    /// Mark the name as used, so that it is never generated.
+    fn reserve(&mut self, name: &str) {
+        self.used.insert(name.to_string());
+    }
+
+    /// Return a new name of the given length made of the letters, whose last letter is one of
+    /// `last_letters`.
+    fn fresh_with_last(
+        &mut self,
+        rng: &mut impl Rng,
+        len: usize,
+        letters: &[u8],
+        last_letters: &[u8],
+    ) -> String {
+        loop {
+            let mut name: String = (1..len)
+                .map(|_| *letters.choose(rng).unwrap() as char)
+                .collect();
+            name.push(*last_letters.choose(rng).unwrap() as char);
+
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// Return a new name of the given length made of the letters.
+    fn fresh(&mut self, rng: &mut impl Rng, len: usize, letters: &[u8]) -> String {
+        self.fresh_with_last(rng, len, letters, letters)
+    }
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        for day in 1..=25 {
+            let input = generate(day, 7, 5).unwrap();
+            assert!(
+                input.ends_with('\n'),
+                "Day {day} input does not end with a newline"
+            );
+            assert_eq!(
+                generate(day, 7, 5).unwrap(),
+                input,
+                "Day {day} is not deterministic"
+            );
+        }
+        assert_ne!(generate(17, 1, 5), generate(17, 2, 5));
+        assert_eq!(generate(26, 1, 5), None);
+    }
+
+    #[test]
+    fn test_names() {
+        let mut rng = rng(0);
+        let mut names = Names::default();
+        names.reserve("a");
+
+        let mut generated: Vec<String> = (0..25)
+            .map(|_| names.fresh(&mut rng, 1, LOWERCASE))
+            .collect();
+        generated.sort();
+        generated.dedup();
+        assert_eq!(generated.len(), 25);
+        assert!(!generated.contains(&"a".to_string()));
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// One of the four cardinal directions. North points towards smaller `y`, i.e. up the rows of the
+/// input, and east points towards larger `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Direction {
+    /// All directions in clockwise order, starting from north.
+    pub const ALL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::N => Direction::S,
+            Direction::E => Direction::W,
+            Direction::S => Direction::N,
+            Direction::W => Direction::E,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::N => Direction::W,
+            Direction::E => Direction::N,
+            Direction::S => Direction::E,
+            Direction::W => Direction::S,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::N => Direction::E,
+            Direction::E => Direction::S,
+            Direction::S => Direction::W,
+            Direction::W => Direction::N,
+        }
+    }
+}
+
+/// Position on a grid, where `x` is the column and `y` is the row. Coordinates are signed, so
+/// that they can also describe positions outside of a grid, e.g. in an infinitely repeating
+/// layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Coord {
+    pub const fn new(x: i64, y: i64) -> Coord {
+        Coord { x, y }
+    }
+
+    pub fn north(self) -> Coord {
+        Coord::new(self.x, self.y - 1)
+    }
+
+    pub fn south(self) -> Coord {
+        Coord::new(self.x, self.y + 1)
+    }
+
+    pub fn east(self) -> Coord {
+        Coord::new(self.x + 1, self.y)
+    }
+
+    pub fn west(self) -> Coord {
+        Coord::new(self.x - 1, self.y)
+    }
+
+    /// The neighbouring coordinate in the given direction.
+    pub fn step(self, direction: Direction) -> Coord {
+        self.step_by(direction, 1)
+    }
+
+    /// The coordinate `distance` steps away in the given direction.
+    pub fn step_by(self, direction: Direction, distance: i64) -> Coord {
+        match direction {
+            Direction::N => Coord::new(self.x, self.y - distance),
+            Direction::E => Coord::new(self.x + distance, self.y),
+            Direction::S => Coord::new(self.x, self.y + distance),
+            Direction::W => Coord::new(self.x - distance, self.y),
+        }
+    }
+
+    /// The 4 orthogonal neighbours, in clockwise order starting from north.
+    pub fn neighbours4(self) -> [Coord; 4] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+
+    /// The 8 orthogonal and diagonal neighbours, in clockwise order starting from north.
+    pub fn neighbours8(self) -> [Coord; 8] {
+        let Coord { x, y } = self;
+        [
+            Coord::new(x, y - 1),
+            Coord::new(x + 1, y - 1),
+            Coord::new(x + 1, y),
+            Coord::new(x + 1, y + 1),
+            Coord::new(x, y + 1),
+            Coord::new(x - 1, y + 1),
+            Coord::new(x - 1, y),
+            Coord::new(x - 1, y - 1),
+        ]
+    }
+
+    pub fn manhattan_distance(self, other: Coord) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(i64, i64)> for Coord {
+    fn from((x, y): (i64, i64)) -> Coord {
+        Coord { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((x, y): (usize, usize)) -> Coord {
+        Coord::new(x as i64, y as i64)
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
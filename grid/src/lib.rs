@@ -0,0 +1,345 @@
+//! Dense two-dimensional grid shared by the days whose input is a map of characters, together with
+//! the coordinates and directions used to move around it.
+
+use common::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+mod coord;
+
+pub use coord::{Coord, Direction};
+
+/// Rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells, given row by row.
+    ///
+    /// Panics if the number of cells doesn't match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "Grid cells don't match its dimensions"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Create a grid from a list of rows.
+    ///
+    /// Panics if the rows are not all of the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Grid<T> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for row in rows {
+            if height == 0 {
+                width = row.len();
+            }
+            assert_eq!(row.len(), width, "Grid rows must be of the same length");
+            cells.extend(row);
+            height += 1;
+        }
+
+        Grid::new(width, height, cells)
+    }
+
+    /// Parse a map of characters, one row per line, converting each character with `tile`. If
+    /// `tile` returns an error, it is reported at the position of the character as what was
+    /// expected there.
+    ///
+    /// Fails if the input is empty or if the rows are not all of the same length.
+    pub fn parse<F>(input: &str, mut tile: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Coord, char) -> Result<T, &'static str>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let row_width = line.chars().count();
+            if y == 0 {
+                width = row_width;
+            } else if row_width != width {
+                let expected = format!("a row of {width} tiles");
+                return Err(ParseError::at(input, line, expected));
+            }
+
+            for (x, (offset, ch)) in line.char_indices().enumerate() {
+                let cell = tile(Coord::from((x, y)), ch)
+                    .map_err(|expected| ParseError::at(input, &line[offset..], expected))?;
+                cells.push(cell);
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(ParseError::at_offset(input, 0, "a row of tiles"));
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        (0..self.width as i64).contains(&coord.x) && (0..self.height as i64).contains(&coord.y)
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        if self.contains(coord) {
+            Some(coord.y as usize * self.width + coord.x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The cell at the given coordinate, or `None` if it is outside of the grid.
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Coordinates of all the cells, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord::from((x, y))))
+    }
+
+    /// All the cells with their coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(&self.cells)
+    }
+
+    /// Coordinate of the first cell, row by row, that satisfies the predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(coord, _)| coord)
+    }
+
+    /// The orthogonal neighbours of the coordinate that are inside of the grid, in clockwise order
+    /// starting from north.
+    pub fn neighbours4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord
+            .neighbours4()
+            .into_iter()
+            .filter(|&coord| self.contains(coord))
+    }
+
+    /// The orthogonal and diagonal neighbours of the coordinate that are inside of the grid, in
+    /// clockwise order starting from north.
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord
+            .neighbours8()
+            .into_iter()
+            .filter(|&coord| self.contains(coord))
+    }
+
+    /// Panics if the row is outside of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_exact_mut(self.width.max(1))
+    }
+
+    /// Panics if the column is outside of the grid.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width, "Column {x} is outside of the grid");
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Create a grid of the same dimensions by converting each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with all the cells set to the same value.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Swap the rows and the columns.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_rows(self.columns().map(|column| column.cloned().collect()))
+    }
+
+    /// Rotate by 90 degrees clockwise, so that the first column becomes the first row.
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid::from_rows(self.columns().map(|column| column.rev().cloned().collect()))
+    }
+
+    /// Rotate by 90 degrees counterclockwise, so that the last column becomes the first row.
+    pub fn rotate_counterclockwise(&self) -> Grid<T> {
+        Grid::from_rows(
+            (0..self.width)
+                .rev()
+                .map(|x| self.column(x).cloned().collect()),
+        )
+    }
+
+    /// Repeat the grid `across` times horizontally and `down` times vertically.
+    pub fn tiled(&self, across: usize, down: usize) -> Grid<T> {
+        let rows = (0..down).flat_map(|_| self.rows());
+        Grid::from_rows(rows.map(|row| (0..across).flat_map(|_| row.iter().cloned()).collect()))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the coordinate is outside of the grid.
+    fn index(&self, coord: Coord) -> &T {
+        match self.index_of(coord) {
+            Some(i) => &self.cells[i],
+            None => panic!("Coordinate {coord} is outside of the grid"),
+        }
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    /// Panics if the coordinate is outside of the grid.
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        match self.index_of(coord) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("Coordinate {coord} is outside of the grid"),
+        }
+    }
+}
+
+/// Print the grid one row per line, with the cells of a row printed next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn parse_chars(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(input, |_, ch| match ch {
+            '.' | '#' => Ok(ch),
+            _ => Err("one of \".#\""),
+        })
+    }
+
+    #[test]
+    fn parse() {
+        let input = indoc! {"
+            #..
+            .#.
+        "};
+
+        let grid = parse_chars(input).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(1, 1)], '#');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.position(|&ch| ch == '#'), Some(Coord::new(0, 0)));
+        assert_eq!(grid.to_string(), input);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = parse_chars("#..\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "one of \".#\"");
+
+        let error = parse_chars("#..\n.#\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a row of 3 tiles");
+
+        let error = parse_chars("").unwrap_err();
+        assert_eq!(error.expected, "a row of tiles");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::filled(3, 2, 0);
+
+        let corner: Vec<Coord> = grid.neighbours4(Coord::new(0, 0)).collect();
+        assert_eq!(corner, [Coord::new(1, 0), Coord::new(0, 1)]);
+
+        let edge: Vec<Coord> = grid.neighbours8(Coord::new(1, 1)).collect();
+        assert_eq!(
+            edge,
+            [
+                Coord::new(1, 0),
+                Coord::new(2, 0),
+                Coord::new(2, 1),
+                Coord::new(0, 1),
+                Coord::new(0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn views() {
+        let grid = Grid::from_rows([vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+        assert_eq!(
+            grid.transpose(),
+            Grid::from_rows([vec![1, 4], vec![2, 5], vec![3, 6]])
+        );
+        assert_eq!(
+            grid.rotate_clockwise(),
+            Grid::from_rows([vec![4, 1], vec![5, 2], vec![6, 3]])
+        );
+        assert_eq!(
+            grid.rotate_counterclockwise(),
+            Grid::from_rows([vec![3, 6], vec![2, 5], vec![1, 4]])
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(
+            grid.tiled(2, 2),
+            Grid::from_rows([
+                vec![1, 2, 3, 1, 2, 3],
+                vec![4, 5, 6, 4, 5, 6],
+                vec![1, 2, 3, 1, 2, 3],
+                vec![4, 5, 6, 4, 5, 6],
+            ])
+        );
+    }
+}
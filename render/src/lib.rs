@@ -0,0 +1,337 @@
+//! Rendering of puzzle states and solutions, e.g. a path through a map, into text or images.
+//!
+//! Anything that implements [`Render`] draws itself onto a [`Canvas`], a grid of coloured
+//! characters. The canvas can then be written to any `io::Write` in one of the [`Format`]s: text
+//! with or without ANSI colours, or an image where each character becomes a square of pixels.
+
+use grid::Grid;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Size in pixels of the square drawn for each cell in raster images.
+const PIXELS_PER_CELL: usize = 4;
+
+/// Size in pixels of each cell in SVG images, fitting a character of a monospace font.
+const SVG_CELL_WIDTH: usize = 10;
+const SVG_CELL_HEIGHT: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    White,
+}
+
+impl Color {
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0x20, 0x20, 0x20],
+            Color::Red => [0xd0, 0x30, 0x30],
+            Color::Green => [0x30, 0xb0, 0x40],
+            Color::Yellow => [0xe0, 0xc0, 0x30],
+            Color::Blue => [0x30, 0x60, 0xd0],
+            Color::White => [0xf0, 0xf0, 0xf0],
+        }
+    }
+
+    fn hex(self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    /// Offset of the colour in the ANSI colour codes, e.g. 31 is red foreground and 41 is red
+    /// background.
+    fn ansi_offset(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::White => 7,
+        }
+    }
+}
+
+/// A single character of the canvas, with optional foreground and background colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(symbol: char) -> Cell {
+        Cell {
+            symbol,
+            fg: None,
+            bg: None,
+        }
+    }
+
+    pub fn fg(self, color: Color) -> Cell {
+        Cell {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bg(self, color: Color) -> Cell {
+        Cell {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    /// Colour of the cell in raster images. Uncoloured cells are white if they are empty, i.e. a
+    /// space or a '.', and black otherwise.
+    fn pixel_color(&self) -> Color {
+        self.bg.or(self.fg).unwrap_or(match self.symbol {
+            ' ' | '.' => Color::White,
+            _ => Color::Black,
+        })
+    }
+}
+
+pub type Canvas = Grid<Cell>;
+
+/// Something that can be drawn onto a canvas.
+pub trait Render {
+    fn render(&self) -> Canvas;
+}
+
+impl Render for Canvas {
+    fn render(&self) -> Canvas {
+        self.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Text coloured with ANSI escape codes, for the terminal.
+    Ansi,
+    /// Text without colours.
+    Plain,
+    /// Binary PPM image.
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl Format {
+    /// Guess the format from the extension of the path. Paths without a known image extension are
+    /// written as plain text, unless the extension is `ansi`.
+    pub fn from_path(path: &Path) -> Format {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ansi") => Format::Ansi,
+            Some("ppm") => Format::Ppm,
+            Some("png") => Format::Png,
+            Some("svg") => Format::Svg,
+            _ => Format::Plain,
+        }
+    }
+}
+
+/// Render the item and write it to the writer in the given format.
+pub fn write<R: Render + ?Sized>(item: &R, format: Format, writer: impl Write) -> io::Result<()> {
+    let canvas = item.render();
+    let mut writer = BufWriter::new(writer);
+
+    match format {
+        Format::Ansi => write_text(&canvas, true, &mut writer)?,
+        Format::Plain => write_text(&canvas, false, &mut writer)?,
+        Format::Ppm => write_ppm(&canvas, &mut writer)?,
+        Format::Png => write_png(&canvas, &mut writer)?,
+        Format::Svg => write_svg(&canvas, &mut writer)?,
+    }
+
+    writer.flush()
+}
+
+/// Render the item and print it to stdout with ANSI colours.
+pub fn print<R: Render + ?Sized>(item: &R) -> io::Result<()> {
+    write(item, Format::Ansi, io::stdout().lock())
+}
+
+/// Render the item and save it to the file, in the format given by the extension of the path.
+pub fn save<R: Render + ?Sized>(item: &R, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    write(item, Format::from_path(path), File::create(path)?)
+}
+
+fn write_text(canvas: &Canvas, ansi: bool, writer: &mut impl Write) -> io::Result<()> {
+    for row in canvas.rows() {
+        for cell in row {
+            let mut codes = Vec::new();
+            if ansi {
+                codes.extend(cell.fg.map(|color| 30 + color.ansi_offset()));
+                codes.extend(cell.bg.map(|color| 40 + color.ansi_offset()));
+            }
+
+            if codes.is_empty() {
+                write!(writer, "{}", cell.symbol)?;
+            } else {
+                let codes: Vec<String> = codes.iter().map(u8::to_string).collect();
+                write!(writer, "\x1b[{}m{}\x1b[0m", codes.join(";"), cell.symbol)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Pixels of the raster image, as RGB bytes row by row.
+fn pixels(canvas: &Canvas) -> Vec<u8> {
+    let mut pixels = Vec::new();
+
+    for row in canvas.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|cell| [cell.pixel_color().rgb(); PIXELS_PER_CELL])
+            .flatten()
+            .collect();
+
+        for _ in 0..PIXELS_PER_CELL {
+            pixels.extend(&line);
+        }
+    }
+    pixels
+}
+
+fn write_ppm(canvas: &Canvas, writer: &mut impl Write) -> io::Result<()> {
+    let (width, height) = (
+        canvas.width() * PIXELS_PER_CELL,
+        canvas.height() * PIXELS_PER_CELL,
+    );
+
+    write!(writer, "P6\n{width} {height}\n255\n")?;
+    writer.write_all(&pixels(canvas))
+}
+
+fn write_png(canvas: &Canvas, writer: &mut impl Write) -> io::Result<()> {
+    let (width, height) = (
+        canvas.width() * PIXELS_PER_CELL,
+        canvas.height() * PIXELS_PER_CELL,
+    );
+
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut png_writer = encoder.write_header()?;
+    png_writer.write_image_data(&pixels(canvas))?;
+    png_writer.finish()?;
+    Ok(())
+}
+
+fn write_svg(canvas: &Canvas, writer: &mut impl Write) -> io::Result<()> {
+    let (w, h) = (SVG_CELL_WIDTH, SVG_CELL_HEIGHT);
+    let (width, height) = (canvas.width() * w, canvas.height() * h);
+
+    write!(writer, r#"<svg xmlns="http://www.w3.org/2000/svg" "#)?;
+    writeln!(
+        writer,
+        r#"width="{width}" height="{height}" font-family="monospace" font-size="{h}">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        Color::White.hex()
+    )?;
+
+    for (coord, cell) in canvas.iter() {
+        let (x, y) = (coord.x as usize * w, coord.y as usize * h);
+
+        if let Some(bg) = cell.bg {
+            writeln!(
+                writer,
+                r#"<rect x="{x}" y="{y}" width="{w}" height="{h}" fill="{}"/>"#,
+                bg.hex()
+            )?;
+        }
+
+        let symbol = match cell.symbol {
+            ' ' => continue,
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            symbol => symbol.to_string(),
+        };
+        let fill = cell.fg.unwrap_or(Color::Black).hex();
+        let baseline = y + h * 4 / 5;
+        writeln!(
+            writer,
+            r#"<text x="{x}" y="{baseline}" fill="{fill}">{symbol}</text>"#
+        )?;
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> Canvas {
+        Grid::from_rows([
+            vec![Cell::new('#'), Cell::new('.').fg(Color::Red)],
+            vec![Cell::new('<').bg(Color::Blue), Cell::new(' ')],
+        ])
+    }
+
+    fn to_bytes(format: Format) -> Vec<u8> {
+        let mut output = Vec::new();
+        write(&canvas(), format, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(to_bytes(Format::Plain), b"#.\n< \n");
+        assert_eq!(
+            to_bytes(Format::Ansi),
+            b"#\x1b[31m.\x1b[0m\n\x1b[44m<\x1b[0m \n"
+        );
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = to_bytes(Format::Ppm);
+        let header = b"P6\n8 8\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 8 * 8 * 3);
+        assert_eq!(pixels[..3], Color::Black.rgb());
+        assert_eq!(pixels[4 * 3..4 * 3 + 3], Color::Red.rgb());
+        assert_eq!(pixels[(4 * 8) * 3..(4 * 8) * 3 + 3], Color::Blue.rgb());
+    }
+
+    #[test]
+    fn png() {
+        let png = to_bytes(Format::Png);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn svg() {
+        let svg = String::from_utf8(to_bytes(Format::Svg)).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r##"<text x="10" y="12" fill="#d03030">.</text>"##));
+        assert!(svg.contains(r##"<rect x="0" y="16" width="10" height="16" fill="#3060d0"/>"##));
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(Format::from_path(Path::new("path.png")), Format::Png);
+        assert_eq!(Format::from_path(Path::new("out/path.svg")), Format::Svg);
+        assert_eq!(Format::from_path(Path::new("path.txt")), Format::Plain);
+    }
+}
@@ -0,0 +1,341 @@
+//! Stepping through the simulations of days 14, 16, 20 and 22 in the terminal: forwards, backwards
+//! and up to a breakpoint, with the fields of the state shown next to its picture.
+//!
+//! [`Stepper`] keeps the snapshots for stepping back, and [`Breakpoint`] is a condition on the
+//! fields of a snapshot. Both are independent of the terminal, which is handled by [`tui`].
+
+use common::{Simulation, Snapshot};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+pub mod tui;
+
+/// Number of the most recent snapshots kept for stepping back. Older snapshots are dropped.
+pub const HISTORY_LEN: usize = 1000;
+
+/// A simulation together with the snapshots of its last steps.
+pub struct Stepper<S: Simulation> {
+    simulation: S,
+    /// Snapshots of the last steps of the simulation, the oldest first
+    history: VecDeque<S::Snapshot>,
+    /// Step number of the oldest snapshot in the history
+    first_step: usize,
+    /// Index in the history of the shown snapshot
+    position: usize,
+    /// Whether the simulation is over
+    finished: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Stopped at a snapshot matching the breakpoint.
+    Breakpoint,
+    /// The simulation is over.
+    Finished,
+    /// Made the maximum number of steps without reaching the breakpoint.
+    Limit,
+}
+
+impl<S: Simulation> Stepper<S> {
+    pub fn new(simulation: S) -> Stepper<S> {
+        let snapshot = simulation.snapshot();
+        Stepper {
+            simulation,
+            history: VecDeque::from([snapshot]),
+            first_step: 0,
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// The shown snapshot.
+    pub fn current(&self) -> &S::Snapshot {
+        &self.history[self.position]
+    }
+
+    /// Number of steps from the start of the simulation to the shown snapshot.
+    pub fn step_number(&self) -> usize {
+        self.first_step + self.position
+    }
+
+    /// Whether the shown snapshot is the last state of a simulation which is over.
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.position + 1 == self.history.len()
+    }
+
+    /// Show the next snapshot, running the next step of the simulation if it hasn't been run yet.
+    /// Return `false` if the simulation is over.
+    pub fn forward(&mut self) -> bool {
+        if self.position + 1 < self.history.len() {
+            self.position += 1;
+            return true;
+        }
+        if self.finished || !self.simulation.step() {
+            self.finished = true;
+            return false;
+        }
+
+        self.history.push_back(self.simulation.snapshot());
+        if self.history.len() > HISTORY_LEN {
+            self.history.pop_front();
+            self.first_step += 1;
+        } else {
+            self.position += 1;
+        }
+        true
+    }
+
+    /// Show the previous snapshot. Return `false` if it is no longer kept, or if the shown
+    /// snapshot is the start of the simulation.
+    pub fn back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        true
+    }
+
+    /// Step forward until a snapshot stops the run, or for at most `max_steps` steps.
+    pub fn run_until(
+        &mut self,
+        mut stop: impl FnMut(&S::Snapshot) -> bool,
+        max_steps: usize,
+    ) -> RunOutcome {
+        for _ in 0..max_steps {
+            if !self.forward() {
+                return RunOutcome::Finished;
+            }
+            if stop(self.current()) {
+                return RunOutcome::Breakpoint;
+            }
+        }
+        RunOutcome::Limit
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Operator {
+    /// Operators with their symbols. Longer symbols come first, so that `<=` is not read as `<`.
+    const SYMBOLS: [(&'static str, Operator); 7] = [
+        ("==", Operator::Eq),
+        ("!=", Operator::Ne),
+        ("<=", Operator::Le),
+        (">=", Operator::Ge),
+        ("=", Operator::Eq),
+        ("<", Operator::Lt),
+        (">", Operator::Gt),
+    ];
+
+    fn symbol(self) -> &'static str {
+        Operator::SYMBOLS
+            .iter()
+            .find(|(_, op)| *op == self)
+            .map(|(symbol, _)| *symbol)
+            .unwrap()
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Operator::Eq => ordering.is_eq(),
+            Operator::Ne => ordering.is_ne(),
+            Operator::Lt => ordering.is_lt(),
+            Operator::Le => ordering.is_le(),
+            Operator::Gt => ordering.is_gt(),
+            Operator::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+/// Condition on a field of the snapshots, e.g. `load > 100` or `last == rk --high--> gh`.
+///
+/// The value is everything after the operator. Values which are both numbers are compared as
+/// numbers, and any other values as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoint {
+    field: String,
+    op: Operator,
+    value: String,
+}
+
+impl Breakpoint {
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Whether the snapshot has the field and its value meets the condition.
+    pub fn matches(&self, snapshot: &impl Snapshot) -> bool {
+        let fields = snapshot.fields();
+        let Some((_, value)) = fields.iter().find(|(name, _)| *name == self.field) else {
+            return false;
+        };
+
+        let ordering = match (value.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(value), Ok(expected)) => value.partial_cmp(&expected),
+            _ => Some(value.as_str().cmp(&self.value)),
+        };
+        ordering.is_some_and(|ordering| self.op.holds(ordering))
+    }
+}
+
+impl FromStr for Breakpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Breakpoint, String> {
+        let (index, symbol, op) = s
+            .char_indices()
+            .find_map(|(index, _)| {
+                Operator::SYMBOLS
+                    .iter()
+                    .find(|(symbol, _)| s[index..].starts_with(symbol))
+                    .map(|&(symbol, op)| (index, symbol, op))
+            })
+            .ok_or("Expected a condition like `load > 100`")?;
+
+        let field = s[..index].trim();
+        let value = s[index + symbol.len()..].trim();
+        if field.is_empty() || value.is_empty() {
+            return Err(format!("Expected a field and a value around `{symbol}`"));
+        }
+
+        Ok(Breakpoint {
+            field: field.to_string(),
+            op,
+            value: value.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.field, self.op.symbol(), self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day14_tilted_rocks::{parser::parse_input, SpinCycle};
+
+    /// Counter which stops after the given number of steps.
+    struct Counter {
+        count: usize,
+        end: usize,
+    }
+
+    #[derive(Clone)]
+    struct Count(usize);
+
+    impl Snapshot for Count {
+        fn fields(&self) -> Vec<(String, String)> {
+            vec![
+                ("count".to_string(), self.0.to_string()),
+                (
+                    "parity".to_string(),
+                    ["even", "odd"][self.0 % 2].to_string(),
+                ),
+            ]
+        }
+    }
+
+    impl Simulation for Counter {
+        type Snapshot = Count;
+
+        fn snapshot(&self) -> Count {
+            Count(self.count)
+        }
+
+        fn step(&mut self) -> bool {
+            if self.count == self.end {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+    }
+
+    #[test]
+    fn test_stepper() {
+        let mut stepper = Stepper::new(Counter { count: 0, end: 3 });
+        assert!(!stepper.back());
+
+        assert!(stepper.forward());
+        assert!(stepper.forward());
+        assert!(stepper.back());
+        assert_eq!(stepper.current().0, 1);
+        assert_eq!(stepper.step_number(), 1);
+
+        assert_eq!(stepper.run_until(|_| false, 10), RunOutcome::Finished);
+        assert_eq!(stepper.current().0, 3);
+        assert!(stepper.is_finished());
+        assert!(!stepper.forward());
+
+        assert!(stepper.back());
+        assert!(!stepper.is_finished());
+    }
+
+    #[test]
+    fn test_stepper_history() {
+        let mut stepper = Stepper::new(Counter {
+            count: 0,
+            end: usize::MAX,
+        });
+        let breakpoint: Breakpoint = "count >= 1500".parse().unwrap();
+
+        let outcome = stepper.run_until(|snapshot| breakpoint.matches(snapshot), 1000);
+        assert_eq!(outcome, RunOutcome::Limit);
+        let outcome = stepper.run_until(|snapshot| breakpoint.matches(snapshot), 1000);
+        assert_eq!(outcome, RunOutcome::Breakpoint);
+        assert_eq!(stepper.step_number(), 1500);
+
+        while stepper.back() {}
+        assert_eq!(stepper.step_number(), 1501 - HISTORY_LEN);
+        assert_eq!(stepper.current().0, 1501 - HISTORY_LEN);
+    }
+
+    #[test]
+    fn test_breakpoint() {
+        let breakpoint: Breakpoint = " count<=10".parse().unwrap();
+        assert_eq!(breakpoint.to_string(), "count <= 10");
+        assert!(breakpoint.matches(&Count(9)));
+        assert!(!breakpoint.matches(&Count(11)));
+
+        // Numbers are not compared as text
+        let breakpoint: Breakpoint = "count > 9".parse().unwrap();
+        assert!(breakpoint.matches(&Count(10)));
+
+        let breakpoint: Breakpoint = "parity = odd".parse().unwrap();
+        assert!(breakpoint.matches(&Count(3)));
+        let breakpoint: Breakpoint = "parity != odd".parse().unwrap();
+        assert!(!breakpoint.matches(&Count(3)));
+
+        let breakpoint: Breakpoint = "missing == 1".parse().unwrap();
+        assert!(!breakpoint.matches(&Count(1)));
+
+        assert!("count".parse::<Breakpoint>().is_err());
+        assert!("== 1".parse::<Breakpoint>().is_err());
+        assert!("count >".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn test_day_breakpoint() {
+        let input = "O.\n.O\n";
+        let platform = parse_input(input).unwrap();
+        let mut stepper = Stepper::new(SpinCycle::new(&platform));
+
+        let breakpoint: Breakpoint = "last_tilt == E".parse().unwrap();
+        let outcome = stepper.run_until(|snapshot| breakpoint.matches(snapshot), 10);
+        assert_eq!(outcome, RunOutcome::Breakpoint);
+        assert_eq!(stepper.step_number(), 4);
+    }
+}
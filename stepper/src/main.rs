@@ -0,0 +1,61 @@
+//! Step through the simulation of a day in the terminal.
+//!
+//! By default the input is read from the `input` file in the directory of the crate of the day, so
+//! the stepper can be invoked from anywhere in the workspace.
+
+use clap::Parser;
+use common::{day_dir, InputSource};
+use grid::{Coord, Direction};
+use std::error::Error;
+use std::path::PathBuf;
+use stepper::{tui, Breakpoint};
+
+#[derive(Parser)]
+#[command(about = "Step through the simulation of a day in the terminal")]
+struct Cli {
+    /// Day to simulate: 14 (spin cycles), 16 (beams), 20 (pulses) or 22 (falling bricks).
+    #[arg(short, long)]
+    day: u8,
+
+    /// Path to the input file. Defaults to the `input` file of the day.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Condition to stop at when continuing, e.g. `load > 100`. It can also be set in the stepper.
+    #[arg(short, long = "break")]
+    breakpoint: Option<Breakpoint>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    let path = match cli.input {
+        Some(path) => path,
+        None => day_dir(cli.day)?.join("input"),
+    };
+    let input = InputSource::File(path).read_to_string()?;
+    let title = format!("Day {}", cli.day);
+
+    match cli.day {
+        14 => {
+            let platform = day14_tilted_rocks::parser::parse_input(&input)?;
+            let simulation = day14_tilted_rocks::SpinCycle::new(&platform);
+            tui::run(&title, simulation, cli.breakpoint)?;
+        },
+        16 => {
+            let layout = day16_beams::Layout::load_from_input(&input)?;
+            let simulation = day16_beams::Beams::new(&layout, Coord::new(0, 0), Direction::E);
+            tui::run(&title, simulation, cli.breakpoint)?;
+        },
+        20 => {
+            let cfg = day20_pulse_logic::parser::parse_input(&input)?;
+            tui::run(&title, day20_pulse_logic::Pulses::new(&cfg), cli.breakpoint)?;
+        },
+        22 => {
+            let bricks = day22_bricks::parser::parse_input(&input)?;
+            tui::run(&title, day22_bricks::Settling::new(&bricks), cli.breakpoint)?;
+        },
+        _ => return Err(format!("Day {} has no simulation to step through", cli.day).into()),
+    }
+    Ok(())
+}
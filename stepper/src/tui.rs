@@ -0,0 +1,315 @@
+//! Terminal user interface of the stepper: the picture of the shown snapshot, its fields beside
+//! it, and a status line at the bottom, redrawn after every key press.
+
+use crate::{Breakpoint, RunOutcome, Stepper};
+use common::{Simulation, Snapshot};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use grid::{Coord, Grid};
+use render::{Format, Render};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Number of steps between checks for a key press while running to a breakpoint.
+const STEPS_PER_CHECK: usize = 10_000;
+
+/// Number of columns or rows the picture moves when scrolling.
+const SCROLL: usize = 10;
+
+const HELP: &str = "→/n step  ←/p back  c continue  b breakpoint  hjkl scroll  q quit";
+
+/// Puts the terminal into raw mode on the alternate screen, and restores it when dropped, also on
+/// errors and panics.
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// What the keys do.
+enum Mode {
+    Normal,
+    /// Typing the breakpoint, with the text so far
+    Prompt(String),
+}
+
+struct App<S: Simulation> {
+    title: String,
+    stepper: Stepper<S>,
+    breakpoint: Option<Breakpoint>,
+    /// Column and row of the picture shown in the top left corner
+    scroll: (usize, usize),
+    mode: Mode,
+    /// Result of the last command
+    message: String,
+}
+
+/// Step through the simulation in the terminal until the user quits.
+pub fn run<S>(title: &str, simulation: S, breakpoint: Option<Breakpoint>) -> io::Result<()>
+where
+    S: Simulation,
+    S::Snapshot: Render,
+{
+    let mut app = App {
+        title: title.to_string(),
+        stepper: Stepper::new(simulation),
+        breakpoint,
+        scroll: (0, 0),
+        mode: Mode::Normal,
+        message: String::new(),
+    };
+
+    let _terminal = Terminal::enter()?;
+    let mut stdout = io::stdout();
+    loop {
+        app.draw(&mut stdout)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Press && !app.handle_key(key, &mut stdout)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Whether a key was pressed, without waiting for one.
+fn key_pressed() -> io::Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Cut the text to the width, counting characters.
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+impl<S> App<S>
+where
+    S: Simulation,
+    S::Snapshot: Render,
+{
+    /// Act on the key. Return `false` if the user quits.
+    fn handle_key(&mut self, key: KeyEvent, stdout: &mut impl Write) -> io::Result<bool> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(false);
+        }
+
+        if let Mode::Prompt(text) = &mut self.mode {
+            match key.code {
+                KeyCode::Char(ch) => text.push(ch),
+                KeyCode::Backspace => {
+                    text.pop();
+                },
+                KeyCode::Enter => {
+                    let text = text.trim().to_string();
+                    self.mode = Mode::Normal;
+                    self.set_breakpoint(&text);
+                },
+                KeyCode::Esc => self.mode = Mode::Normal,
+                _ => {},
+            }
+            return Ok(true);
+        }
+
+        self.message.clear();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Char('n') | KeyCode::Char(' ') | KeyCode::Right => self.forward(),
+            KeyCode::Char('p') | KeyCode::Left => self.back(),
+            KeyCode::Char('c') => self.continue_run(stdout)?,
+            KeyCode::Char('b') => self.mode = Mode::Prompt(String::new()),
+            KeyCode::Char('h') => self.scroll.0 = self.scroll.0.saturating_sub(SCROLL),
+            KeyCode::Char('l') => self.scroll.0 += SCROLL,
+            KeyCode::Char('k') => self.scroll.1 = self.scroll.1.saturating_sub(SCROLL),
+            KeyCode::Char('j') => self.scroll.1 += SCROLL,
+            _ => {},
+        }
+        Ok(true)
+    }
+
+    fn forward(&mut self) {
+        if !self.stepper.forward() {
+            self.message = "The simulation is over".to_string();
+        }
+    }
+
+    fn back(&mut self) {
+        if !self.stepper.back() {
+            self.message = "No earlier snapshot is kept".to_string();
+        }
+    }
+
+    /// Set the breakpoint from the text typed by the user, or clear it if the text is empty.
+    fn set_breakpoint(&mut self, text: &str) {
+        if text.is_empty() {
+            self.breakpoint = None;
+            self.message = "Breakpoint cleared".to_string();
+            return;
+        }
+
+        match text.parse::<Breakpoint>() {
+            Ok(breakpoint) => {
+                let fields = self.stepper.current().fields();
+                if fields.iter().any(|(name, _)| name == breakpoint.field()) {
+                    self.message = format!("Breakpoint set: {breakpoint}");
+                    self.breakpoint = Some(breakpoint);
+                } else {
+                    let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+                    self.message = format!("No such field, expected one of: {}", names.join(", "));
+                }
+            },
+            Err(err) => self.message = err,
+        }
+    }
+
+    /// Run to the breakpoint, or to the end of the simulation if there is no breakpoint, until a
+    /// key is pressed.
+    fn continue_run(&mut self, stdout: &mut impl Write) -> io::Result<()> {
+        let breakpoint = self.breakpoint.clone();
+        let start = self.stepper.step_number();
+
+        loop {
+            let outcome = self.stepper.run_until(
+                |snapshot| breakpoint.as_ref().is_some_and(|bp| bp.matches(snapshot)),
+                STEPS_PER_CHECK,
+            );
+            match outcome {
+                RunOutcome::Breakpoint => {
+                    self.message = "Stopped at the breakpoint".to_string();
+                    break;
+                },
+                RunOutcome::Finished => {
+                    self.message = "The simulation is over".to_string();
+                    break;
+                },
+                RunOutcome::Limit if key_pressed()? => {
+                    self.message = "Interrupted".to_string();
+                    break;
+                },
+                RunOutcome::Limit => {
+                    let steps = self.stepper.step_number() - start;
+                    self.message = format!("Running, {steps} steps so far, press any key to stop");
+                    self.draw(stdout)?;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let (columns, rows) = (columns as usize, rows as usize);
+        queue!(stdout, terminal::Clear(ClearType::All))?;
+
+        let snapshot = self.stepper.current();
+        let end = if self.stepper.is_finished() {
+            " (end)"
+        } else {
+            ""
+        };
+        let breakpoint = self
+            .breakpoint
+            .as_ref()
+            .map_or("none".to_string(), Breakpoint::to_string);
+        let header = format!(
+            "{}  step {}{end}  breakpoint: {breakpoint}",
+            self.title,
+            self.stepper.step_number()
+        );
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            Print(truncate(&header, columns))
+        )?;
+
+        // Fields on the right, as wide as the longest one but at most half of the screen
+        let fields: Vec<String> = snapshot
+            .fields()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+        let fields_width = fields
+            .iter()
+            .map(|field| field.chars().count())
+            .max()
+            .unwrap_or(0);
+        let fields_width = fields_width.min(columns / 2);
+        let picture_width = columns.saturating_sub(fields_width + 2);
+        let picture_height = rows.saturating_sub(4);
+
+        for (row, field) in fields.iter().take(picture_height).enumerate() {
+            let x = (picture_width + 2) as u16;
+            let field = truncate(field, fields_width);
+            queue!(stdout, cursor::MoveTo(x, row as u16 + 2), Print(field))?;
+        }
+
+        for (row, line) in self
+            .picture(picture_width, picture_height)?
+            .iter()
+            .enumerate()
+        {
+            queue!(stdout, cursor::MoveTo(0, row as u16 + 2), Print(line))?;
+        }
+
+        let status = match &self.mode {
+            Mode::Normal => self.message.clone(),
+            Mode::Prompt(text) => format!("Break when (e.g. `load > 100`, empty to clear): {text}"),
+        };
+        let status_row = rows.saturating_sub(2) as u16;
+        queue!(
+            stdout,
+            cursor::MoveTo(0, status_row),
+            Print(truncate(&status, columns))
+        )?;
+        queue!(
+            stdout,
+            cursor::MoveTo(0, status_row + 1),
+            Print(truncate(HELP, columns))
+        )?;
+        stdout.flush()
+    }
+
+    /// Lines of the visible part of the picture, with ANSI colours.
+    fn picture(&self, width: usize, height: usize) -> io::Result<Vec<String>> {
+        let canvas = self.stepper.current().render();
+        let (left, top) = self.scroll;
+        let right = canvas.width().min(left + width);
+        let bottom = canvas.height().min(top + height);
+        if left >= right || top >= bottom {
+            return Ok(Vec::new());
+        }
+
+        let visible = Grid::from_rows((top..bottom).map(|y| {
+            (left..right)
+                .map(|x| canvas[Coord::from((x, y))])
+                .collect::<Vec<_>>()
+        }));
+        let mut text = Vec::new();
+        render::write(&visible, Format::Ansi, &mut text)?;
+
+        Ok(String::from_utf8_lossy(&text)
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+}